@@ -1,23 +1,43 @@
+use super::{MatchArm, Parameter, Span, Trivia};
+use crate::object::{Color, Regex};
 use std::{convert::From, rc::Rc};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Statement {
   Block(Vec<Statement>),
-  VariableDeclaration(String, Expression),
+  VariableDeclaration(String, Expression, Span),
+  ConstDeclaration(String, Expression, Span),
+  LocalDeclaration(String, Expression, usize),
+  LocalConstDeclaration(String, Expression, usize),
   Return(Expression),
   Expression(Expression),
-  WhileLoop(Expression, Box<Statement>),
-  ForLoop(String, Expression, Box<Statement>),
+  WhileLoop(Expression, Box<Statement>, Span),
+  ForLoop(String, Expression, Box<Statement>, Span),
+  Trivia(Trivia),
+  Error(Span),
   Null,
 }
 
 impl Statement {
+  pub fn variable(name: &str, value: Expression) -> Self {
+    Self::VariableDeclaration(name.to_owned(), value, Span::default())
+  }
+
+  pub fn constant(name: &str, value: Expression) -> Self {
+    Self::ConstDeclaration(name.to_owned(), value, Span::default())
+  }
+
   pub fn while_loop(condition: Expression, block: Self) -> Self {
-    Self::WhileLoop(condition, Box::new(block))
+    Self::WhileLoop(condition, Box::new(block), Span::default())
   }
 
   pub fn for_loop(string: &str, iterable: Expression, block: Self) -> Self {
-    Self::ForLoop(string.to_owned(), iterable, Box::new(block))
+    Self::ForLoop(
+      string.to_owned(),
+      iterable,
+      Box::new(block),
+      Span::default(),
+    )
   }
 }
 
@@ -27,20 +47,33 @@ impl From<Expression> for Statement {
   }
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
 pub enum Expression {
-  Id(String),
+  Id(String, Span),
+  Local(String, usize, usize),
   Integer(i64),
+  Float(f64),
   String(String),
+  Regex(Regex),
+  Color(Color),
   Boolean(bool),
-  Call(Box<Expression>, Vec<Expression>),
+  Call(Box<Expression>, Vec<Expression>, Span),
   Prefix(String, Box<Expression>),
   Infix(String, Box<Expression>, Box<Expression>),
-  Conditional(Box<Expression>, Box<Statement>, Option<Box<Statement>>),
-  Function(Option<String>, Vec<String>, Rc<Statement>),
+  Conditional(
+    Box<Expression>,
+    Box<Statement>,
+    Option<Box<Statement>>,
+    Span,
+  ),
+  Function(Option<String>, Vec<Parameter>, Rc<Statement>, Span),
   Array(Vec<Expression>),
   Hash(Vec<(Expression, Expression)>),
   Index(Box<Expression>, Box<Expression>),
+  Property(Box<Expression>, String),
+  Match(Box<Expression>, Vec<MatchArm>),
+  Spread(Box<Expression>),
+  Named(String, Box<Expression>),
   Null,
 }
 
@@ -48,8 +81,12 @@ impl Expression {
   pub const TRUE: Self = Self::Boolean(true);
   pub const FALSE: Self = Self::Boolean(false);
 
+  pub fn id(name: &str) -> Self {
+    Self::Id(name.to_owned(), Span::default())
+  }
+
   pub fn call(id: Expression, args: Vec<Expression>) -> Self {
-    Self::Call(Box::new(id), args)
+    Self::Call(Box::new(id), args, Span::default())
   }
 
   pub fn conditional(
@@ -60,7 +97,8 @@ impl Expression {
     Self::Conditional(
       Box::new(condition),
       Box::new(consequence),
-      alternative.and_then(|alt| Some(Box::new(alt))),
+      alternative.map(Box::new),
+      Span::default(),
     )
   }
 
@@ -76,7 +114,28 @@ impl Expression {
     Self::Index(Box::new(indexed), Box::new(index))
   }
 
+  pub fn property(object: Expression, name: &str) -> Self {
+    Self::Property(Box::new(object), name.to_owned())
+  }
+
+  pub fn r#match(value: Expression, arms: Vec<MatchArm>) -> Self {
+    Self::Match(Box::new(value), arms)
+  }
+
+  pub fn spread(expression: Expression) -> Self {
+    Self::Spread(Box::new(expression))
+  }
+
+  pub fn named(name: &str, expression: Expression) -> Self {
+    Self::Named(name.to_owned(), Box::new(expression))
+  }
+
   pub fn function(name: Option<&str>, args: Vec<String>, block: Statement) -> Self {
-    Self::Function(name.and_then(|n| Some(n.to_owned())), args, Rc::new(block))
+    Self::Function(
+      name.map(|n| n.to_owned()),
+      args.iter().map(|arg| Parameter::new(arg)).collect(),
+      Rc::new(block),
+      Span::default(),
+    )
   }
 }
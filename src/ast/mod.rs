@@ -1,6 +1,19 @@
 mod ast;
+mod parameter;
+mod pattern;
 mod precedence;
+mod span;
+#[cfg(test)]
+mod strip;
+mod trivia;
 
 pub use ast::Expression;
 pub use ast::Statement;
+pub use parameter::Parameter;
+pub use pattern::MatchArm;
+pub use pattern::Pattern;
 pub use precedence::Precedence;
+pub use span::Span;
+#[cfg(test)]
+pub use strip::without_spans;
+pub use trivia::Trivia;
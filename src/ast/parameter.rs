@@ -0,0 +1,51 @@
+use super::{Expression, Span};
+use std::fmt;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Parameter {
+  pub name: String,
+  pub default: Option<Expression>,
+  pub rest: bool,
+  pub span: Span,
+}
+
+impl Parameter {
+  pub fn new(name: &str) -> Self {
+    Self {
+      name: name.to_owned(),
+      default: None,
+      rest: false,
+      span: Span::default(),
+    }
+  }
+
+  pub fn with_default(name: &str, default: Expression) -> Self {
+    Self {
+      name: name.to_owned(),
+      default: Some(default),
+      rest: false,
+      span: Span::default(),
+    }
+  }
+
+  pub fn rest(name: &str) -> Self {
+    Self {
+      name: name.to_owned(),
+      default: None,
+      rest: true,
+      span: Span::default(),
+    }
+  }
+}
+
+impl fmt::Display for Parameter {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    if self.rest {
+      write!(f, "...{}", self.name)
+    } else if self.default.is_some() {
+      write!(f, "{}?", self.name)
+    } else {
+      write!(f, "{}", self.name)
+    }
+  }
+}
@@ -0,0 +1,65 @@
+use super::{Expression, Statement};
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Pattern {
+  Wildcard,
+  Literal(Expression),
+  Binding(String),
+  Array(Vec<Pattern>, Option<String>),
+  Hash(Vec<(String, Pattern)>),
+  Or(Vec<Pattern>),
+}
+
+impl Pattern {
+  pub fn is_irrefutable(&self) -> bool {
+    match self {
+      Self::Wildcard | Self::Binding(..) => true,
+      Self::Or(patterns) => patterns.iter().any(|pattern| pattern.is_irrefutable()),
+      _ => false,
+    }
+  }
+
+  pub fn bindings(&self) -> Vec<&str> {
+    let mut names = vec![];
+    self.collect_bindings(&mut names);
+    names.sort_unstable();
+    names.dedup();
+    names
+  }
+
+  fn collect_bindings<'a>(&'a self, names: &mut Vec<&'a str>) {
+    match self {
+      Self::Binding(name) => names.push(name),
+      Self::Array(patterns, rest) => {
+        patterns
+          .iter()
+          .for_each(|pattern| pattern.collect_bindings(names));
+        names.extend(rest.as_deref());
+      }
+      Self::Hash(entries) => entries
+        .iter()
+        .for_each(|(_, pattern)| pattern.collect_bindings(names)),
+      Self::Or(patterns) => patterns
+        .iter()
+        .for_each(|pattern| pattern.collect_bindings(names)),
+      Self::Wildcard | Self::Literal(..) => (),
+    }
+  }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct MatchArm {
+  pub pattern: Pattern,
+  pub guard: Option<Expression>,
+  pub body: Statement,
+}
+
+impl MatchArm {
+  pub fn new(pattern: Pattern, guard: Option<Expression>, body: Statement) -> Self {
+    Self {
+      pattern,
+      guard,
+      body,
+    }
+  }
+}
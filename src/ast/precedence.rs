@@ -2,23 +2,24 @@
 pub enum Precedence {
   Lowest,
   Assign,      // =
-  Equals,      // == | !=
+  Equals,      // == | != | =~
   LessGreater, // >  | < | <= | >=
   Sum,         // +  | -
   Product,     // *
   Prefix,      // -X or !X
   Call,        // myFunction(X)
-  Index,       // array[index]
+  Index,       // array[index] | object.property
 }
 
 impl Precedence {
   pub fn from(operator: &str) -> Self {
     match operator {
       "=" | "+=" | "-=" | "*=" | "/=" => Precedence::Assign,
-      "==" | "!=" => Precedence::Equals,
+      "==" | "!=" | "=~" => Precedence::Equals,
       ">" | "<" | ">=" | "<=" => Precedence::LessGreater,
       "+" | "-" => Precedence::Sum,
       "*" | "/" => Precedence::Product,
+      "." => Precedence::Index,
       _ => unreachable!(),
     }
   }
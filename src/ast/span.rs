@@ -0,0 +1,21 @@
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Span {
+  pub start: usize,
+  pub end: usize,
+  pub line: usize,
+  pub column: usize,
+}
+
+impl Span {
+  pub fn is_known(&self) -> bool {
+    self.line > 0
+  }
+}
+
+impl fmt::Display for Span {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}:{}", self.line, self.column)
+  }
+}
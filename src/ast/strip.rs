@@ -0,0 +1,119 @@
+use super::{Expression, MatchArm, Parameter, Pattern, Span, Statement};
+use std::rc::Rc;
+
+// Parsed nodes carry the spans they came from, while nodes built by hand in
+// tests use `Span::default()`; clearing the spans lets the two be compared.
+pub fn without_spans(statements: Vec<Statement>) -> Vec<Statement> {
+  statements.into_iter().map(statement).collect()
+}
+
+fn statement(statement: Statement) -> Statement {
+  match statement {
+    Statement::Block(statements) => Statement::Block(without_spans(statements)),
+    Statement::VariableDeclaration(name, value, _) => {
+      Statement::VariableDeclaration(name, expression(value), Span::default())
+    }
+    Statement::ConstDeclaration(name, value, _) => {
+      Statement::ConstDeclaration(name, expression(value), Span::default())
+    }
+    Statement::LocalDeclaration(name, value, slot) => {
+      Statement::LocalDeclaration(name, expression(value), slot)
+    }
+    Statement::LocalConstDeclaration(name, value, slot) => {
+      Statement::LocalConstDeclaration(name, expression(value), slot)
+    }
+    Statement::Return(value) => Statement::Return(expression(value)),
+    Statement::Expression(value) => Statement::Expression(expression(value)),
+    Statement::WhileLoop(condition, body, _) => Statement::WhileLoop(
+      expression(condition),
+      Box::new(self::statement(*body)),
+      Span::default(),
+    ),
+    Statement::ForLoop(variable, iterable, body, _) => Statement::ForLoop(
+      variable,
+      expression(iterable),
+      Box::new(self::statement(*body)),
+      Span::default(),
+    ),
+    Statement::Error(_) => Statement::Error(Span::default()),
+    statement => statement,
+  }
+}
+
+fn expression(expression: Expression) -> Expression {
+  let boxed = |value: Box<Expression>| Box::new(self::expression(*value));
+  match expression {
+    Expression::Id(name, _) => Expression::Id(name, Span::default()),
+    Expression::Call(function, args, _) => {
+      Expression::Call(boxed(function), expressions(args), Span::default())
+    }
+    Expression::Prefix(operator, operand) => Expression::Prefix(operator, boxed(operand)),
+    Expression::Infix(operator, left, right) => {
+      Expression::Infix(operator, boxed(left), boxed(right))
+    }
+    Expression::Conditional(condition, consequence, alternative, _) => Expression::Conditional(
+      boxed(condition),
+      Box::new(statement(*consequence)),
+      alternative.map(|alternative| Box::new(statement(*alternative))),
+      Span::default(),
+    ),
+    Expression::Function(name, parameters, body, _) => Expression::Function(
+      name,
+      parameters.into_iter().map(parameter).collect(),
+      Rc::new(statement((*body).clone())),
+      Span::default(),
+    ),
+    Expression::Array(items) => Expression::Array(expressions(items)),
+    Expression::Hash(entries) => Expression::Hash(
+      entries
+        .into_iter()
+        .map(|(key, value)| (self::expression(key), self::expression(value)))
+        .collect(),
+    ),
+    Expression::Index(indexed, index) => Expression::Index(boxed(indexed), boxed(index)),
+    Expression::Property(object, name) => Expression::Property(boxed(object), name),
+    Expression::Match(value, arms) => {
+      Expression::Match(boxed(value), arms.into_iter().map(arm).collect())
+    }
+    Expression::Spread(value) => Expression::Spread(boxed(value)),
+    Expression::Named(name, value) => Expression::Named(name, boxed(value)),
+    expression => expression,
+  }
+}
+
+fn expressions(expressions: Vec<Expression>) -> Vec<Expression> {
+  expressions.into_iter().map(expression).collect()
+}
+
+fn parameter(parameter: Parameter) -> Parameter {
+  Parameter {
+    default: parameter.default.map(expression),
+    span: Span::default(),
+    ..parameter
+  }
+}
+
+fn arm(arm: MatchArm) -> MatchArm {
+  MatchArm::new(
+    pattern(arm.pattern),
+    arm.guard.map(expression),
+    statement(arm.body),
+  )
+}
+
+fn pattern(pattern: Pattern) -> Pattern {
+  match pattern {
+    Pattern::Literal(literal) => Pattern::Literal(expression(literal)),
+    Pattern::Array(patterns, rest) => {
+      Pattern::Array(patterns.into_iter().map(self::pattern).collect(), rest)
+    }
+    Pattern::Hash(entries) => Pattern::Hash(
+      entries
+        .into_iter()
+        .map(|(key, pattern)| (key, self::pattern(pattern)))
+        .collect(),
+    ),
+    Pattern::Or(patterns) => Pattern::Or(patterns.into_iter().map(self::pattern).collect()),
+    pattern => pattern,
+  }
+}
@@ -0,0 +1,6 @@
+#[derive(Debug, PartialEq, Clone)]
+pub enum Trivia {
+  Comment(String),
+  TrailingComment(String),
+  BlankLine,
+}
@@ -0,0 +1,8 @@
+use std::process::exit;
+
+fn main() {
+  if let Err(err) = helium::lsp::run() {
+    eprintln!("{}", err);
+    exit(1)
+  }
+}
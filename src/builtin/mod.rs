@@ -1,8 +1,12 @@
-mod helium;
 mod rust;
-use crate::{env::Env, error::Error};
+use crate::env::Env;
 
-pub fn register(env: &Env) -> Result<(), Vec<Error>> {
-  rust::register(&env);
-  helium::register(&env)
+pub use rust::fs::FsPolicy;
+
+pub fn register(env: &Env) {
+  rust::register(env);
+}
+
+pub fn register_fs(env: &Env, policy: FsPolicy) {
+  rust::fs::register(env, policy);
 }
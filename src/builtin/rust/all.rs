@@ -0,0 +1,16 @@
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
+
+pub fn all(context: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 2)?;
+  match (args.remove(0), args.remove(0)) {
+    (Object::Array(array), function) => {
+      for item in array {
+        if !context.call(function.clone(), vec![item])?.is_truthy() {
+          return Ok(Object::FALSE);
+        }
+      }
+      Ok(Object::TRUE)
+    }
+    (obj, _) => Err(Error::type_error("array", obj)),
+  }
+}
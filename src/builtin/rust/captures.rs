@@ -0,0 +1,30 @@
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
+use std::collections::HashMap;
+
+pub fn captures(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 2)?;
+
+  let (string, regex) = match (args.remove(0), args.remove(0)) {
+    (Object::String(string), Object::Regex(regex)) => (string, regex),
+    (Object::String(_), obj) => return Err(Error::type_error("regex", obj)),
+    (obj, _) => return Err(Error::type_error("string", obj)),
+  };
+
+  let captures = match regex.captures(&string) {
+    Some(captures) => captures,
+    None => return Ok(Object::Null),
+  };
+
+  let mut hash = HashMap::new();
+  for (i, name) in regex.capture_names().enumerate() {
+    let value = match captures.get(i) {
+      Some(group) => Object::String(group.as_str().to_owned()),
+      None => Object::Null,
+    };
+    if let Some(name) = name {
+      hash.insert(name.to_owned(), value.clone());
+    }
+    hash.insert(i.to_string(), value);
+  }
+  Ok(Object::Hash(hash))
+}
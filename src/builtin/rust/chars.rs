@@ -0,0 +1,15 @@
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
+
+pub fn chars(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 1)?;
+
+  match args.remove(0) {
+    Object::String(string) => Ok(Object::Array(
+      string
+        .chars()
+        .map(|c| Object::String(c.to_string()))
+        .collect(),
+    )),
+    obj => Err(Error::type_error("string", obj)),
+  }
+}
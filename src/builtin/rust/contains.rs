@@ -0,0 +1,13 @@
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
+
+pub fn contains(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 2)?;
+
+  match (args.remove(0), args.remove(0)) {
+    (Object::String(string), Object::String(part)) => Ok(Object::boolean(string.contains(&part))),
+    (Object::String(string), Object::Regex(regex)) => Ok(Object::boolean(regex.is_match(&string))),
+    (Object::Array(array), item) => Ok(Object::boolean(array.contains(&item))),
+    (Object::String(_), obj) => Err(Error::type_error("string or regex", obj)),
+    (obj, _) => Err(Error::type_error("array or string", obj)),
+  }
+}
@@ -0,0 +1,12 @@
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
+
+pub fn ends_with(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 2)?;
+
+  match (args.remove(0), args.remove(0)) {
+    (Object::String(string), Object::String(suffix)) => {
+      Ok(Object::boolean(string.ends_with(&suffix)))
+    }
+    (Object::String(_), obj) | (obj, _) => Err(Error::type_error("string", obj)),
+  }
+}
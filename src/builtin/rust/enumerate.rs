@@ -0,0 +1,15 @@
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
+
+pub fn enumerate(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 1)?;
+  match args.remove(0) {
+    Object::Array(array) => Ok(Object::Array(
+      array
+        .into_iter()
+        .enumerate()
+        .map(|(i, item)| Object::Array(vec![Object::Integer(i as i64), item]))
+        .collect(),
+    )),
+    obj => Err(Error::type_error("array", obj)),
+  }
+}
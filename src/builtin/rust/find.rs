@@ -0,0 +1,24 @@
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
+
+pub fn find(context: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 2)?;
+  match (args.remove(0), args.remove(0)) {
+    (Object::Array(array), function) => {
+      for item in array {
+        if context
+          .call(function.clone(), vec![item.clone()])?
+          .is_truthy()
+        {
+          return Ok(item);
+        }
+      }
+      Ok(Object::Null)
+    }
+    (Object::String(string), Object::Regex(regex)) => Ok(match regex.find(&string) {
+      Some(found) => Object::String(found.as_str().to_owned()),
+      None => Object::Null,
+    }),
+    (Object::String(_), obj) => Err(Error::type_error("regex", obj)),
+    (obj, _) => Err(Error::type_error("array or string", obj)),
+  }
+}
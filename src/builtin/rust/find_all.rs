@@ -0,0 +1,16 @@
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
+
+pub fn find_all(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 2)?;
+
+  match (args.remove(0), args.remove(0)) {
+    (Object::String(string), Object::Regex(regex)) => Ok(Object::Array(
+      regex
+        .find_iter(&string)
+        .map(|found| Object::String(found.as_str().to_owned()))
+        .collect(),
+    )),
+    (Object::String(_), obj) => Err(Error::type_error("regex", obj)),
+    (obj, _) => Err(Error::type_error("string", obj)),
+  }
+}
@@ -1,13 +1,14 @@
-use crate::{error::Error, helpers::validate_params, object::Object};
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
 
-pub fn first(mut args: Vec<Object>) -> Result<Object, Error> {
+pub fn first(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
   validate_params(&args, 1)?;
 
   match args.remove(0) {
-    Object::String(obj) if obj.len() == 0 => Ok(Object::Null),
-    Object::Array(obj) if obj.len() == 0 => Ok(Object::Null),
-    Object::String(string) => Ok(Object::String(string[0..1].to_string())),
-    Object::Array(array) => match array.get(0) {
+    Object::String(string) => Ok(match string.chars().next() {
+      Some(char) => Object::String(char.to_string()),
+      None => Object::Null,
+    }),
+    Object::Array(array) => match array.first() {
       Some(obj) => Ok(obj.clone()),
       None => Ok(Object::Null),
     },
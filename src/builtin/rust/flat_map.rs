@@ -0,0 +1,18 @@
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
+
+pub fn flat_map(context: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 2)?;
+  match (args.remove(0), args.remove(0)) {
+    (Object::Array(array), function) => {
+      let mut result = vec![];
+      for item in array {
+        match context.call(function.clone(), vec![item])? {
+          Object::Array(items) => result.extend(items),
+          obj => result.push(obj),
+        }
+      }
+      Ok(Object::Array(result))
+    }
+    (obj, _) => Err(Error::type_error("array", obj)),
+  }
+}
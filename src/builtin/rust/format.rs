@@ -0,0 +1,63 @@
+use crate::{error::Error, object::Object, visitor::Context};
+use std::{iter::Peekable, str::Chars};
+
+pub fn format(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  if args.is_empty() {
+    return Err(Error::wrong_parameters(1, 0));
+  }
+
+  let template = match args.remove(0) {
+    Object::String(template) => template,
+    obj => return Err(Error::type_error("string", obj)),
+  };
+
+  let mut result = String::with_capacity(template.len());
+  let mut chars = template.chars().peekable();
+  let mut next_positional = 0;
+  while let Some(c) = chars.next() {
+    match c {
+      '{' if chars.peek() == Some(&'{') => {
+        chars.next();
+        result.push('{');
+      }
+      '}' if chars.peek() == Some(&'}') => {
+        chars.next();
+        result.push('}');
+      }
+      '{' => {
+        let placeholder = read_placeholder(&mut chars, &template)?;
+        let value = if placeholder.is_empty() {
+          next_positional += 1;
+          args.get(next_positional - 1)
+        } else if let Ok(index) = placeholder.parse::<usize>() {
+          args.get(index)
+        } else {
+          match args.last() {
+            Some(Object::Hash(hash)) => hash.get(&placeholder),
+            _ => None,
+          }
+        };
+        match value {
+          Some(value) => result.push_str(&value.to_text()),
+          None => return Err(Error::missing_argument("format", &placeholder)),
+        }
+      }
+      '}' => return Err(Error::invalid_format(&template)),
+      c => result.push(c),
+    }
+  }
+
+  Ok(Object::String(result))
+}
+
+fn read_placeholder(chars: &mut Peekable<Chars>, template: &str) -> Result<String, Error> {
+  let mut placeholder = String::new();
+  for c in chars {
+    match c {
+      '}' => return Ok(placeholder.trim().to_owned()),
+      '{' => break,
+      c => placeholder.push(c),
+    }
+  }
+  Err(Error::invalid_format(template))
+}
@@ -0,0 +1,33 @@
+mod policy;
+mod read;
+#[cfg(test)]
+mod test;
+mod write;
+
+use crate::{env::Env, error::Error, object::Object};
+use std::rc::Rc;
+
+pub use policy::FsPolicy;
+
+type FsFn = fn(&FsPolicy, Vec<Object>) -> Result<Object, Error>;
+
+pub fn register(env: &Env, policy: FsPolicy) {
+  let functions: Vec<(&str, FsFn)> = vec![
+    ("read_file", read::read_file),
+    ("read_lines", read::read_lines),
+    ("list_dir", read::list_dir),
+    ("exists", read::exists),
+    ("write_file", write::write_file),
+    ("append_file", write::append_file),
+    ("remove", write::remove),
+  ];
+
+  let policy = Rc::new(policy);
+  for (name, function) in functions {
+    let policy = policy.clone();
+    env.define_builtin(
+      name,
+      Object::builtin(move |_, args| function(&policy, args)),
+    );
+  }
+}
@@ -0,0 +1,91 @@
+use crate::error::Error;
+use std::{
+  env,
+  path::{Component, Path, PathBuf},
+};
+
+#[derive(Debug, Default, Clone)]
+pub struct FsPolicy {
+  roots: Vec<PathBuf>,
+  read_only: bool,
+}
+
+impl FsPolicy {
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  pub fn allow_dir<P: AsRef<Path>>(mut self, dir: P) -> Self {
+    let dir = absolute(dir.as_ref());
+    self.roots.push(dir.canonicalize().unwrap_or(dir));
+    self
+  }
+
+  pub fn read_only(mut self) -> Self {
+    self.read_only = true;
+    self
+  }
+
+  pub fn check_read(&self, path: &str) -> Result<PathBuf, Error> {
+    let resolved = resolve(path)?;
+    if self.roots.iter().any(|root| resolved.starts_with(root)) {
+      Ok(resolved)
+    } else {
+      Err(Error::permission_denied(path))
+    }
+  }
+
+  pub fn check_write(&self, path: &str) -> Result<PathBuf, Error> {
+    if self.read_only {
+      return Err(Error::permission_denied(path));
+    }
+    let resolved = self.check_read(path)?;
+    if self.roots.contains(&resolved) {
+      return Err(Error::permission_denied(path));
+    }
+    Ok(resolved)
+  }
+}
+
+fn absolute(path: &Path) -> PathBuf {
+  if path.is_absolute() {
+    path.to_path_buf()
+  } else {
+    env::current_dir().unwrap_or_default().join(path)
+  }
+}
+
+fn resolve(path: &str) -> Result<PathBuf, Error> {
+  let path = absolute(Path::new(path));
+  let mut existing = path.as_path();
+  let mut missing = vec![];
+  let canonical = loop {
+    match existing.canonicalize() {
+      Ok(canonical) => break canonical,
+      // Anything that exists but cannot be canonicalized is a dangling
+      // symlink, and writing through it would create its target unchecked.
+      Err(_) if existing.symlink_metadata().is_ok() => {
+        return Err(Error::permission_denied(&path.to_string_lossy()))
+      }
+      Err(_) => match (existing.parent(), existing.file_name()) {
+        (Some(parent), Some(name)) => {
+          missing.push(name.to_owned());
+          existing = parent;
+        }
+        _ => return Err(Error::permission_denied(&path.to_string_lossy())),
+      },
+    }
+  };
+
+  if missing.is_empty() {
+    return Ok(canonical);
+  }
+  let rest: PathBuf = missing.into_iter().rev().collect();
+  if rest
+    .components()
+    .any(|component| !matches!(component, Component::Normal(_)))
+  {
+    return Err(Error::permission_denied(&path.to_string_lossy()));
+  }
+  Ok(canonical.join(rest))
+}
@@ -0,0 +1,54 @@
+use super::FsPolicy;
+use crate::{error::Error, helpers::validate_params, object::Object};
+use std::fs;
+
+pub fn read_file(policy: &FsPolicy, args: Vec<Object>) -> Result<Object, Error> {
+  let path = path(args)?;
+  let resolved = policy.check_read(&path)?;
+  match fs::read_to_string(resolved) {
+    Ok(content) => Ok(Object::String(content)),
+    Err(err) => Err(Error::io(&path, err)),
+  }
+}
+
+pub fn read_lines(policy: &FsPolicy, args: Vec<Object>) -> Result<Object, Error> {
+  match read_file(policy, args)? {
+    Object::String(content) => Ok(Object::Array(
+      content
+        .lines()
+        .map(|line| Object::String(line.to_owned()))
+        .collect(),
+    )),
+    obj => Ok(obj),
+  }
+}
+
+pub fn list_dir(policy: &FsPolicy, args: Vec<Object>) -> Result<Object, Error> {
+  let path = path(args)?;
+  let resolved = policy.check_read(&path)?;
+  let entries = fs::read_dir(resolved).map_err(|err| Error::io(&path, err))?;
+
+  let mut names = vec![];
+  for entry in entries {
+    let entry = entry.map_err(|err| Error::io(&path, err))?;
+    names.push(entry.file_name().to_string_lossy().into_owned());
+  }
+  names.sort();
+  Ok(Object::Array(
+    names.into_iter().map(Object::String).collect(),
+  ))
+}
+
+pub fn exists(policy: &FsPolicy, args: Vec<Object>) -> Result<Object, Error> {
+  let path = path(args)?;
+  let resolved = policy.check_read(&path)?;
+  Ok(Object::boolean(resolved.exists()))
+}
+
+pub(super) fn path(mut args: Vec<Object>) -> Result<String, Error> {
+  validate_params(&args, 1)?;
+  match args.remove(0) {
+    Object::String(path) => Ok(path),
+    obj => Err(Error::type_error("string", obj)),
+  }
+}
@@ -0,0 +1,130 @@
+use super::FsPolicy;
+use crate::{error::ErrorType, interpreter::Interpreter, object::Object};
+use std::{env, fs, path::PathBuf, process};
+
+fn sandbox(name: &str) -> PathBuf {
+  let dir = env::temp_dir().join(format!("helium-fs-{}-{}", process::id(), name));
+  let _ = fs::remove_dir_all(&dir);
+  fs::create_dir_all(dir.join("data")).unwrap();
+  dir
+}
+
+fn assert_fails(interpreter: &Interpreter, input: &str, expected: ErrorType) {
+  let errors = interpreter.run(input).unwrap_err();
+  assert_eq!(errors[0].error_type(), &expected, "{}", input);
+}
+
+#[test]
+fn fs_builtins_are_opt_in() {
+  let interpreter = Interpreter::new();
+  assert_fails(
+    &interpreter,
+    "read_file('Cargo.toml')",
+    ErrorType::UndefinedVariable,
+  );
+}
+
+#[test]
+fn read_and_write_inside_allowed_dirs() {
+  let dir = sandbox("read-write");
+  let interpreter = Interpreter::new();
+  interpreter.enable_fs(FsPolicy::new().allow_dir(dir.join("data")));
+  interpreter
+    .set_global("dir", dir.join("data").to_string_lossy().into_owned())
+    .unwrap();
+
+  let input = "
+    let path = dir + '/log.txt'
+    write_file(path, 'first\\n')
+    append_file(path, 'second\\n')
+    let result = [exists(path), read_lines(path), list_dir(dir)]
+    result";
+  assert_eq!(
+    interpreter.run(input),
+    Ok(Object::Array(vec![
+      Object::TRUE,
+      Object::Array(vec![
+        Object::String("first".to_owned()),
+        Object::String("second".to_owned()),
+      ]),
+      Object::Array(vec![Object::String("log.txt".to_owned())]),
+    ]))
+  );
+
+  assert_eq!(
+    interpreter.run("remove(path); exists(path)"),
+    Ok(Object::FALSE)
+  );
+  assert_fails(&interpreter, "read_file(path)", ErrorType::IoError);
+  fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn paths_outside_the_sandbox_are_denied() {
+  let dir = sandbox("escape");
+  fs::write(dir.join("secret.txt"), "secret").unwrap();
+  let interpreter = Interpreter::new();
+  interpreter.enable_fs(FsPolicy::new().allow_dir(dir.join("data")));
+  interpreter
+    .set_global("dir", dir.join("data").to_string_lossy().into_owned())
+    .unwrap();
+
+  for input in [
+    "read_file(dir + '/../secret.txt')",
+    "write_file(dir + '/missing/../../secret.txt', 'x')",
+    "exists('/etc/passwd')",
+    "remove(dir)",
+  ] {
+    assert_fails(&interpreter, input, ErrorType::PermissionDenied);
+  }
+  assert_eq!(
+    fs::read_to_string(dir.join("secret.txt")).unwrap(),
+    "secret"
+  );
+  fs::remove_dir_all(dir).unwrap();
+}
+
+#[cfg(unix)]
+#[test]
+fn dangling_symlinks_out_of_the_sandbox_are_denied() {
+  let dir = sandbox("dangling");
+  std::os::unix::fs::symlink(dir.join("outside.txt"), dir.join("data/link")).unwrap();
+  let interpreter = Interpreter::new();
+  interpreter.enable_fs(FsPolicy::new().allow_dir(dir.join("data")));
+  interpreter
+    .set_global("dir", dir.join("data").to_string_lossy().into_owned())
+    .unwrap();
+
+  for input in [
+    "write_file(dir + '/link', 'x')",
+    "write_file(dir + '/link/inner.txt', 'x')",
+  ] {
+    assert_fails(&interpreter, input, ErrorType::PermissionDenied);
+  }
+  assert!(!dir.join("outside.txt").exists());
+  fs::remove_dir_all(dir).unwrap();
+}
+
+#[test]
+fn read_only_policies_reject_writes() {
+  let dir = sandbox("read-only");
+  fs::write(dir.join("data/notes.txt"), "notes").unwrap();
+  let interpreter = Interpreter::new();
+  interpreter.enable_fs(FsPolicy::new().allow_dir(&dir).read_only());
+  interpreter
+    .set_global("dir", dir.to_string_lossy().into_owned())
+    .unwrap();
+
+  assert_eq!(
+    interpreter.run("read_file(dir + '/data/notes.txt')"),
+    Ok(Object::String("notes".to_owned()))
+  );
+  for input in [
+    "write_file(dir + '/data/notes.txt', '')",
+    "append_file(dir + '/new.txt', '')",
+    "remove(dir + '/data/notes.txt')",
+  ] {
+    assert_fails(&interpreter, input, ErrorType::PermissionDenied);
+  }
+  fs::remove_dir_all(dir).unwrap();
+}
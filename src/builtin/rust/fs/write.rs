@@ -0,0 +1,42 @@
+use super::{read::path, FsPolicy};
+use crate::{error::Error, helpers::validate_params, object::Object};
+use std::{fs, io::Write};
+
+pub fn write_file(policy: &FsPolicy, args: Vec<Object>) -> Result<Object, Error> {
+  let (path, content) = path_and_content(args)?;
+  let resolved = policy.check_write(&path)?;
+  fs::write(resolved, content).map_err(|err| Error::io(&path, err))?;
+  Ok(Object::Null)
+}
+
+pub fn append_file(policy: &FsPolicy, args: Vec<Object>) -> Result<Object, Error> {
+  let (path, content) = path_and_content(args)?;
+  let resolved = policy.check_write(&path)?;
+  fs::OpenOptions::new()
+    .create(true)
+    .append(true)
+    .open(resolved)
+    .and_then(|mut file| file.write_all(content.as_bytes()))
+    .map_err(|err| Error::io(&path, err))?;
+  Ok(Object::Null)
+}
+
+pub fn remove(policy: &FsPolicy, args: Vec<Object>) -> Result<Object, Error> {
+  let path = path(args)?;
+  let resolved = policy.check_write(&path)?;
+  let result = if resolved.is_dir() {
+    fs::remove_dir(resolved)
+  } else {
+    fs::remove_file(resolved)
+  };
+  result.map_err(|err| Error::io(&path, err))?;
+  Ok(Object::Null)
+}
+
+fn path_and_content(mut args: Vec<Object>) -> Result<(String, String), Error> {
+  validate_params(&args, 2)?;
+  match (args.remove(0), args.remove(0)) {
+    (Object::String(path), Object::String(content)) => Ok((path, content)),
+    (Object::String(_), obj) | (obj, _) => Err(Error::type_error("string", obj)),
+  }
+}
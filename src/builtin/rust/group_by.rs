@@ -0,0 +1,25 @@
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
+use std::collections::HashMap;
+
+pub fn group_by(context: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 2)?;
+  match (args.remove(0), args.remove(0)) {
+    (Object::Array(array), function) => {
+      let mut groups: HashMap<String, Object> = HashMap::new();
+      for item in array {
+        let key = context.call(function.clone(), vec![item.clone()])?;
+        let key = match key.hash_key() {
+          Some(key) => key,
+          None => return Err(Error::index_error(Object::Hash(groups), key)),
+        };
+
+        match groups.entry(key).or_insert_with(|| Object::Array(vec![])) {
+          Object::Array(group) => group.push(item),
+          _ => unreachable!(),
+        }
+      }
+      Ok(Object::Hash(groups))
+    }
+    (obj, _) => Err(Error::type_error("array", obj)),
+  }
+}
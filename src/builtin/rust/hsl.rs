@@ -0,0 +1,23 @@
+use crate::{
+  error::Error,
+  helpers::fraction,
+  interpreter::FromObject,
+  object::{Color, Object},
+  visitor::Context,
+};
+
+pub fn hsl(_: &Context, args: Vec<Object>) -> Result<Object, Error> {
+  if args.len() != 3 && args.len() != 4 {
+    return Err(Error::wrong_parameters(4, args.len()));
+  }
+
+  let mut args = args.into_iter();
+  let hue = f64::from_object(args.next().unwrap())?;
+  let saturation = fraction(args.next().unwrap())?;
+  let lightness = fraction(args.next().unwrap())?;
+  let color = Color::from_hsl(hue, saturation, lightness, 255);
+  Ok(Object::Color(match args.next() {
+    Some(alpha) => color.with_alpha(fraction(alpha)?),
+    None => color,
+  }))
+}
@@ -0,0 +1,19 @@
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
+
+pub fn index_of(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 2)?;
+
+  let index = match (args.remove(0), args.remove(0)) {
+    (Object::String(string), Object::String(part)) => string
+      .find(&part)
+      .map(|byte| string[..byte].chars().count()),
+    (Object::Array(array), item) => array.iter().position(|other| *other == item),
+    (Object::String(_), obj) => return Err(Error::type_error("string", obj)),
+    (obj, _) => return Err(Error::type_error("array or string", obj)),
+  };
+
+  Ok(match index {
+    Some(index) => Object::Integer(index as i64),
+    None => Object::Integer(-1),
+  })
+}
@@ -0,0 +1,24 @@
+use crate::{error::Error, object::Object, visitor::Context};
+
+pub fn join(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  if args.is_empty() || args.len() > 2 {
+    return Err(Error::wrong_parameters(2, args.len()));
+  }
+
+  let separator = match args.get(1) {
+    None => String::new(),
+    Some(Object::String(separator)) => separator.clone(),
+    Some(obj) => return Err(Error::type_error("string", obj.clone())),
+  };
+
+  match args.remove(0) {
+    Object::Array(array) => Ok(Object::String(
+      array
+        .iter()
+        .map(Object::to_text)
+        .collect::<Vec<String>>()
+        .join(&separator),
+    )),
+    obj => Err(Error::type_error("array", obj)),
+  }
+}
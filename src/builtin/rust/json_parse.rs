@@ -0,0 +1,15 @@
+use crate::{
+  error::Error,
+  helpers::{parse_json, validate_params},
+  object::Object,
+  visitor::Context,
+};
+
+pub fn json_parse(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 1)?;
+
+  match args.remove(0) {
+    Object::String(string) => parse_json(&string),
+    obj => Err(Error::type_error("string", obj)),
+  }
+}
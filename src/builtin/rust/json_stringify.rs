@@ -0,0 +1,15 @@
+use crate::{error::Error, helpers::stringify_json, object::Object, visitor::Context};
+
+pub fn json_stringify(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  if args.is_empty() || args.len() > 2 {
+    return Err(Error::wrong_parameters(2, args.len()));
+  }
+
+  let indent = match args.get(1) {
+    None | Some(Object::Null) => 0,
+    Some(Object::Integer(indent)) if *indent >= 0 => *indent as usize,
+    Some(obj) => return Err(Error::type_error("non-negative integer", obj.clone())),
+  };
+
+  Ok(Object::String(stringify_json(&args.remove(0), indent)?))
+}
@@ -0,0 +1,15 @@
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
+
+pub fn map(context: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 2)?;
+  match (args.remove(0), args.remove(0)) {
+    (Object::Array(array), function) => {
+      let mut result = Vec::with_capacity(array.len());
+      for item in array {
+        result.push(context.call(function.clone(), vec![item])?);
+      }
+      Ok(Object::Array(result))
+    }
+    (obj, _) => Err(Error::type_error("array", obj)),
+  }
+}
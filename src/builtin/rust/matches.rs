@@ -0,0 +1,11 @@
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
+
+pub fn matches(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 2)?;
+
+  match (args.remove(0), args.remove(0)) {
+    (Object::String(string), Object::Regex(regex)) => Ok(Object::boolean(regex.is_match(&string))),
+    (Object::String(_), obj) => Err(Error::type_error("regex", obj)),
+    (obj, _) => Err(Error::type_error("string", obj)),
+  }
+}
@@ -0,0 +1,43 @@
+mod numeric;
+mod random;
+mod stats;
+mod trig;
+
+use super::Native;
+use crate::object::Object;
+use random::Random;
+use std::{collections::HashMap, f64::consts, rc::Rc};
+
+pub fn math() -> Object {
+  let functions: Vec<(&str, Native)> = vec![
+    ("abs", numeric::abs),
+    ("min", numeric::min),
+    ("max", numeric::max),
+    ("pow", numeric::pow),
+    ("sqrt", numeric::sqrt),
+    ("clamp", numeric::clamp),
+    ("floor", numeric::floor),
+    ("ceil", numeric::ceil),
+    ("round", numeric::round),
+    ("sin", trig::sin),
+    ("cos", trig::cos),
+    ("tan", trig::tan),
+    ("asin", trig::asin),
+    ("acos", trig::acos),
+    ("atan", trig::atan),
+    ("atan2", trig::atan2),
+    ("sum", stats::sum),
+    ("mean", stats::mean),
+  ];
+
+  let mut namespace: HashMap<String, Object> = functions
+    .into_iter()
+    .map(|(name, function)| (name.to_owned(), Object::builtin(function)))
+    .collect();
+  namespace.insert("pi".to_owned(), Object::Float(consts::PI));
+  namespace.insert("e".to_owned(), Object::Float(consts::E));
+
+  let random = Rc::new(Random::from_time());
+  namespace.extend(random::register(&random));
+  Object::Hash(namespace)
+}
@@ -0,0 +1,104 @@
+use crate::{
+  error::Error, helpers::validate_params, interpreter::FromObject, object::Object, visitor::Context,
+};
+use std::convert::TryFrom;
+
+pub fn abs(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 1)?;
+  match args.remove(0) {
+    Object::Integer(value) => value
+      .checked_abs()
+      .map(Object::Integer)
+      .ok_or_else(|| Error::integer_overflow("abs")),
+    Object::Float(value) => Ok(Object::Float(value.abs())),
+    obj => Err(Error::type_error("number", obj)),
+  }
+}
+
+pub fn min(_: &Context, args: Vec<Object>) -> Result<Object, Error> {
+  extreme(args, |candidate, best| candidate < best)
+}
+
+pub fn max(_: &Context, args: Vec<Object>) -> Result<Object, Error> {
+  extreme(args, |candidate, best| candidate > best)
+}
+
+fn extreme(args: Vec<Object>, replaces: fn(f64, f64) -> bool) -> Result<Object, Error> {
+  let values = match args.as_slice() {
+    [Object::Array(array)] => array.clone(),
+    _ => args,
+  };
+
+  let mut best: Option<Object> = None;
+  for value in values {
+    let number = f64::from_object(value.clone())?;
+    best = match best {
+      Some(current) if !replaces(number, f64::from_object(current.clone())?) => Some(current),
+      _ => Some(value),
+    };
+  }
+  best.ok_or_else(|| Error::wrong_parameters(1, 0))
+}
+
+pub fn pow(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 2)?;
+  match (args.remove(0), args.remove(0)) {
+    (Object::Integer(base), Object::Integer(exponent)) if exponent >= 0 => {
+      match u32::try_from(exponent)
+        .ok()
+        .and_then(|exponent| base.checked_pow(exponent))
+      {
+        Some(result) => Ok(Object::Integer(result)),
+        None => Ok(Object::Float((base as f64).powf(exponent as f64))),
+      }
+    }
+    (base, exponent) => Ok(Object::Float(
+      f64::from_object(base)?.powf(f64::from_object(exponent)?),
+    )),
+  }
+}
+
+pub fn sqrt(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 1)?;
+  Ok(Object::Float(f64::from_object(args.remove(0))?.sqrt()))
+}
+
+pub fn clamp(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 3)?;
+  match (args.remove(0), args.remove(0), args.remove(0)) {
+    (Object::Integer(value), Object::Integer(low), Object::Integer(high)) if low <= high => {
+      Ok(Object::Integer(value.clamp(low, high)))
+    }
+    (value, low, high) => {
+      let (low, high) = (f64::from_object(low)?, f64::from_object(high)?);
+      if low > high || low.is_nan() || high.is_nan() {
+        return Err(Error::type_error(
+          "a lower bound below the upper bound",
+          Object::Float(low),
+        ));
+      }
+      Ok(Object::Float(f64::from_object(value)?.clamp(low, high)))
+    }
+  }
+}
+
+pub fn floor(_: &Context, args: Vec<Object>) -> Result<Object, Error> {
+  to_integer(args, f64::floor)
+}
+
+pub fn ceil(_: &Context, args: Vec<Object>) -> Result<Object, Error> {
+  to_integer(args, f64::ceil)
+}
+
+pub fn round(_: &Context, args: Vec<Object>) -> Result<Object, Error> {
+  to_integer(args, f64::round)
+}
+
+fn to_integer(mut args: Vec<Object>, function: fn(f64) -> f64) -> Result<Object, Error> {
+  validate_params(&args, 1)?;
+  match args.remove(0) {
+    Object::Integer(value) => Ok(Object::Integer(value)),
+    Object::Float(value) if value.is_finite() => Ok(Object::Integer(function(value) as i64)),
+    obj => Err(Error::type_error("finite number", obj)),
+  }
+}
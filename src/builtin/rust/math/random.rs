@@ -0,0 +1,108 @@
+use crate::{error::Error, helpers::validate_params, object::Object};
+use std::{
+  cell::Cell,
+  rc::Rc,
+  time::{SystemTime, UNIX_EPOCH},
+};
+
+pub struct Random {
+  state: Cell<u64>,
+}
+
+impl Random {
+  pub fn from_time() -> Self {
+    let nanos = SystemTime::now()
+      .duration_since(UNIX_EPOCH)
+      .map(|duration| duration.as_nanos() as u64)
+      .unwrap_or_default();
+    Random {
+      state: Cell::new(nanos),
+    }
+  }
+
+  fn seed(&self, seed: u64) {
+    self.state.set(seed);
+  }
+
+  fn next(&self) -> u64 {
+    let state = self.state.get().wrapping_add(0x9E37_79B9_7F4A_7C15);
+    self.state.set(state);
+    let mut z = state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+  }
+
+  fn float(&self) -> f64 {
+    (self.next() >> 11) as f64 / (1u64 << 53) as f64
+  }
+
+  fn below(&self, bound: u64) -> u64 {
+    ((self.next() as u128 * bound as u128) >> 64) as u64
+  }
+}
+
+pub fn register(random: &Rc<Random>) -> Vec<(String, Object)> {
+  let seed = random.clone();
+  let float = random.clone();
+  let integer = random.clone();
+  let shuffle = random.clone();
+
+  vec![
+    (
+      "seed".to_owned(),
+      Object::builtin(move |_, mut args| {
+        validate_params(&args, 1)?;
+        match args.remove(0) {
+          Object::Integer(value) => {
+            seed.seed(value as u64);
+            Ok(Object::Null)
+          }
+          obj => Err(Error::type_error("integer", obj)),
+        }
+      }),
+    ),
+    (
+      "random".to_owned(),
+      Object::builtin(move |_, args| {
+        validate_params(&args, 0)?;
+        Ok(Object::Float(float.float()))
+      }),
+    ),
+    (
+      "random_int".to_owned(),
+      Object::builtin(move |_, mut args| {
+        validate_params(&args, 2)?;
+        match (args.remove(0), args.remove(0)) {
+          (Object::Integer(low), Object::Integer(high)) if low <= high => {
+            let range = (high as i128 - low as i128 + 1) as u128;
+            let offset = if range > u64::MAX as u128 {
+              integer.next()
+            } else {
+              integer.below(range as u64)
+            };
+            Ok(Object::Integer((low as i128 + offset as i128) as i64))
+          }
+          (Object::Integer(_), obj) => Err(Error::type_error("integer above the lower bound", obj)),
+          (obj, _) => Err(Error::type_error("integer", obj)),
+        }
+      }),
+    ),
+    (
+      "shuffle".to_owned(),
+      Object::builtin(move |_, mut args| {
+        validate_params(&args, 1)?;
+        match args.remove(0) {
+          Object::Array(mut array) => {
+            for i in (1..array.len()).rev() {
+              let j = shuffle.below(i as u64 + 1) as usize;
+              array.swap(i, j);
+            }
+            Ok(Object::Array(array))
+          }
+          obj => Err(Error::type_error("array", obj)),
+        }
+      }),
+    ),
+  ]
+}
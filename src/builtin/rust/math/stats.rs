@@ -0,0 +1,40 @@
+use crate::{
+  error::Error, helpers::validate_params, interpreter::FromObject, object::Object, visitor::Context,
+};
+
+pub fn sum(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 1)?;
+  match args.remove(0) {
+    Object::Array(array) => total(array),
+    obj => Err(Error::type_error("array", obj)),
+  }
+}
+
+pub fn mean(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 1)?;
+  match args.remove(0) {
+    Object::Array(array) if array.is_empty() => Ok(Object::Null),
+    Object::Array(array) => {
+      let count = array.len() as f64;
+      Ok(Object::Float(f64::from_object(total(array)?)? / count))
+    }
+    obj => Err(Error::type_error("array", obj)),
+  }
+}
+
+fn total(array: Vec<Object>) -> Result<Object, Error> {
+  let mut integer: Option<i64> = Some(0);
+  let mut float = 0.0;
+  for item in array {
+    integer = match (integer, &item) {
+      (Some(total), Object::Integer(value)) => total.checked_add(*value),
+      _ => None,
+    };
+    float += f64::from_object(item)?;
+  }
+
+  Ok(match integer {
+    Some(total) => Object::Integer(total),
+    None => Object::Float(float),
+  })
+}
@@ -0,0 +1,39 @@
+use crate::{
+  error::Error, helpers::validate_params, interpreter::FromObject, object::Object, visitor::Context,
+};
+
+pub fn sin(_: &Context, args: Vec<Object>) -> Result<Object, Error> {
+  apply(args, f64::sin)
+}
+
+pub fn cos(_: &Context, args: Vec<Object>) -> Result<Object, Error> {
+  apply(args, f64::cos)
+}
+
+pub fn tan(_: &Context, args: Vec<Object>) -> Result<Object, Error> {
+  apply(args, f64::tan)
+}
+
+pub fn asin(_: &Context, args: Vec<Object>) -> Result<Object, Error> {
+  apply(args, f64::asin)
+}
+
+pub fn acos(_: &Context, args: Vec<Object>) -> Result<Object, Error> {
+  apply(args, f64::acos)
+}
+
+pub fn atan(_: &Context, args: Vec<Object>) -> Result<Object, Error> {
+  apply(args, f64::atan)
+}
+
+pub fn atan2(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 2)?;
+  let y = f64::from_object(args.remove(0))?;
+  let x = f64::from_object(args.remove(0))?;
+  Ok(Object::Float(y.atan2(x)))
+}
+
+fn apply(mut args: Vec<Object>, function: fn(f64) -> f64) -> Result<Object, Error> {
+  validate_params(&args, 1)?;
+  Ok(Object::Float(function(f64::from_object(args.remove(0))?)))
+}
@@ -0,0 +1,17 @@
+use crate::{error::Error, helpers::fraction, object::Object, visitor::Context};
+
+pub fn mix(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  if args.len() != 2 && args.len() != 3 {
+    return Err(Error::wrong_parameters(3, args.len()));
+  }
+
+  let weight = match args.get(2) {
+    Some(weight) => fraction(weight.clone())?,
+    None => 0.5,
+  };
+
+  match (args.remove(0), args.remove(0)) {
+    (Object::Color(left), Object::Color(right)) => Ok(Object::Color(left.mix(&right, weight))),
+    (Object::Color(_), obj) | (obj, _) => Err(Error::type_error("color", obj)),
+  }
+}
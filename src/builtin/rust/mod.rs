@@ -1,24 +1,102 @@
+mod all;
+mod any;
+mod captures;
+mod chars;
+mod contains;
+mod ends_with;
+mod enumerate;
+mod filter;
+mod find;
+mod find_all;
 mod first;
+mod flat_map;
+mod format;
+pub mod fs;
+mod group_by;
+mod hsl;
+mod index_of;
+mod join;
+mod json_parse;
+mod json_stringify;
 mod last;
 mod len;
+mod lower;
+mod map;
+mod matches;
+mod math;
+mod mix;
+mod pad;
 mod print;
 mod push;
+mod reduce;
+mod regex;
+mod repeat;
+mod replace;
 mod rest;
+mod rgb;
+mod shade;
+mod sort_by;
+mod split;
+mod starts_with;
 #[cfg(test)]
 mod test;
-use crate::{env::Env, error::Error, object::Object};
+mod trim;
+mod upper;
+mod zip;
+use crate::{env::Env, error::Error, object::Object, visitor::Context};
+
+type Native = fn(&Context, Vec<Object>) -> Result<Object, Error>;
 
 pub fn register(env: &Env) {
-  let builtins: Vec<(&str, fn(Vec<Object>) -> Result<Object, Error>)> = vec![
+  let builtins: Vec<(&str, Native)> = vec![
     ("len", len::len),
     ("first", first::first),
     ("last", last::last),
     ("push", push::push),
     ("print", print::print),
     ("rest", rest::rest),
+    ("map", map::map),
+    ("filter", filter::filter),
+    ("reduce", reduce::reduce),
+    ("find", find::find),
+    ("any", any::any),
+    ("all", all::all),
+    ("sort_by", sort_by::sort_by),
+    ("group_by", group_by::group_by),
+    ("zip", zip::zip),
+    ("enumerate", enumerate::enumerate),
+    ("flat_map", flat_map::flat_map),
+    ("json_parse", json_parse::json_parse),
+    ("json_stringify", json_stringify::json_stringify),
+    ("regex", regex::regex),
+    ("matches", matches::matches),
+    ("find_all", find_all::find_all),
+    ("captures", captures::captures),
+    ("replace", replace::replace),
+    ("split", split::split),
+    ("join", join::join),
+    ("trim", trim::trim),
+    ("upper", upper::upper),
+    ("lower", lower::lower),
+    ("starts_with", starts_with::starts_with),
+    ("ends_with", ends_with::ends_with),
+    ("contains", contains::contains),
+    ("index_of", index_of::index_of),
+    ("chars", chars::chars),
+    ("repeat", repeat::repeat),
+    ("pad_left", pad::pad_left),
+    ("pad_right", pad::pad_right),
+    ("format", format::format),
+    ("rgb", rgb::rgb),
+    ("rgba", rgb::rgba),
+    ("hsl", hsl::hsl),
+    ("mix", mix::mix),
+    ("lighten", shade::lighten),
+    ("darken", shade::darken),
   ];
 
   for builtin in &builtins {
-    env.set(builtin.0, Object::BuiltIn(builtin.1))
+    env.set(builtin.0, Object::builtin(builtin.1))
   }
+  env.set("math", math::math());
 }
@@ -0,0 +1,36 @@
+use crate::{error::Error, object::Object, visitor::Context};
+
+pub fn pad_left(context: &Context, args: Vec<Object>) -> Result<Object, Error> {
+  let (string, padding) = pad(context, args)?;
+  Ok(Object::String(padding + &string))
+}
+
+pub fn pad_right(context: &Context, args: Vec<Object>) -> Result<Object, Error> {
+  let (string, padding) = pad(context, args)?;
+  Ok(Object::String(string + &padding))
+}
+
+fn pad(context: &Context, mut args: Vec<Object>) -> Result<(String, String), Error> {
+  if args.len() != 2 && args.len() != 3 {
+    return Err(Error::wrong_parameters(3, args.len()));
+  }
+
+  let fill = match args.get(2) {
+    None => ' ',
+    Some(Object::String(fill)) if fill.chars().count() == 1 => fill.chars().next().unwrap(),
+    Some(obj) => return Err(Error::type_error("single character string", obj.clone())),
+  };
+
+  match (args.remove(0), args.remove(0)) {
+    (Object::String(string), Object::Integer(width)) => {
+      let missing = (width.max(0) as usize).saturating_sub(string.chars().count());
+      let size = missing
+        .checked_mul(fill.len_utf8())
+        .and_then(|padding| padding.checked_add(string.len()));
+      context.check_size(size.ok_or_else(|| Error::integer_overflow(context.name()))?)?;
+      Ok((string, fill.to_string().repeat(missing)))
+    }
+    (Object::String(_), obj) => Err(Error::type_error("integer", obj)),
+    (obj, _) => Err(Error::type_error("string", obj)),
+  }
+}
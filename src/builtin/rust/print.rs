@@ -1,6 +1,6 @@
-use crate::{error::Error, object::Object};
+use crate::{error::Error, object::Object, visitor::Context};
 
-pub fn print(args: Vec<Object>) -> Result<Object, Error> {
+pub fn print(_: &Context, args: Vec<Object>) -> Result<Object, Error> {
   for arg in args {
     println!("{}", arg);
   }
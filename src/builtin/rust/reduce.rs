@@ -0,0 +1,26 @@
+use crate::{error::Error, object::Object, visitor::Context};
+
+pub fn reduce(context: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  if args.len() != 2 && args.len() != 3 {
+    return Err(Error::wrong_parameters(3, args.len()));
+  }
+
+  let (array, function) = match (args.remove(0), args.remove(0)) {
+    (Object::Array(array), function) => (array, function),
+    (obj, _) => return Err(Error::type_error("array", obj)),
+  };
+
+  let mut items = array.into_iter();
+  let mut acc = match args.pop() {
+    Some(initial) => initial,
+    None => match items.next() {
+      Some(first) => first,
+      None => return Ok(Object::Null),
+    },
+  };
+
+  for item in items {
+    acc = context.call(function.clone(), vec![acc, item])?;
+  }
+  Ok(acc)
+}
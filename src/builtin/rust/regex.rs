@@ -0,0 +1,22 @@
+use crate::{
+  error::Error,
+  object::{Object, Regex},
+  visitor::Context,
+};
+
+pub fn regex(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  if args.is_empty() || args.len() > 2 {
+    return Err(Error::wrong_parameters(2, args.len()));
+  }
+
+  let flags = match args.get(1) {
+    None => String::new(),
+    Some(Object::String(flags)) => flags.clone(),
+    Some(obj) => return Err(Error::type_error("string", obj.clone())),
+  };
+
+  match args.remove(0) {
+    Object::String(source) => Ok(Object::Regex(Regex::new(&source, &flags)?)),
+    obj => Err(Error::type_error("string", obj)),
+  }
+}
@@ -0,0 +1,15 @@
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
+
+pub fn repeat(context: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 2)?;
+
+  match (args.remove(0), args.remove(0)) {
+    (Object::String(string), Object::Integer(times)) if times >= 0 => {
+      let size = string.len().checked_mul(times as usize);
+      context.check_size(size.ok_or_else(|| Error::integer_overflow("repeat"))?)?;
+      Ok(Object::String(string.repeat(times as usize)))
+    }
+    (Object::String(_), obj) => Err(Error::type_error("non-negative integer", obj)),
+    (obj, _) => Err(Error::type_error("string", obj)),
+  }
+}
@@ -0,0 +1,48 @@
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
+
+pub fn replace(context: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 3)?;
+  let (string, pattern, replacement) = (args.remove(0), args.remove(0), args.remove(0));
+
+  let string = match string {
+    Object::String(string) => string,
+    obj => return Err(Error::type_error("string", obj)),
+  };
+
+  let matches: Vec<(usize, usize)> = match (&pattern, &replacement) {
+    (Object::Regex(regex), Object::String(template)) => {
+      return Ok(Object::String(
+        regex.replace_all(&string, template.as_str()).into_owned(),
+      ))
+    }
+    (Object::Regex(regex), _) => regex
+      .find_iter(&string)
+      .map(|found| (found.start(), found.end()))
+      .collect(),
+    (Object::String(pattern), _) if !pattern.is_empty() => string
+      .match_indices(pattern.as_str())
+      .map(|(start, found)| (start, start + found.len()))
+      .collect(),
+    _ => return Err(Error::type_error("non-empty string or regex", pattern)),
+  };
+
+  let mut result = String::with_capacity(string.len());
+  let mut last = 0;
+  for (start, end) in matches {
+    result.push_str(&string[last..start]);
+    let replaced = match &replacement {
+      Object::String(replacement) => replacement.clone(),
+      function => {
+        let found = Object::String(string[start..end].to_owned());
+        match context.call(function.clone(), vec![found])? {
+          Object::String(replaced) => replaced,
+          obj => return Err(Error::type_error("string", obj)),
+        }
+      }
+    };
+    result.push_str(&replaced);
+    last = end;
+  }
+  result.push_str(&string[last..]);
+  Ok(Object::String(result))
+}
@@ -1,6 +1,6 @@
-use crate::{error::Error, helpers::validate_params, object::Object};
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
 
-pub fn rest(mut args: Vec<Object>) -> Result<Object, Error> {
+pub fn rest(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
   validate_params(&args, 1)?;
   match args.remove(0) {
     Object::Array(values) => {
@@ -10,6 +10,13 @@ pub fn rest(mut args: Vec<Object>) -> Result<Object, Error> {
         Ok(Object::Array(values[1..].to_vec()))
       }
     }
-    obj => Err(Error::type_error("array", obj)),
+    Object::String(string) => {
+      let mut chars = string.chars();
+      Ok(match chars.next() {
+        Some(_) => Object::String(chars.as_str().to_owned()),
+        None => Object::Null,
+      })
+    }
+    obj => Err(Error::type_error("array or string", obj)),
   }
 }
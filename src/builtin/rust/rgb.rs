@@ -0,0 +1,36 @@
+use crate::{
+  error::Error,
+  helpers::{fraction, validate_params},
+  object::{Color, Object},
+  visitor::Context,
+};
+use std::convert::TryFrom;
+
+pub fn rgb(_: &Context, args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 3)?;
+  let (red, green, blue) = channels(args)?;
+  Ok(Object::Color(Color::new(red, green, blue, 255)))
+}
+
+pub fn rgba(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 4)?;
+  let alpha = fraction(args.pop().unwrap())?;
+  let (red, green, blue) = channels(args)?;
+  Ok(Object::Color(
+    Color::new(red, green, blue, 255).with_alpha(alpha),
+  ))
+}
+
+fn channels(args: Vec<Object>) -> Result<(u8, u8, u8), Error> {
+  let mut channels = args.into_iter().map(|arg| match arg {
+    Object::Integer(value) => {
+      u8::try_from(value).map_err(|_| Error::type_error("integer from 0 to 255", arg))
+    }
+    obj => Err(Error::type_error("integer from 0 to 255", obj)),
+  });
+  Ok((
+    channels.next().unwrap()?,
+    channels.next().unwrap()?,
+    channels.next().unwrap()?,
+  ))
+}
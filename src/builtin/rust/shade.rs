@@ -0,0 +1,24 @@
+use crate::{
+  error::Error,
+  helpers::{fraction, validate_params},
+  object::{Color, Object},
+  visitor::Context,
+};
+
+pub fn lighten(_: &Context, args: Vec<Object>) -> Result<Object, Error> {
+  let (color, amount) = shade(args)?;
+  Ok(Object::Color(color.lighten(amount)))
+}
+
+pub fn darken(_: &Context, args: Vec<Object>) -> Result<Object, Error> {
+  let (color, amount) = shade(args)?;
+  Ok(Object::Color(color.lighten(-amount)))
+}
+
+fn shade(mut args: Vec<Object>) -> Result<(Color, f64), Error> {
+  validate_params(&args, 2)?;
+  match (args.remove(0), args.remove(0)) {
+    (Object::Color(color), amount) => Ok((color, fraction(amount)?)),
+    (obj, _) => Err(Error::type_error("color", obj)),
+  }
+}
@@ -0,0 +1,47 @@
+use crate::{
+  error::Error, helpers::validate_params, interpreter::FromObject, object::Object, visitor::Context,
+};
+use std::cmp::Ordering;
+
+pub fn sort_by(context: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 2)?;
+  let (array, function) = match (args.remove(0), args.remove(0)) {
+    (Object::Array(array), function) => (array, function),
+    (obj, _) => return Err(Error::type_error("array", obj)),
+  };
+
+  let mut keyed = Vec::with_capacity(array.len());
+  for item in array {
+    keyed.push((context.call(function.clone(), vec![item.clone()])?, item));
+  }
+
+  let mut error = None;
+  keyed.sort_by(|(left, _), (right, _)| match compare(left, right) {
+    Ok(ordering) => ordering,
+    Err(err) => {
+      error.get_or_insert(err);
+      Ordering::Equal
+    }
+  });
+
+  match error {
+    Some(err) => Err(err),
+    None => Ok(Object::Array(
+      keyed.into_iter().map(|(_, item)| item).collect(),
+    )),
+  }
+}
+
+// Orders keys the way '<' and '>' do. Numbers only lack such an order when
+// one of them is NaN; `total_cmp` on the absolute values sorts NaN last
+// whatever its sign bit.
+fn compare(left: &Object, right: &Object) -> Result<Ordering, Error> {
+  match left.compare_to(right) {
+    Ok(Some(ordering)) => Ok(ordering),
+    Ok(None) => {
+      let left = f64::from_object(left.clone())?.abs();
+      Ok(left.total_cmp(&f64::from_object(right.clone())?.abs()))
+    }
+    Err(..) => Err(Error::type_mismatch("sort_by", left.clone(), right.clone())),
+  }
+}
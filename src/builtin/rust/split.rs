@@ -0,0 +1,24 @@
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
+
+pub fn split(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 2)?;
+
+  let parts: Vec<String> = match (args.remove(0), args.remove(0)) {
+    (Object::String(string), Object::Regex(regex)) => {
+      regex.split(&string).map(|part| part.to_owned()).collect()
+    }
+    (Object::String(string), Object::String(separator)) if separator.is_empty() => {
+      string.chars().map(|c| c.to_string()).collect()
+    }
+    (Object::String(string), Object::String(separator)) => string
+      .split(&separator)
+      .map(|part| part.to_owned())
+      .collect(),
+    (Object::String(_), obj) => return Err(Error::type_error("string or regex", obj)),
+    (obj, _) => return Err(Error::type_error("string", obj)),
+  };
+
+  Ok(Object::Array(
+    parts.into_iter().map(Object::String).collect(),
+  ))
+}
@@ -1,4 +1,8 @@
-use crate::{ast::Statement, lexer::lex, object::Object, parser::Parser, visitor::Visitor};
+use crate::{
+  ast::Statement, error::ErrorType, lexer::lex, object::Object, optimizer::optimize,
+  parser::Parser, visitor::Visitor,
+};
+use std::collections::HashMap;
 
 #[test]
 fn array_push() {
@@ -75,6 +79,21 @@ fn empty_string_last() {
   assert_eq!(visit(input), Object::Null)
 }
 
+#[test]
+fn multibyte_string_first_last_and_rest() {
+  let inputs = vec![
+    ("first('é')", Object::String("é".to_owned())),
+    ("last('hé')", Object::String("é".to_owned())),
+    ("rest('héllo')", Object::String("éllo".to_owned())),
+    ("rest('😀')", Object::String("".to_owned())),
+    ("rest('')", Object::Null),
+  ];
+
+  for (input, expected) in inputs {
+    assert_eq!(visit(input), expected, "{}", input);
+  }
+}
+
 #[test]
 fn empty_array_last() {
   let input = "last([])";
@@ -87,6 +106,559 @@ fn print() {
   assert_eq!(visit(input), Object::Null)
 }
 
+#[test]
+fn array_map() {
+  let input = "map([1, 2, 3], |x| x * 2)";
+  assert_eq!(visit(input), array(vec![2, 4, 6]))
+}
+
+#[test]
+fn array_filter() {
+  let input = "filter([1, 2, 3, 4], |x| x > 2)";
+  assert_eq!(visit(input), array(vec![3, 4]))
+}
+
+#[test]
+fn array_reduce() {
+  let input = "reduce([1, 2, 3, 4], |acc, x| acc + x, 10)";
+  assert_eq!(visit(input), Object::Integer(20))
+}
+
+#[test]
+fn array_reduce_without_initial() {
+  let input = "reduce([1, 2, 3, 4], |acc, x| acc * x)";
+  assert_eq!(visit(input), Object::Integer(24))
+}
+
+#[test]
+fn empty_array_reduce() {
+  let input = "reduce([], |acc, x| acc + x, 0)";
+  assert_eq!(visit(input), Object::Integer(0))
+}
+
+#[test]
+fn array_find() {
+  let input = "find([1, 2, 3, 4], |x| x > 2)";
+  assert_eq!(visit(input), Object::Integer(3))
+}
+
+#[test]
+fn array_find_missing() {
+  let input = "find([1, 2], |x| x > 2)";
+  assert_eq!(visit(input), Object::Null)
+}
+
+#[test]
+fn array_any_and_all() {
+  let input = "[any([1, 2], |x| x > 1), all([1, 2], |x| x > 1)]";
+  assert_eq!(
+    visit(input),
+    Object::Array(vec![Object::TRUE, Object::FALSE])
+  )
+}
+
+#[test]
+fn array_sort_by() {
+  let input = "sort_by(['ccc', 'a', 'bb'], len)";
+  assert_eq!(
+    visit(input),
+    Object::Array(vec![
+      Object::String("a".to_owned()),
+      Object::String("bb".to_owned()),
+      Object::String("ccc".to_owned()),
+    ])
+  );
+
+  let input = "sort_by([1, 2.5, -1, 0.5], |x| x)";
+  assert_eq!(
+    visit(input),
+    Object::Array(vec![
+      Object::Integer(-1),
+      Object::Float(0.5),
+      Object::Integer(1),
+      Object::Float(2.5),
+    ])
+  );
+
+  let input = "let nan = 0.0 / 0.0; sort_by([nan, 2, 1.5], |x| x)";
+  match visit(input) {
+    Object::Array(items) => {
+      assert_eq!(items[..2], [Object::Float(1.5), Object::Integer(2)]);
+      assert!(matches!(items[2], Object::Float(value) if value.is_nan()));
+    }
+    obj => panic!("expected an array, got {}", obj),
+  }
+}
+
+#[test]
+fn array_group_by() {
+  let input = "group_by([1, 2, 3, 4, 5], |x| x > 2)";
+
+  let mut expected = HashMap::new();
+  expected.insert("false".to_owned(), array(vec![1, 2]));
+  expected.insert("true".to_owned(), array(vec![3, 4, 5]));
+
+  assert_eq!(visit(input), Object::Hash(expected))
+}
+
+#[test]
+fn array_zip() {
+  let input = "zip([1, 2, 3], [4, 5])";
+  assert_eq!(
+    visit(input),
+    Object::Array(vec![array(vec![1, 4]), array(vec![2, 5])])
+  )
+}
+
+#[test]
+fn array_enumerate() {
+  let input = "enumerate([5, 6])";
+  assert_eq!(
+    visit(input),
+    Object::Array(vec![array(vec![0, 5]), array(vec![1, 6])])
+  )
+}
+
+#[test]
+fn array_flat_map() {
+  let input = "flat_map([1, 2], |x| [x, x * 10])";
+  assert_eq!(visit(input), array(vec![1, 10, 2, 20]))
+}
+
+#[test]
+fn callback_errors_propagate() {
+  let input = "map([1, 2], |x, y| x)";
+  let program = parse(Parser::new(lex(input)));
+  let error = Visitor::new().visit(&program).unwrap_err();
+  assert_eq!(error.error_type(), &ErrorType::WrongParameters)
+}
+
+#[test]
+fn errors_have_call_location() {
+  let input = "let x = 1
+    let y = len(x)";
+  let program = parse(Parser::new(lex(input)));
+  let error = Visitor::new().visit(&program).unwrap_err();
+  let location = error.location().unwrap();
+
+  assert_eq!((location.line, location.column), (2, 13));
+  assert!(error.to_string().starts_with("TypeError at 2:13"))
+}
+
+#[test]
+fn json_parse_values() {
+  let input = r#"json_parse('{"name": "caf\\u00e9 \\ud83d\\ude00", "list": [1, -2.5e1, true, null], "empty": {}}')"#;
+  let mut expected = HashMap::new();
+  expected.insert("name".to_owned(), Object::String("café 😀".to_owned()));
+  expected.insert(
+    "list".to_owned(),
+    Object::Array(vec![
+      Object::Integer(1),
+      Object::Float(-25.0),
+      Object::TRUE,
+      Object::Null,
+    ]),
+  );
+  expected.insert("empty".to_owned(), Object::Hash(HashMap::new()));
+
+  assert_eq!(visit(input), Object::Hash(expected))
+}
+
+#[test]
+fn json_parse_error_positions() {
+  let inputs = vec![
+    (
+      "json_parse('{\"a\": 1,\n  \"b\" 2}')",
+      "invalid JSON at 2:7: expected ':', got '2'",
+    ),
+    (
+      "json_parse('[1, 2')",
+      "invalid JSON at 1:6: unexpected end of input",
+    ),
+    ("json_parse('[01]')", "invalid JSON at 1:3: unexpected '1'"),
+  ];
+
+  for (input, message) in inputs {
+    let program = parse(Parser::new(lex(input)));
+    let error = Visitor::new().visit(&program).unwrap_err();
+    assert_eq!(error.error_type(), &ErrorType::JsonError);
+    assert!(error.to_string().ends_with(message), "{}", error);
+  }
+}
+
+#[test]
+fn json_parse_limits_nesting() {
+  let input = "json_parse(repeat('[', 100000))";
+  let program = parse(Parser::new(lex(input)));
+  let error = Visitor::new().visit(&program).unwrap_err();
+  assert!(
+    error
+      .to_string()
+      .ends_with("at 1:513: nested deeper than 512 levels"),
+    "{}",
+    error
+  );
+
+  let input = "len(json_parse(repeat('[', 512) + repeat(']', 512)))";
+  assert_eq!(visit(input), Object::Integer(1))
+}
+
+#[test]
+fn json_stringify_values() {
+  let input = r#"json_stringify({'b': [1, 2.5, null], 'a': 'say "hi"\n'})"#;
+  assert_eq!(
+    visit(input),
+    Object::String(r#"{"a":"say \"hi\"\n","b":[1,2.5,null]}"#.to_owned())
+  );
+
+  let input = "json_stringify({'a': [1, {}], 'b': []}, 2)";
+  assert_eq!(
+    visit(input),
+    Object::String("{\n  \"a\": [\n    1,\n    {}\n  ],\n  \"b\": []\n}".to_owned())
+  );
+}
+
+#[test]
+fn json_round_trip() {
+  let input =
+    r#"let data = {'id': 7, 'tags': ['a', 'b']}; json_parse(json_stringify(data, 4)) == data"#;
+  assert_eq!(visit(input), Object::TRUE)
+}
+
+#[test]
+fn json_stringify_rejects_functions() {
+  let input = "json_stringify([len])";
+  let program = parse(Parser::new(lex(input)));
+  let error = Visitor::new().visit(&program).unwrap_err();
+  assert_eq!(error.error_type(), &ErrorType::JsonError)
+}
+
+#[test]
+fn regex_matching() {
+  let inputs = vec![
+    (r#"'ERROR 42' =~ r"error \d+"i"#, Object::TRUE),
+    (r"matches('abc', regex('^\\w+$'))", Object::TRUE),
+    (r#"matches('a-c', r"^\w+$")"#, Object::FALSE),
+    (
+      r#"find('id: 1234, 56', r"\d+")"#,
+      Object::String("1234".to_owned()),
+    ),
+    (r#"find('none', r"\d+")"#, Object::Null),
+    (r"find([1, 2, 3], |x| x > 1)", Object::Integer(2)),
+    (
+      r#"find_all('1 a 22 b 333', r"\d+")"#,
+      Object::Array(vec![
+        Object::String("1".to_owned()),
+        Object::String("22".to_owned()),
+        Object::String("333".to_owned()),
+      ]),
+    ),
+    (
+      r#"split('a, b,c', r",\s*")"#,
+      Object::Array(vec![
+        Object::String("a".to_owned()),
+        Object::String("b".to_owned()),
+        Object::String("c".to_owned()),
+      ]),
+    ),
+  ];
+
+  for (input, expected) in inputs {
+    assert_eq!(visit(input), expected, "{}", input);
+  }
+}
+
+#[test]
+fn regex_captures() {
+  let input = r#"captures('GET /index.html 200', r"(?P<method>[A-Z]+) (?P<path>\S+) (\d+)?")"#;
+  let mut expected = HashMap::new();
+  for (key, value) in [
+    ("0", "GET /index.html 200"),
+    ("1", "GET"),
+    ("method", "GET"),
+    ("2", "/index.html"),
+    ("path", "/index.html"),
+    ("3", "200"),
+  ] {
+    expected.insert(key.to_owned(), Object::String(value.to_owned()));
+  }
+
+  assert_eq!(visit(input), Object::Hash(expected));
+  assert_eq!(visit(r#"captures('nothing', r"\d")"#), Object::Null);
+}
+
+#[test]
+fn regex_replace() {
+  let inputs = vec![
+    (
+      r#"replace('2024-01-31', r"(\d+)-(\d+)-(\d+)", '$3/$2/$1')"#,
+      "31/01/2024",
+    ),
+    (
+      r#"replace('a1b22', r"\d+", |n| '<' + n + '>')"#,
+      "a<1>b<22>",
+    ),
+  ];
+
+  for (input, expected) in inputs {
+    assert_eq!(visit(input), Object::String(expected.to_owned()));
+  }
+}
+
+#[test]
+fn invalid_regexes() {
+  for input in ["regex('(')", "regex('a', 'q')"] {
+    let program = parse(Parser::new(lex(input)));
+    let error = Visitor::new().visit(&program).unwrap_err();
+    assert_eq!(error.error_type(), &ErrorType::RegexError, "{}", input);
+  }
+}
+
+#[test]
+fn invalid_regex_literals_are_syntax_errors() {
+  let mut parser = Parser::new(lex(r#"let x = r"[""#));
+  parser.parse();
+  assert_eq!(parser.errors.len(), 1);
+  assert_eq!(parser.errors[0].error_type(), &ErrorType::RegexError);
+  assert!(parser.errors[0].location().is_some());
+}
+
+#[test]
+fn regex_literals_escape_quotes() {
+  let literal = r#"r"say \"hi\""i"#;
+  assert_eq!(visit(literal).to_string(), literal);
+  assert_eq!(visit(r#"'say "HI"' =~ r"\"hi\""i"#), Object::TRUE);
+}
+
+#[test]
+fn string_library() {
+  let inputs = vec![
+    ("join(['a', 1, true], ', ')", "a, 1, true"),
+    ("join(split('a-b-c', '-'), '+')", "a+b+c"),
+    ("join(split('héllo', ''), '.')", "h.é.l.l.o"),
+    ("trim('  \t padded \n ')", "padded"),
+    ("upper('straße')", "STRASSE"),
+    ("lower('ÀÉÎ')", "àéî"),
+    ("replace('a.b.c', '.', '::')", "a::b::c"),
+    ("replace('one two', 'o', |o| upper(o))", "One twO"),
+    ("repeat('ab', 3)", "ababab"),
+    ("pad_left('7', 3, '0')", "007"),
+    ("pad_right('né', 4)", "né  "),
+    ("pad_left('long', 2)", "long"),
+    ("format('{} + {} = {2}', 1, 2, 3)", "1 + 2 = 3"),
+    (
+      "format('{name} is {age} {{years}}', {'name': 'Ana', 'age': 30})",
+      "Ana is 30 {years}",
+    ),
+  ];
+
+  for (input, expected) in inputs {
+    assert_eq!(
+      visit(input),
+      Object::String(expected.to_owned()),
+      "{}",
+      input
+    );
+  }
+}
+
+#[test]
+fn huge_strings_fail_without_limits() {
+  let max = i64::MAX;
+  for (input, error_type) in [
+    (format!("repeat('ab', {})", max), ErrorType::LimitExceeded),
+    (
+      format!("repeat('abc', {})", max),
+      ErrorType::ArithmeticError,
+    ),
+    (
+      "repeat('ab', 10000000000)".to_owned(),
+      ErrorType::LimitExceeded,
+    ),
+    (format!("pad_left('a', {})", max), ErrorType::LimitExceeded),
+    (
+      format!("pad_right('a', {}, '€')", max),
+      ErrorType::ArithmeticError,
+    ),
+  ] {
+    let program = parse(Parser::new(lex(&input)));
+    let error = Visitor::new().visit(&program).unwrap_err();
+    assert_eq!(error.error_type(), &error_type, "{}", input);
+  }
+}
+
+#[test]
+fn string_predicates() {
+  let inputs = vec![
+    ("starts_with('helium', 'he')", Object::TRUE),
+    ("ends_with('helium', 'he')", Object::FALSE),
+    ("contains('helium', 'liu')", Object::TRUE),
+    (r#"contains('helium', r"\d")"#, Object::FALSE),
+    ("contains([1, 2], 2)", Object::TRUE),
+    ("index_of('añob', 'b')", Object::Integer(3)),
+    ("index_of('abc', 'z')", Object::Integer(-1)),
+    ("index_of([1, 2, 3], 3)", Object::Integer(2)),
+    ("len('añob')", Object::Integer(4)),
+    (
+      "chars('añ')",
+      Object::Array(vec![
+        Object::String("a".to_owned()),
+        Object::String("ñ".to_owned()),
+      ]),
+    ),
+  ];
+
+  for (input, expected) in inputs {
+    assert_eq!(visit(input), expected, "{}", input);
+  }
+}
+
+#[test]
+fn format_errors() {
+  for input in ["format('{}')", "format('{missing}', {})", "format('{oops')"] {
+    let program = parse(Parser::new(lex(input)));
+    assert!(Visitor::new().visit(&program).is_err(), "{}", input);
+  }
+}
+
+#[test]
+fn color_library() {
+  let inputs = vec![
+    ("#F80", "#ff8800"),
+    ("rgb(255, 136, 0)", "#ff8800"),
+    ("rgba(0, 0, 0, 0.5)", "#00000080"),
+    ("hsl(120, 1, 0.25)", "#008000"),
+    ("hsl(0, 0, 1, 0)", "#ffffff00"),
+    ("mix(#000, #fff)", "#808080"),
+    ("mix(#ff0000, #0000ff, 0.25)", "#bf0040"),
+    ("lighten(#800000, 0.25)", "#ff0000"),
+    ("darken(#ff0000, 0.25)", "#800000"),
+  ];
+
+  for (input, expected) in inputs {
+    assert_eq!(visit(input).to_string(), expected, "{}", input);
+  }
+}
+
+#[test]
+fn color_components() {
+  let inputs = vec![
+    ("#ff8800.red", Object::Integer(255)),
+    ("let c = #ff880080; c.green + c.blue", Object::Integer(136)),
+    ("#ff880080.alpha > 0.5", Object::TRUE),
+    ("hsl(200, 0.5, 0.4).lightness", Object::Float(0.4)),
+    ("#fff == rgb(255, 255, 255)", Object::TRUE),
+    (
+      "json_stringify([#abc])",
+      Object::String("[\"#aabbcc\"]".to_owned()),
+    ),
+  ];
+
+  for (input, expected) in inputs {
+    assert_eq!(visit(input), expected, "{}", input);
+  }
+}
+
+#[test]
+fn color_errors() {
+  for input in [
+    "#ff880",
+    "rgb(256, 0, 0)",
+    "rgba(0, 0, 0, 1.5)",
+    "mix(#fff, 1)",
+    "mix(#000, #fff, 2)",
+    "#fff.purple",
+    "hsl(120, 100, 50)",
+    "hsl(120, 0.5, -0.1)",
+    "hsl(0, 0, 1, 255)",
+    "darken(#808080, 10)",
+    "lighten(#000, -0.5)",
+  ] {
+    let mut parser = Parser::new(lex(input));
+    let program = parser.parse();
+    let failed = !parser.errors.is_empty() || Visitor::new().visit(&program).is_err();
+    assert!(failed, "{}", input);
+  }
+}
+
+#[test]
+fn math_library() {
+  let inputs = vec![
+    ("math.abs(-3)", Object::Integer(3)),
+    ("math.abs(-1.5)", Object::Float(1.5)),
+    ("math.min(3, 1.5, 2)", Object::Float(1.5)),
+    ("math.max([4, 9, 2])", Object::Integer(9)),
+    ("math.pow(2, 10)", Object::Integer(1024)),
+    ("math.pow(4, 0.5)", Object::Float(2.0)),
+    ("math.sqrt(16)", Object::Float(4.0)),
+    ("math.clamp(15, 0, 10)", Object::Integer(10)),
+    ("math.clamp(-0.5, 0, 1)", Object::Float(0.0)),
+    ("math.round(2.5)", Object::Integer(3)),
+    ("math.floor(-2.5)", Object::Integer(-3)),
+    ("math.cos(0)", Object::Float(1.0)),
+    (
+      "math.round(math.sin(math.pi / 2) * 100)",
+      Object::Integer(100),
+    ),
+    ("math.atan2(0, 1)", Object::Float(0.0)),
+    ("math.e > 2.71", Object::TRUE),
+    ("math.sum([1, 2, 3])", Object::Integer(6)),
+    ("math.sum([1, 0.5])", Object::Float(1.5)),
+    ("math.mean([1, 2, 3, 4])", Object::Float(2.5)),
+    ("math.mean([])", Object::Null),
+  ];
+
+  for (input, expected) in inputs {
+    assert_eq!(visit(input), expected, "{}", input);
+  }
+}
+
+#[test]
+fn math_abs_overflow() {
+  let input = "math.abs(-9223372036854775807 - 1)";
+  let program = parse(Parser::new(lex(input)));
+  let error = Visitor::new().visit(&program).unwrap_err();
+  assert_eq!(error.error_type(), &ErrorType::ArithmeticError)
+}
+
+#[test]
+fn hash_properties_of_locals() {
+  let input = "fn f() { let point = {'x': 1}; { [point.x, point.y] } }; f()";
+  let program = optimize(parse(Parser::new(lex(input))));
+  assert_eq!(
+    Visitor::new().visit(&program),
+    Ok(Object::Array(vec![Object::Integer(1), Object::Null]))
+  );
+}
+
+#[test]
+fn math_random_is_seedable() {
+  let input = "
+    math.seed(42)
+    let a = [math.random(), math.random_int(1, 6), math.shuffle([1, 2, 3, 4, 5])]
+    math.seed(42)
+    let b = [math.random(), math.random_int(1, 6), math.shuffle([1, 2, 3, 4, 5])]
+    a == b";
+  assert_eq!(visit(input), Object::TRUE);
+
+  let input = "
+    math.seed(7)
+    let rolls = map([1, 2, 3, 4, 5, 6, 7, 8, 9, 10], |_| math.random_int(1, 3))
+    let bounds = [math.min(rolls) > 0, math.max(rolls) < 4]
+    bounds";
+  assert_eq!(
+    visit(input),
+    Object::Array(vec![Object::TRUE, Object::TRUE])
+  );
+
+  let input = "sort_by(math.shuffle([3, 1, 2]), |x| x)";
+  assert_eq!(visit(input), array(vec![1, 2, 3]));
+}
+
+fn array(values: Vec<i64>) -> Object {
+  Object::Array(values.into_iter().map(Object::Integer).collect())
+}
+
 fn visit(input: &str) -> Object {
   let visitor = Visitor::new();
   let program = parse(Parser::new(lex(input)));
@@ -0,0 +1,10 @@
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
+
+pub fn trim(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 1)?;
+
+  match args.remove(0) {
+    Object::String(string) => Ok(Object::String(string.trim().to_owned())),
+    obj => Err(Error::type_error("string", obj)),
+  }
+}
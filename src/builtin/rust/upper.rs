@@ -0,0 +1,10 @@
+use crate::{error::Error, helpers::validate_params, object::Object, visitor::Context};
+
+pub fn upper(_: &Context, mut args: Vec<Object>) -> Result<Object, Error> {
+  validate_params(&args, 1)?;
+
+  match args.remove(0) {
+    Object::String(string) => Ok(Object::String(string.to_uppercase())),
+    obj => Err(Error::type_error("string", obj)),
+  }
+}
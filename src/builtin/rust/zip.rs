@@ -0,0 +1,27 @@
+use crate::{error::Error, object::Object, visitor::Context};
+
+pub fn zip(_: &Context, args: Vec<Object>) -> Result<Object, Error> {
+  let mut arrays = vec![];
+  for arg in args {
+    match arg {
+      Object::Array(array) => arrays.push(array.into_iter()),
+      obj => return Err(Error::type_error("array", obj)),
+    }
+  }
+
+  let mut result = vec![];
+  if arrays.is_empty() {
+    return Ok(Object::Array(result));
+  }
+
+  loop {
+    let mut tuple = Vec::with_capacity(arrays.len());
+    for array in arrays.iter_mut() {
+      match array.next() {
+        Some(item) => tuple.push(item),
+        None => return Ok(Object::Array(result)),
+      }
+    }
+    result.push(Object::Array(tuple));
+  }
+}
@@ -1,54 +1,245 @@
 use crate::{builtin, error::Error, object::Object};
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{
+  cell::RefCell,
+  collections::{HashMap, HashSet},
+  rc::Rc,
+};
 
 pub type Env = Rc<Environment>;
 
 pub fn global() -> Env {
-  let env = Rc::new(Environment {
-    store: RefCell::new(HashMap::new()),
-    parent: None,
-  });
+  let env = Rc::new(Environment::new(None));
 
-  builtin::register(&env).unwrap();
+  builtin::register(&env);
+  env.protect_defined();
   env
 }
 
 pub fn local(parent: Env) -> Env {
-  Rc::new(Environment {
-    store: RefCell::new(HashMap::new()),
-    parent: Some(parent),
-  })
+  Rc::new(Environment::new(Some(parent)))
 }
 
 #[derive(Debug, PartialEq)]
 pub struct Environment {
   store: RefCell<HashMap<String, Object>>,
+  slots: RefCell<Vec<Option<Object>>>,
+  slot_names: RefCell<HashMap<String, usize>>,
+  constants: RefCell<HashSet<String>>,
+  protected: RefCell<HashSet<String>>,
   parent: Option<Rc<Self>>,
 }
 
 impl Environment {
+  fn new(parent: Option<Rc<Self>>) -> Self {
+    Environment {
+      store: RefCell::new(HashMap::new()),
+      slots: RefCell::new(vec![]),
+      slot_names: RefCell::new(HashMap::new()),
+      constants: RefCell::new(HashSet::new()),
+      protected: RefCell::new(HashSet::new()),
+      parent,
+    }
+  }
+
   pub fn get(&self, key: &str) -> Option<Object> {
-    match self.store.borrow().get(key) {
+    match self.lookup(key) {
       None => match &self.parent {
         Some(parent) => parent.get(key),
         None => None,
       },
-      Some(obj) => Some(obj.clone()),
+      value => value,
+    }
+  }
+
+  fn lookup(&self, key: &str) -> Option<Object> {
+    match self.slot_names.borrow().get(key) {
+      Some(slot) => self.slots.borrow()[*slot].clone(),
+      None => self.store.borrow().get(key).cloned(),
+    }
+  }
+
+  fn contains(&self, key: &str) -> bool {
+    self.slot_names.borrow().contains_key(key) || self.store.borrow().contains_key(key)
+  }
+
+  pub fn get_slot(&self, depth: usize, slot: usize) -> Option<Object> {
+    let env = self.ancestor(depth)?;
+    let slots = env.slots.borrow();
+    slots.get(slot).cloned().flatten()
+  }
+
+  // Like `get`, but hands out a reference so callers that only need part of
+  // a value don't have to clone all of it.
+  pub fn inspect<T>(&self, key: &str, f: impl FnOnce(&Object) -> T) -> Option<T> {
+    {
+      let slot = self.slot_names.borrow().get(key).copied();
+      let slots = self.slots.borrow();
+      let store = self.store.borrow();
+      let value = match slot {
+        Some(slot) => slots[slot].as_ref(),
+        None => store.get(key),
+      };
+      if let Some(value) = value {
+        return Some(f(value));
+      }
+    }
+    self.parent.as_ref()?.inspect(key, f)
+  }
+
+  pub fn inspect_slot<T>(
+    &self,
+    depth: usize,
+    slot: usize,
+    f: impl FnOnce(&Object) -> T,
+  ) -> Option<T> {
+    let env = self.ancestor(depth)?;
+    let slots = env.slots.borrow();
+    slots.get(slot)?.as_ref().map(f)
+  }
+
+  pub fn names(&self) -> Vec<String> {
+    let mut names: Vec<String> = self.store.borrow().keys().cloned().collect();
+    names.extend(self.slot_names.borrow().keys().cloned());
+    if let Some(parent) = &self.parent {
+      names.extend(parent.names());
     }
+    names.sort();
+    names.dedup();
+    names
   }
 
-  pub fn update(&self, key: &str, value: Object) {
-    if self.store.borrow().contains_key(key) {
-      self.set(key, value)
+  pub fn update(&self, key: &str, value: Object) -> Result<(), Error> {
+    if self.contains(key) {
+      if self.constants.borrow().contains(key) {
+        return Err(Error::constant_assign(key));
+      }
+      self.set(key, value);
+      Ok(())
     } else {
       match &self.parent {
         Some(parent) => parent.update(key, value),
-        None => Error::undefined_variable(key).raise(),
+        None => Err(Error::undefined_variable(key)),
       }
     }
   }
 
+  pub fn update_slot(
+    &self,
+    depth: usize,
+    slot: usize,
+    key: &str,
+    value: Object,
+  ) -> Result<(), Error> {
+    match self.ancestor(depth) {
+      Some(env) if env.has_slot(slot) => {
+        if env.constants.borrow().contains(key) {
+          return Err(Error::constant_assign(key));
+        }
+        env.write_slot(slot, value);
+        Ok(())
+      }
+      _ => self.update(key, value),
+    }
+  }
+
+  pub fn define(&self, key: &str, value: Object) -> Result<(), Error> {
+    if self.constants.borrow().contains(key) {
+      return Err(Error::constant_assign(key));
+    }
+    if self.protected.borrow().contains(key) {
+      return Err(Error::shadowed_builtin(key));
+    }
+    self.set(key, value);
+    Ok(())
+  }
+
+  pub fn define_const(&self, key: &str, value: Object) -> Result<(), Error> {
+    self.define(key, value)?;
+    self.constants.borrow_mut().insert(key.to_owned());
+    Ok(())
+  }
+
+  pub fn define_slot(&self, slot: usize, key: &str, value: Object) -> Result<(), Error> {
+    if self.constants.borrow().contains(key) {
+      return Err(Error::constant_assign(key));
+    }
+    if self.protected.borrow().contains(key) {
+      return Err(Error::shadowed_builtin(key));
+    }
+    self.set_slot(slot, key, value);
+    Ok(())
+  }
+
+  pub fn define_const_slot(&self, slot: usize, key: &str, value: Object) -> Result<(), Error> {
+    self.define_slot(slot, key, value)?;
+    self.constants.borrow_mut().insert(key.to_owned());
+    Ok(())
+  }
+
   pub fn set(&self, key: &str, value: Object) {
-    self.store.borrow_mut().insert(key.to_owned(), value);
+    let slot = self.slot_names.borrow().get(key).copied();
+    match slot {
+      Some(slot) => self.write_slot(slot, value),
+      None => {
+        self.store.borrow_mut().insert(key.to_owned(), value);
+      }
+    }
+  }
+
+  pub fn set_slot(&self, slot: usize, key: &str, value: Object) {
+    self.write_slot(slot, value);
+    self.store.borrow_mut().remove(key);
+    self.slot_names.borrow_mut().insert(key.to_owned(), slot);
+  }
+
+  fn write_slot(&self, slot: usize, value: Object) {
+    let mut slots = self.slots.borrow_mut();
+    if slots.len() <= slot {
+      slots.resize(slot + 1, None);
+    }
+    slots[slot] = Some(value);
+  }
+
+  fn has_slot(&self, slot: usize) -> bool {
+    matches!(self.slots.borrow().get(slot), Some(Some(..)))
+  }
+
+  fn ancestor(&self, depth: usize) -> Option<&Self> {
+    let mut env = self;
+    for _ in 0..depth {
+      env = env.parent.as_deref()?;
+    }
+    Some(env)
+  }
+
+  pub fn define_builtin(&self, key: &str, value: Object) {
+    self.set(key, value);
+    self.constants.borrow_mut().insert(key.to_owned());
+    self.protected.borrow_mut().insert(key.to_owned());
+  }
+
+  pub fn allow_shadowing(&self, key: &str) {
+    self.protected.borrow_mut().remove(key);
+    self.constants.borrow_mut().remove(key);
+    if let Some(parent) = &self.parent {
+      parent.allow_shadowing(key)
+    }
+  }
+
+  fn protect_defined(&self) {
+    for key in self.store.borrow().keys() {
+      self.constants.borrow_mut().insert(key.clone());
+      self.protected.borrow_mut().insert(key.clone());
+    }
+  }
+
+  pub fn is_protected(&self, key: &str) -> bool {
+    if self.protected.borrow().contains(key) {
+      return true;
+    }
+    match &self.parent {
+      Some(parent) => parent.is_protected(key),
+      None => false,
+    }
   }
 }
@@ -1,10 +1,15 @@
-use crate::{lexer::Token, object::Object};
-use std::{fmt, process::exit};
+use crate::{
+  ast::{Expression, Span},
+  lexer::Token,
+  object::Object,
+};
+use std::{fmt, time::Duration};
 
 #[derive(Debug, PartialEq)]
 pub struct Error {
   message: String,
   _type: ErrorType,
+  location: Option<Span>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -12,120 +17,427 @@ pub enum ErrorType {
   UnexpectedToken,
   ExpectedExpression,
   ExpectedPrefix,
+  ExpectedPattern,
   ExpectedId,
   TypeMismatch,
   UnknownOperator,
+  ArithmeticError,
   UndefinedVariable,
   WrongParameters,
   CallError,
   TypeError,
   IndexError,
+  PropertyError,
   CannotAssign,
+  StackOverflow,
+  LimitExceeded,
+  Cancelled,
+  ConversionError,
+  JsonError,
+  RegexError,
+  PermissionDenied,
+  IoError,
 }
 
 impl Error {
-  pub fn raise(&self) -> ! {
-    eprintln!("{}", self);
-    exit(1)
-  }
-
   pub fn unexpected_token(expected: Token, got: Token) -> Self {
     Self {
       message: format!("expected {:?}, got {:?} instead", expected, got),
       _type: ErrorType::UnexpectedToken,
+      location: None,
     }
   }
 
   pub fn expected_expression(got: Token) -> Self {
     Self {
       message: format!("expected an expression, got {:?} instead", got),
-      _type: ErrorType::UnexpectedToken,
+      _type: ErrorType::ExpectedExpression,
+      location: None,
     }
   }
 
   pub fn expected_prefix(got: Token) -> Self {
     Self {
       message: format!("expected a prefix, got {:?} instead", got),
-      _type: ErrorType::UnexpectedToken,
+      _type: ErrorType::ExpectedPrefix,
+      location: None,
+    }
+  }
+
+  pub fn expected_pattern(got: Token) -> Self {
+    Self {
+      message: format!("expected a pattern, got {:?} instead", got),
+      _type: ErrorType::ExpectedPattern,
+      location: None,
     }
   }
 
   pub fn type_mismatch(operator: &str, left: Object, right: Object) -> Self {
     Self {
       message: format!("cannot use '{}' on {} and {}", operator, left, right),
-      _type: ErrorType::UnexpectedToken,
+      _type: ErrorType::TypeMismatch,
+      location: None,
     }
   }
 
   pub fn unknown_operator(operator: &str, obj: Object) -> Self {
     Self {
       message: format!("cannot use '{}' on {}", operator, obj),
-      _type: ErrorType::UnexpectedToken,
+      _type: ErrorType::UnknownOperator,
+      location: None,
+    }
+  }
+
+  pub fn division_by_zero() -> Self {
+    Self {
+      message: "division by zero".to_owned(),
+      _type: ErrorType::ArithmeticError,
+      location: None,
+    }
+  }
+
+  pub fn integer_overflow(operator: &str) -> Self {
+    Self {
+      message: format!("integer overflow in '{}'", operator),
+      _type: ErrorType::ArithmeticError,
+      location: None,
     }
   }
 
   pub fn undefined_variable(id: &str) -> Self {
     Self {
       message: format!("'{}' was used before it was defined", id),
-      _type: ErrorType::UnexpectedToken,
+      _type: ErrorType::UndefinedVariable,
+      location: None,
     }
   }
 
   pub fn wrong_parameters(expected: usize, got: usize) -> Self {
     Self {
       message: format!("expected {} parameters, got {} instead", expected, got),
+      _type: ErrorType::WrongParameters,
+      location: None,
+    }
+  }
+
+  pub fn wrong_argument_count(function: &str, expected: usize, got: usize) -> Self {
+    Self {
+      message: format!(
+        "{} expected {} parameters, got {} instead",
+        function, expected, got
+      ),
+      _type: ErrorType::WrongParameters,
+      location: None,
+    }
+  }
+
+  pub fn too_many_arguments(function: &str, expected: usize, got: usize) -> Self {
+    Self {
+      message: format!(
+        "{} expected at most {} parameters, got {} instead",
+        function, expected, got
+      ),
+      _type: ErrorType::WrongParameters,
+      location: None,
+    }
+  }
+
+  pub fn missing_argument(function: &str, parameter: &str) -> Self {
+    Self {
+      message: format!("{} is missing parameter '{}'", function, parameter),
+      _type: ErrorType::WrongParameters,
+      location: None,
+    }
+  }
+
+  pub fn unknown_argument(function: &str, parameter: &str) -> Self {
+    Self {
+      message: format!("{} has no parameter named '{}'", function, parameter),
+      _type: ErrorType::WrongParameters,
+      location: None,
+    }
+  }
+
+  pub fn duplicate_argument(function: &str, parameter: &str) -> Self {
+    Self {
+      message: format!("{} got multiple values for '{}'", function, parameter),
+      _type: ErrorType::WrongParameters,
+      location: None,
+    }
+  }
+
+  pub fn duplicate_parameter(parameter: &str) -> Self {
+    Self {
+      message: format!("parameter '{}' is declared more than once", parameter),
+      _type: ErrorType::WrongParameters,
+      location: None,
+    }
+  }
+
+  pub fn unexpected_named_argument(function: &Object) -> Self {
+    Self {
+      message: format!("{} does not accept named arguments", function),
+      _type: ErrorType::WrongParameters,
+      location: None,
+    }
+  }
+
+  pub fn unexpected_argument(argument: Expression) -> Self {
+    Self {
+      message: format!("{:?} can only be used as a function argument", argument),
       _type: ErrorType::UnexpectedToken,
+      location: None,
     }
   }
 
   pub fn call_error(obj: Object) -> Self {
     Self {
       message: format!("{} is not a function", obj),
-      _type: ErrorType::UnexpectedToken,
+      _type: ErrorType::CallError,
+      location: None,
+    }
+  }
+
+  pub fn reentrant_call(function: &str) -> Self {
+    Self {
+      message: format!("{} cannot be called while it is already running", function),
+      _type: ErrorType::CallError,
+      location: None,
     }
   }
 
   pub fn type_error(expected: &str, got: Object) -> Self {
     Self {
       message: format!("expected {:?}, got {:?} instead", expected, got),
-      _type: ErrorType::UnexpectedToken,
+      _type: ErrorType::TypeError,
+      location: None,
     }
   }
 
   pub fn index_error(iterable: Object, index: Object) -> Self {
     Self {
       message: format!("cannot index {} with {:?}", iterable, index),
-      _type: ErrorType::UnexpectedToken,
+      _type: ErrorType::IndexError,
+      location: None,
+    }
+  }
+
+  pub fn unknown_property(type_name: &str, property: &str) -> Self {
+    Self {
+      message: format!("{} has no property '{}'", type_name, property),
+      _type: ErrorType::PropertyError,
+      location: None,
     }
   }
 
   pub fn cannot_assign(obj: Object) -> Self {
     Self {
       message: format!("cannot assign to {:?}", obj),
+      _type: ErrorType::CannotAssign,
+      location: None,
+    }
+  }
+
+  pub fn constant_assign(id: &str) -> Self {
+    Self {
+      message: format!("cannot assign to constant '{}'", id),
+      _type: ErrorType::CannotAssign,
+      location: None,
+    }
+  }
+
+  pub fn shadowed_builtin(id: &str) -> Self {
+    Self {
+      message: format!("cannot redefine builtin '{}'", id),
+      _type: ErrorType::CannotAssign,
+      location: None,
+    }
+  }
+
+  pub fn stack_overflow(max_depth: usize) -> Self {
+    Self {
+      message: format!("maximum call depth of {} exceeded", max_depth),
+      _type: ErrorType::StackOverflow,
+      location: None,
+    }
+  }
+
+  pub fn step_limit(max_steps: u64) -> Self {
+    Self {
+      message: format!("maximum of {} evaluation steps exceeded", max_steps),
+      _type: ErrorType::LimitExceeded,
+      location: None,
+    }
+  }
+
+  pub fn timed_out(timeout: Duration) -> Self {
+    Self {
+      message: format!("execution timed out after {:?}", timeout),
+      _type: ErrorType::LimitExceeded,
+      location: None,
+    }
+  }
+
+  pub fn collection_too_large(size: usize, max_size: usize) -> Self {
+    Self {
+      message: format!(
+        "collection of size {} exceeds the maximum of {}",
+        size, max_size
+      ),
+      _type: ErrorType::LimitExceeded,
+      location: None,
+    }
+  }
+
+  pub fn cancelled() -> Self {
+    Self {
+      message: "execution was cancelled".to_owned(),
+      _type: ErrorType::Cancelled,
+      location: None,
+    }
+  }
+
+  pub fn conversion(message: &str) -> Self {
+    Self {
+      message: message.to_owned(),
+      _type: ErrorType::ConversionError,
+      location: None,
+    }
+  }
+
+  pub fn json(message: &str, line: usize, column: usize) -> Self {
+    Self {
+      message: format!("invalid JSON at {}:{}: {}", line, column, message),
+      _type: ErrorType::JsonError,
+      location: None,
+    }
+  }
+
+  pub fn not_serializable(obj: Object) -> Self {
+    Self {
+      message: format!("{} cannot be converted to JSON", obj),
+      _type: ErrorType::JsonError,
+      location: None,
+    }
+  }
+
+  pub fn invalid_regex(message: &str) -> Self {
+    Self {
+      message: format!("invalid regex: {}", message),
+      _type: ErrorType::RegexError,
+      location: None,
+    }
+  }
+
+  pub fn unformattable_number(literal: &str) -> Self {
+    Self {
+      message: format!("cannot format '{}' without changing the number", literal),
       _type: ErrorType::UnexpectedToken,
+      location: None,
+    }
+  }
+
+  pub fn misplaced_comment(comment: &str) -> Self {
+    Self {
+      message: format!(
+        "cannot keep '{}' inside an expression, move it between statements",
+        comment
+      ),
+      _type: ErrorType::UnexpectedToken,
+      location: None,
+    }
+  }
+
+  pub fn invalid_format(template: &str) -> Self {
+    Self {
+      message: format!("unbalanced braces in format string '{}'", template),
+      _type: ErrorType::TypeError,
+      location: None,
     }
   }
 
+  pub fn invalid_color(hex: &str) -> Self {
+    Self {
+      message: format!(
+        "{} is not a valid color, expected #rgb, #rgba, #rrggbb or #rrggbbaa",
+        hex
+      ),
+      _type: ErrorType::TypeError,
+      location: None,
+    }
+  }
+
+  pub fn permission_denied(path: &str) -> Self {
+    Self {
+      message: format!("access to '{}' is not allowed", path),
+      _type: ErrorType::PermissionDenied,
+      location: None,
+    }
+  }
+
+  pub fn io(path: &str, err: std::io::Error) -> Self {
+    Self {
+      message: format!("{}: {}", path, err),
+      _type: ErrorType::IoError,
+      location: None,
+    }
+  }
+
+  pub fn at(mut self, span: Span) -> Self {
+    if self.location.is_none() && span.is_known() {
+      self.location = Some(span);
+    }
+    self
+  }
+
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  pub fn location(&self) -> Option<Span> {
+    self.location
+  }
+
+  pub fn error_type(&self) -> &ErrorType {
+    &self._type
+  }
+
   fn type_string(&self) -> &str {
     match self._type {
       ErrorType::CallError => "CallError",
       ErrorType::IndexError => "IndexError",
+      ErrorType::PropertyError => "PropertyError",
       ErrorType::TypeError => "TypeError",
       ErrorType::CannotAssign => "CannotAssign",
       ErrorType::ExpectedExpression => "ExpectedExpression",
       ErrorType::ExpectedId => "ExpectedId",
       ErrorType::ExpectedPrefix => "ExpectedPrefix",
+      ErrorType::ExpectedPattern => "ExpectedPattern",
       ErrorType::TypeMismatch => "TypeMismatch",
       ErrorType::UndefinedVariable => "UndefinedVariable",
       ErrorType::UnexpectedToken => "UnexpectedToken",
       ErrorType::UnknownOperator => "UnknownOperator",
+      ErrorType::ArithmeticError => "ArithmeticError",
       ErrorType::WrongParameters => "WrongParameters",
+      ErrorType::StackOverflow => "StackOverflow",
+      ErrorType::LimitExceeded => "LimitExceeded",
+      ErrorType::Cancelled => "Cancelled",
+      ErrorType::ConversionError => "ConversionError",
+      ErrorType::JsonError => "JsonError",
+      ErrorType::RegexError => "RegexError",
+      ErrorType::PermissionDenied => "PermissionDenied",
+      ErrorType::IoError => "IoError",
     }
   }
 }
 
 impl fmt::Display for Error {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    write!(f, "{}:\n\t{}", self.type_string(), self.message)
+    match self.location {
+      Some(span) => write!(f, "{} at {}:\n\t{}", self.type_string(), span, self.message),
+      None => write!(f, "{}:\n\t{}", self.type_string(), self.message),
+    }
   }
 }
+
+impl std::error::Error for Error {}
@@ -0,0 +1,552 @@
+use crate::{
+  ast::{Expression, MatchArm, Parameter, Pattern, Precedence, Span, Statement, Trivia},
+  error::Error,
+  lexer::{lex, Token},
+  parser::Parser,
+};
+
+const INDENT: &str = "  ";
+const MAX_WIDTH: usize = 80;
+
+pub fn format(source: &str) -> Result<String, Vec<Error>> {
+  let mut parser = Parser::new(lex(source)).keep_comments();
+  let program = parser.parse();
+  if !parser.errors.is_empty() {
+    return Err(parser.errors);
+  }
+  check_numbers(source)?;
+
+  let output = statements(&program, 0);
+  if output.is_empty() {
+    Ok(output)
+  } else {
+    Ok(output + "\n")
+  }
+}
+
+fn statements(block: &[Statement], depth: usize) -> String {
+  let indent = INDENT.repeat(depth);
+  let rendered: Vec<Option<String>> = block
+    .iter()
+    .map(|statement| match statement {
+      Statement::Trivia(..) | Statement::Error(..) | Statement::Null => None,
+      statement => Some(self::statement(statement, depth)),
+    })
+    .collect();
+
+  let mut lines: Vec<String> = vec![];
+  for (i, statement) in block.iter().enumerate() {
+    match statement {
+      Statement::Trivia(Trivia::BlankLine) => lines.push(String::new()),
+      Statement::Trivia(Trivia::Comment(comment)) => lines.push(format!("{}{}", indent, comment)),
+      Statement::Trivia(Trivia::TrailingComment(comment)) => match lines.last_mut() {
+        Some(line) if !line.is_empty() => {
+          line.push(' ');
+          line.push_str(comment);
+        }
+        _ => lines.push(format!("{}{}", indent, comment)),
+      },
+      Statement::Error(..) | Statement::Null => (),
+      statement => {
+        let mut line = format!("{}{}", indent, rendered[i].as_deref().unwrap_or_default());
+        let next = rendered[i + 1..].iter().flatten().next();
+        if ends_with_expression(statement) && next.is_some_and(|next| continues(next)) {
+          line.push(';');
+        }
+        lines.push(line);
+      }
+    }
+  }
+
+  lines.join("\n")
+}
+
+fn ends_with_expression(statement: &Statement) -> bool {
+  matches!(
+    statement,
+    Statement::Expression(..)
+      | Statement::VariableDeclaration(..)
+      | Statement::ConstDeclaration(..)
+      | Statement::LocalDeclaration(..)
+      | Statement::LocalConstDeclaration(..)
+      | Statement::Return(..)
+  )
+}
+
+fn continues(next: &str) -> bool {
+  next.starts_with(['(', '[', '-', '!', '+'])
+}
+
+fn statement(statement: &Statement, depth: usize) -> String {
+  match statement {
+    Statement::VariableDeclaration(name, value, _)
+    | Statement::LocalDeclaration(name, value, _) => {
+      prefixed(&format!("let {} = ", name), value, depth)
+    }
+    Statement::ConstDeclaration(name, value, _)
+    | Statement::LocalConstDeclaration(name, value, _) => {
+      prefixed(&format!("const {} = ", name), value, depth)
+    }
+    Statement::Return(value) => prefixed("return ", value, depth),
+    Statement::Expression(value) => expression(value, depth),
+    Statement::WhileLoop(condition, body, _) => format!(
+      "while {} {}",
+      expression(condition, depth),
+      multiline_block(body, depth)
+    ),
+    Statement::ForLoop(variable, iterable, body, _) => format!(
+      "for {} in {} {}",
+      variable,
+      expression(iterable, depth),
+      multiline_block(body, depth)
+    ),
+    Statement::Block(..) => multiline_block(statement, depth),
+    Statement::Trivia(..) | Statement::Error(..) | Statement::Null => String::new(),
+  }
+}
+
+fn prefixed(prefix: &str, value: &Expression, depth: usize) -> String {
+  format!("{}{}", prefix, expression_at(value, depth, prefix.len()))
+}
+
+fn block_statements(body: &Statement) -> &[Statement] {
+  match body {
+    Statement::Block(block) => block,
+    statement => std::slice::from_ref(statement),
+  }
+}
+
+fn multiline_block(body: &Statement, depth: usize) -> String {
+  let block = block_statements(body);
+  if block.is_empty() {
+    return "{}".to_owned();
+  }
+  format!(
+    "{{\n{}\n{}}}",
+    statements(block, depth + 1),
+    INDENT.repeat(depth)
+  )
+}
+
+fn inline_block(body: &Statement, depth: usize) -> Option<String> {
+  match block_statements(body) {
+    [] => Some("{}".to_owned()),
+    [statement @ Statement::Expression(..)] | [statement @ Statement::Return(..)] => {
+      let inner = self::statement(statement, depth);
+      if inner.contains('\n') {
+        None
+      } else {
+        Some(format!("{{ {} }}", inner))
+      }
+    }
+    _ => None,
+  }
+}
+
+fn fits(text: &str, depth: usize, column: usize) -> bool {
+  !text.contains('\n') && INDENT.len() * depth + column + text.len() <= MAX_WIDTH
+}
+
+fn expression(expression: &Expression, depth: usize) -> String {
+  expression_at(expression, depth, 0)
+}
+
+fn expression_at(expression: &Expression, depth: usize, column: usize) -> String {
+  match expression {
+    Expression::Id(id, _) | Expression::Local(id, ..) => id.clone(),
+    Expression::Integer(value) => value.to_string(),
+    Expression::Float(value) => float(*value),
+    Expression::String(value) => quote(value),
+    Expression::Regex(regex) => regex.to_string(),
+    Expression::Color(color) => color.to_string(),
+    Expression::Boolean(value) => value.to_string(),
+    Expression::Null => "null".to_owned(),
+    Expression::Call(function, args, _) => {
+      let function = postfix_operand(function, depth);
+      let args = list("(", ")", depth, column + function.len(), |depth| {
+        args
+          .iter()
+          .map(|arg| self::expression(arg, depth))
+          .collect()
+      });
+      function + &args
+    }
+    Expression::Prefix(operator, operand) => {
+      format!("{}{}", operator, prefix_operand(operand, depth))
+    }
+    Expression::Infix(operator, left, right) if operator == "=" => {
+      let target = format!("{} = ", self::expression(left, depth));
+      let value = expression_at(right, depth, column + target.len());
+      target + &value
+    }
+    Expression::Infix(operator, left, right) => format!(
+      "{} {} {}",
+      infix_operand(left, operator, false, depth),
+      operator,
+      infix_operand(right, operator, true, depth)
+    ),
+    Expression::Conditional(condition, consequence, alternative, _) => {
+      conditional(condition, consequence, alternative, depth, column)
+    }
+    Expression::Function(name, parameters, body, _) => {
+      function(name, parameters, body, depth, column)
+    }
+    Expression::Array(items) => list("[", "]", depth, column, |depth| {
+      items
+        .iter()
+        .map(|item| self::expression(item, depth))
+        .collect()
+    }),
+    Expression::Hash(entries) => list("{", "}", depth, column, |depth| {
+      entries
+        .iter()
+        .map(|(key, value)| {
+          format!(
+            "{}: {}",
+            self::expression(key, depth),
+            self::expression(value, depth)
+          )
+        })
+        .collect()
+    }),
+    Expression::Index(indexed, index) => format!(
+      "{}[{}]",
+      postfix_operand(indexed, depth),
+      self::expression(index, depth)
+    ),
+    Expression::Property(object, name) => format!("{}.{}", postfix_operand(object, depth), name),
+    Expression::Match(value, arms) => r#match(value, arms, depth),
+    Expression::Spread(value) => format!("...{}", self::expression(value, depth)),
+    Expression::Named(name, value) => format!("{}: {}", name, self::expression(value, depth)),
+  }
+}
+
+fn is_compound(expression: &Expression) -> bool {
+  matches!(
+    expression,
+    Expression::Conditional(..) | Expression::Match(..) | Expression::Function(..)
+  )
+}
+
+fn parenthesize(expression: &Expression, wrap: bool, depth: usize) -> String {
+  if wrap {
+    format!("({})", self::expression(expression, depth))
+  } else {
+    self::expression(expression, depth)
+  }
+}
+
+fn infix_operand(operand: &Expression, operator: &str, right: bool, depth: usize) -> String {
+  let wrap = match operand {
+    Expression::Infix(inner, ..) => {
+      let (inner, outer) = (Precedence::from(inner), Precedence::from(operator));
+      inner < outer || (right && inner == outer)
+    }
+    operand => is_compound(operand) && !(right && operator == "="),
+  };
+  parenthesize(operand, wrap, depth)
+}
+
+fn prefix_operand(operand: &Expression, depth: usize) -> String {
+  let wrap = matches!(operand, Expression::Infix(..)) || is_compound(operand);
+  parenthesize(operand, wrap, depth)
+}
+
+fn postfix_operand(operand: &Expression, depth: usize) -> String {
+  let wrap =
+    matches!(operand, Expression::Infix(..) | Expression::Prefix(..)) || is_compound(operand);
+  parenthesize(operand, wrap, depth)
+}
+
+fn list<F>(open: &str, close: &str, depth: usize, column: usize, render: F) -> String
+where
+  F: Fn(usize) -> Vec<String>,
+{
+  let items = render(depth);
+  let flat = format!("{}{}{}", open, items.join(", "), close);
+  let (last, init) = match items.split_last() {
+    Some(split) => split,
+    None => return flat,
+  };
+
+  let hugs_last = init.iter().all(|item| !item.contains('\n'))
+    && last.contains('\n')
+    && fits(flat.lines().next().unwrap_or_default(), depth, column);
+  if fits(&flat, depth, column) || hugs_last {
+    return flat;
+  }
+
+  let indent = INDENT.repeat(depth + 1);
+  let items: Vec<String> = render(depth + 1)
+    .into_iter()
+    .map(|item| format!("{}{},\n", indent, item))
+    .collect();
+  format!(
+    "{}\n{}{}{}",
+    open,
+    items.concat(),
+    INDENT.repeat(depth),
+    close
+  )
+}
+
+fn conditional(
+  condition: &Expression,
+  consequence: &Statement,
+  alternative: &Option<Box<Statement>>,
+  depth: usize,
+  column: usize,
+) -> String {
+  match inline_conditional(condition, consequence, alternative, depth) {
+    Some(flat) if fits(&flat, depth, column) => flat,
+    _ => multiline_conditional(condition, consequence, alternative, depth),
+  }
+}
+
+fn multiline_conditional(
+  condition: &Expression,
+  consequence: &Statement,
+  alternative: &Option<Box<Statement>>,
+  depth: usize,
+) -> String {
+  let alternative = match alternative.as_deref() {
+    None => String::new(),
+    Some(Statement::Expression(Expression::Conditional(
+      condition,
+      consequence,
+      alternative,
+      _,
+    ))) => {
+      format!(
+        " else {}",
+        multiline_conditional(condition, consequence, alternative, depth)
+      )
+    }
+    Some(alternative) => format!(" else {}", multiline_block(alternative, depth)),
+  };
+  format!(
+    "if {} {}{}",
+    expression(condition, depth),
+    multiline_block(consequence, depth),
+    alternative
+  )
+}
+
+fn inline_conditional(
+  condition: &Expression,
+  consequence: &Statement,
+  alternative: &Option<Box<Statement>>,
+  depth: usize,
+) -> Option<String> {
+  let alternative = match alternative.as_deref() {
+    None => String::new(),
+    Some(Statement::Expression(Expression::Conditional(
+      condition,
+      consequence,
+      alternative,
+      _,
+    ))) => {
+      format!(
+        " else {}",
+        inline_conditional(condition, consequence, alternative, depth)?
+      )
+    }
+    Some(alternative) => format!(" else {}", inline_block(alternative, depth)?),
+  };
+  let condition = expression(condition, depth);
+  if condition.contains('\n') {
+    return None;
+  }
+  Some(format!(
+    "if {} {}{}",
+    condition,
+    inline_block(consequence, depth)?,
+    alternative
+  ))
+}
+
+fn function(
+  name: &Option<String>,
+  parameters: &[Parameter],
+  body: &Statement,
+  depth: usize,
+  column: usize,
+) -> String {
+  let parameters = parameters
+    .iter()
+    .map(|parameter| self::parameter(parameter, depth))
+    .collect::<Vec<_>>()
+    .join(", ");
+
+  if let (None, Statement::Expression(value)) = (name, body) {
+    return format!("|{}| {}", parameters, expression(value, depth));
+  }
+
+  let head = match name {
+    Some(name) => format!("fn {}({})", name, parameters),
+    None => format!("fn({})", parameters),
+  };
+  if let Some(body) = inline_block(body, depth) {
+    let flat = format!("{} {}", head, body);
+    if fits(&flat, depth, column) {
+      return flat;
+    }
+  }
+  format!("{} {}", head, multiline_block(body, depth))
+}
+
+fn parameter(parameter: &Parameter, depth: usize) -> String {
+  if parameter.rest {
+    return format!("...{}", parameter.name);
+  }
+  match &parameter.default {
+    Some(default) => format!("{} = {}", parameter.name, expression(default, depth)),
+    None => parameter.name.clone(),
+  }
+}
+
+fn r#match(value: &Expression, arms: &[MatchArm], depth: usize) -> String {
+  let indent = INDENT.repeat(depth + 1);
+  let arms: Vec<String> = arms
+    .iter()
+    .map(|arm| format!("{}{},\n", indent, self::arm(arm, depth + 1)))
+    .collect();
+  format!(
+    "match {} {{\n{}{}}}",
+    expression(value, depth),
+    arms.concat(),
+    INDENT.repeat(depth)
+  )
+}
+
+fn arm(arm: &MatchArm, depth: usize) -> String {
+  let guard = match &arm.guard {
+    Some(guard) => format!(" if {}", expression(guard, depth)),
+    None => String::new(),
+  };
+  let body = match &arm.body {
+    Statement::Block(..) => match inline_block(&arm.body, depth) {
+      Some(body) => body,
+      None => multiline_block(&arm.body, depth),
+    },
+    body => statement(body, depth),
+  };
+  format!("{}{} => {}", pattern(&arm.pattern, depth), guard, body)
+}
+
+fn pattern(pattern: &Pattern, depth: usize) -> String {
+  match pattern {
+    Pattern::Wildcard => "_".to_owned(),
+    Pattern::Literal(literal) => expression(literal, depth),
+    Pattern::Binding(name) => name.clone(),
+    Pattern::Array(patterns, rest) => {
+      let mut items: Vec<String> = patterns
+        .iter()
+        .map(|pattern| self::pattern(pattern, depth))
+        .collect();
+      if let Some(rest) = rest {
+        items.push(format!("...{}", rest));
+      }
+      format!("[{}]", items.join(", "))
+    }
+    Pattern::Hash(entries) => {
+      let entries: Vec<String> = entries
+        .iter()
+        .map(|(key, pattern)| match pattern {
+          Pattern::Binding(name) if name == key => key.clone(),
+          pattern => format!("{}: {}", hash_key(key), self::pattern(pattern, depth)),
+        })
+        .collect();
+      format!("{{{}}}", entries.join(", "))
+    }
+    Pattern::Or(patterns) => patterns
+      .iter()
+      .map(|pattern| self::pattern(pattern, depth))
+      .collect::<Vec<_>>()
+      .join(" | "),
+  }
+}
+
+fn hash_key(key: &str) -> String {
+  let mut lexer = lex(key);
+  match (lexer.next(), lexer.next()) {
+    (Some(Token::Id(id)), None) if id == key => key.to_owned(),
+    _ => quote(key),
+  }
+}
+
+// Number literals are printed from their parsed values, so refuse sources
+// where that would change what the author wrote: `1e3` lexes as `1` followed
+// by `e3`, and `0.30000000000000001` cannot be told apart from `0.3`.
+fn check_numbers(source: &str) -> Result<(), Vec<Error>> {
+  let mut tokens = lex(source).spanned().peekable();
+  while let Some((token, span)) = tokens.next() {
+    let printed = match token {
+      Token::Integer(..) => None,
+      Token::Float(value) => Some(float(value)),
+      _ => continue,
+    };
+    let end = match tokens.peek() {
+      Some((Token::Id(..) | Token::Integer(..) | Token::Float(..), next))
+        if next.start == span.end =>
+      {
+        next.end
+      }
+      _ if printed.is_some_and(|printed| printed != normalize_float(&source[span.clone()])) => {
+        span.end
+      }
+      _ => continue,
+    };
+    let error = Error::unformattable_number(&source[span.start..end]);
+    return Err(vec![error.at(location(source, span.start, end))]);
+  }
+  Ok(())
+}
+
+fn normalize_float(literal: &str) -> String {
+  let (whole, fraction) = literal.split_once('.').unwrap_or((literal, ""));
+  let whole = whole.trim_start_matches('0');
+  let fraction = fraction.trim_end_matches('0');
+  format!(
+    "{}.{}",
+    if whole.is_empty() { "0" } else { whole },
+    if fraction.is_empty() { "0" } else { fraction }
+  )
+}
+
+fn location(source: &str, start: usize, end: usize) -> Span {
+  let line_start = source[..start].rfind('\n').map_or(0, |newline| newline + 1);
+  Span {
+    start,
+    end,
+    line: source[..start].matches('\n').count() + 1,
+    column: start - line_start + 1,
+  }
+}
+
+fn float(value: f64) -> String {
+  let text = value.to_string();
+  if text.contains('.') {
+    text
+  } else {
+    text + ".0"
+  }
+}
+
+fn quote(string: &str) -> String {
+  let mut quoted = String::with_capacity(string.len() + 2);
+  quoted.push('\'');
+  for c in string.chars() {
+    match c {
+      '\'' => quoted.push_str("\\'"),
+      '\\' => quoted.push_str("\\\\"),
+      '\n' => quoted.push_str("\\n"),
+      '\t' => quoted.push_str("\\t"),
+      '\r' => quoted.push_str("\\r"),
+      '\0' => quoted.push_str("\\0"),
+      c => quoted.push(c),
+    }
+  }
+  quoted.push('\'');
+  quoted
+}
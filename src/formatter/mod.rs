@@ -0,0 +1,5 @@
+mod formatter;
+#[cfg(test)]
+mod test;
+
+pub use formatter::format;
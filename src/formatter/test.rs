@@ -0,0 +1,138 @@
+use super::format;
+use crate::{error::ErrorType, interpreter::Interpreter};
+
+fn assert_formats(input: &str, expected: &str) {
+  let formatted = format(input).unwrap();
+  assert_eq!(formatted, expected);
+  assert_eq!(format(&formatted).unwrap(), formatted, "not idempotent");
+}
+
+#[test]
+fn canonical_spacing_and_semicolons() {
+  assert_formats(
+    "let   a=1;let b = \"two\";\nlet add = fn(x,y){x+y;};\nadd( a,b )",
+    "let a = 1\nlet b = 'two'\nlet add = fn(x, y) { x + y }\nadd(a, b)\n",
+  );
+}
+
+#[test]
+fn keeps_semicolons_before_ambiguous_lines() {
+  assert_formats(
+    "let a = 1; -a; (a + 1) * 2; a; [a]; !a",
+    "let a = 1;\n-a;\n(a + 1) * 2\na;\n[a];\n!a\n",
+  );
+}
+
+#[test]
+fn parentheses_follow_precedence() {
+  assert_formats(
+    "(1 + 2) * 3 - (4 - 5); -(a + b); (1 * 2) + 3; (-a)[0]",
+    "(1 + 2) * 3 - (4 - 5);\n-(a + b)\n1 * 2 + 3;\n(-a)[0]\n",
+  );
+}
+
+#[test]
+fn preserves_comments_and_blank_lines() {
+  assert_formats(
+    "// header\nlet a = 1 // one\n\n\n// about f\nfn f() {\n  // inside\n  a\n}\n",
+    "// header\nlet a = 1 // one\n\n// about f\nfn f() {\n  // inside\n  a\n}\n",
+  );
+}
+
+#[test]
+fn refuses_to_move_comments_out_of_expressions() {
+  for (input, line) in [
+    ("let a = [\n  1, // one\n  2, // two\n]", 2),
+    (
+      "match x {\n  1 => 'one', // one\n  // rest\n  _ => 'other'\n}",
+      2,
+    ),
+    ("let b = [\n  // first\n  1,\n  fn() { 2 },\n]\nb", 2),
+  ] {
+    let errors = format(input).unwrap_err();
+    assert_eq!(errors[0].location().unwrap().line, line, "{}", input);
+  }
+}
+
+#[test]
+fn wraps_long_collections() {
+  assert_formats(
+    "let config = {'name': 'helium', 'version': 4, 'tags': ['fast', 'small', 'friendly']}",
+    "let config = {
+  'name': 'helium',
+  'version': 4,
+  'tags': ['fast', 'small', 'friendly'],
+}
+",
+  );
+  assert_formats(
+    "map(items, fn(item) { let doubled = item * 2; doubled })",
+    "map(items, fn(item) {
+  let doubled = item * 2
+  doubled
+})
+",
+  );
+}
+
+#[test]
+fn blocks_and_control_flow() {
+  assert_formats(
+    "while x < 3 { x = x + 1 } for i in [1] print(i)
+if a { 1 } else if b { 2 } else { 3 }
+match v { 0 | 1 => 'small', [h, ...t] if h > 1 => { print(h); t }, {name, 'id': i} => i, _ => null }",
+    "while x < 3 {
+  x = x + 1
+}
+for i in [1] {
+  print(i)
+}
+if a { 1 } else if b { 2 } else { 3 }
+match v {
+  0 | 1 => 'small',
+  [h, ...t] if h > 1 => {
+    print(h)
+    t
+  },
+  {name, id: i} => i,
+  _ => null,
+}
+",
+  );
+}
+
+#[test]
+fn formatting_preserves_behaviour() {
+  let source = "
+    fn fib(n) { if n < 2 { return n }; fib(n - 1) + fib(n - 2) }
+    let items = map([1, 2, 3], |x| x * 2) // doubled
+    let total = reduce(items, |acc, x| acc + x, 0);
+    -total;
+    [fib(10), total, 'it\\'s', 2.0, 'AA' =~ r\"a+\"i]
+  ";
+  let formatted = format(source).unwrap();
+  assert_eq!(
+    Interpreter::new().run(source),
+    Interpreter::new().run(&formatted)
+  );
+}
+
+#[test]
+fn rejects_invalid_source() {
+  let errors = format("let = 1").unwrap_err();
+  assert_eq!(errors[0].error_type(), &ErrorType::UnexpectedToken);
+}
+
+#[test]
+fn refuses_to_change_numbers() {
+  for (input, column) in [
+    ("let x = 1e3", 9),
+    ("let y = 2.5e10", 9),
+    ("let z = 1\nlet w = 100000000000000000000.5", 9),
+    ("0.30000000000000001", 1),
+  ] {
+    let errors = format(input).unwrap_err();
+    assert_eq!(errors[0].location().unwrap().column, column, "{}", input);
+  }
+  assert_formats("[007, 1.50, 00.25, 2 - x]", "[7, 1.5, 0.25, 2 - x]\n");
+}
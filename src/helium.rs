@@ -1,24 +1,17 @@
 use crate::{
-  ast::Statement, env::Env, error::Error, lexer::lex, object::Object, parser::Parser,
-  visitor::Visitor,
+  ast::Statement, env::Env, error::Error, interpreter::Interpreter, lexer::lex, object::Object,
+  optimizer::optimize, parser::Parser, visitor::Visitor, warning::Warning,
 };
 use std::fs;
 
 pub fn run(input: &str) -> Result<Object, Vec<Error>> {
-  let visitor = Visitor::new();
-  let program = parse(input)?;
-  let result = visitor.visit(&program);
-
-  match result {
-    Err(err) => Err(vec![err]),
-    Ok(ok) => Ok(ok),
-  }
+  Interpreter::new().run(input)
 }
 
 pub fn import(env: &Env, filename: &str) -> Result<(), Vec<Error>> {
   let file = fs::read_to_string(filename).unwrap();
   let visitor = Visitor::from(env.clone());
-  let program = parse(&file)?;
+  let program = optimize(parse(&file)?);
 
   match visitor.visit(&program) {
     Ok(..) => Ok(()),
@@ -26,11 +19,18 @@ pub fn import(env: &Env, filename: &str) -> Result<(), Vec<Error>> {
   }
 }
 
-fn parse(input: &str) -> Result<Vec<Statement>, Vec<Error>> {
+pub(crate) fn parse(input: &str) -> Result<Vec<Statement>, Vec<Error>> {
+  parse_with_warnings(input).map(|(program, _)| program)
+}
+
+pub(crate) fn parse_with_warnings(
+  input: &str,
+) -> Result<(Vec<Statement>, Vec<Warning>), Vec<Error>> {
   let mut parser = Parser::new(lex(input));
   let program = parser.parse();
-  if parser.errors.len() == 0 {
-    Ok(program)
+
+  if parser.errors.is_empty() {
+    Ok((program, parser.warnings))
   } else {
     Err(parser.errors)
   }
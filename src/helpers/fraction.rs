@@ -0,0 +1,9 @@
+use crate::{error::Error, object::Object};
+
+pub fn fraction(object: Object) -> Result<f64, Error> {
+  match object {
+    Object::Integer(value) if (0..=1).contains(&value) => Ok(value as f64),
+    Object::Float(value) if (0.0..=1.0).contains(&value) => Ok(value),
+    obj => Err(Error::type_error("number from 0 to 1", obj)),
+  }
+}
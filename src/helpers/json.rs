@@ -0,0 +1,390 @@
+use crate::{error::Error, object::Object};
+use std::{collections::HashMap, iter::Peekable, str::Chars};
+
+const MAX_DEPTH: usize = 512;
+
+pub fn parse_json(input: &str) -> Result<Object, Error> {
+  let mut parser = JsonParser {
+    chars: input.chars().peekable(),
+    line: 1,
+    column: 1,
+    depth: 0,
+  };
+  let value = parser.parse_value()?;
+  parser.skip_whitespace();
+  match parser.peek() {
+    None => Ok(value),
+    Some(c) => Err(parser.error(&format!("unexpected {:?} after the end of the value", c))),
+  }
+}
+
+pub fn stringify_json(object: &Object, indent: usize) -> Result<String, Error> {
+  let mut output = String::new();
+  write_value(&mut output, object, indent, 0)?;
+  Ok(output)
+}
+
+struct JsonParser<'a> {
+  chars: Peekable<Chars<'a>>,
+  line: usize,
+  column: usize,
+  depth: usize,
+}
+
+impl<'a> JsonParser<'a> {
+  fn peek(&mut self) -> Option<char> {
+    self.chars.peek().copied()
+  }
+
+  fn next(&mut self) -> Option<char> {
+    let c = self.chars.next()?;
+    if c == '\n' {
+      self.line += 1;
+      self.column = 1;
+    } else {
+      self.column += 1;
+    }
+    Some(c)
+  }
+
+  fn error(&self, message: &str) -> Error {
+    Error::json(message, self.line, self.column)
+  }
+
+  fn unexpected(&mut self) -> Error {
+    match self.peek() {
+      Some(c) => self.error(&format!("unexpected {:?}", c)),
+      None => self.error("unexpected end of input"),
+    }
+  }
+
+  fn expect(&mut self, expected: char) -> Result<(), Error> {
+    if self.peek() == Some(expected) {
+      self.next();
+      Ok(())
+    } else {
+      match self.peek() {
+        Some(c) => Err(self.error(&format!("expected {:?}, got {:?}", expected, c))),
+        None => Err(self.error(&format!("expected {:?}, got end of input", expected))),
+      }
+    }
+  }
+
+  fn skip_whitespace(&mut self) {
+    while let Some(' ') | Some('\t') | Some('\n') | Some('\r') = self.peek() {
+      self.next();
+    }
+  }
+
+  fn parse_value(&mut self) -> Result<Object, Error> {
+    self.skip_whitespace();
+    match self.peek() {
+      Some('{') => self.nested(Self::parse_object),
+      Some('[') => self.nested(Self::parse_array),
+      Some('"') => Ok(Object::String(self.parse_string()?)),
+      Some('t') => self.parse_keyword("true", Object::TRUE),
+      Some('f') => self.parse_keyword("false", Object::FALSE),
+      Some('n') => self.parse_keyword("null", Object::Null),
+      Some('-') | Some('0'..='9') => self.parse_number(),
+      _ => Err(self.unexpected()),
+    }
+  }
+
+  fn nested(&mut self, parse: fn(&mut Self) -> Result<Object, Error>) -> Result<Object, Error> {
+    if self.depth == MAX_DEPTH {
+      return Err(self.error(&format!("nested deeper than {} levels", MAX_DEPTH)));
+    }
+    self.depth += 1;
+    let value = parse(self);
+    self.depth -= 1;
+    value
+  }
+
+  fn parse_keyword(&mut self, keyword: &str, value: Object) -> Result<Object, Error> {
+    for expected in keyword.chars() {
+      if self.peek() != Some(expected) {
+        return Err(self.unexpected());
+      }
+      self.next();
+    }
+    Ok(value)
+  }
+
+  fn parse_object(&mut self) -> Result<Object, Error> {
+    self.expect('{')?;
+    let mut hash = HashMap::new();
+    self.skip_whitespace();
+    if self.peek() == Some('}') {
+      self.next();
+      return Ok(Object::Hash(hash));
+    }
+
+    loop {
+      self.skip_whitespace();
+      if self.peek() != Some('"') {
+        return Err(match self.peek() {
+          Some(c) => self.error(&format!("expected a string key, got {:?}", c)),
+          None => self.error("expected a string key, got end of input"),
+        });
+      }
+      let key = self.parse_string()?;
+      self.skip_whitespace();
+      self.expect(':')?;
+      let value = self.parse_value()?;
+      hash.insert(key, value);
+      self.skip_whitespace();
+      match self.peek() {
+        Some(',') => self.next(),
+        Some('}') => {
+          self.next();
+          return Ok(Object::Hash(hash));
+        }
+        _ => return Err(self.unexpected()),
+      };
+    }
+  }
+
+  fn parse_array(&mut self) -> Result<Object, Error> {
+    self.expect('[')?;
+    let mut array = vec![];
+    self.skip_whitespace();
+    if self.peek() == Some(']') {
+      self.next();
+      return Ok(Object::Array(array));
+    }
+
+    loop {
+      array.push(self.parse_value()?);
+      self.skip_whitespace();
+      match self.peek() {
+        Some(',') => self.next(),
+        Some(']') => {
+          self.next();
+          return Ok(Object::Array(array));
+        }
+        _ => return Err(self.unexpected()),
+      };
+    }
+  }
+
+  fn parse_string(&mut self) -> Result<String, Error> {
+    self.expect('"')?;
+    let mut string = String::new();
+    loop {
+      match self.peek() {
+        Some('"') => {
+          self.next();
+          return Ok(string);
+        }
+        Some('\\') => {
+          self.next();
+          string.push(self.parse_escape()?);
+        }
+        Some(c) if (c as u32) < 0x20 => {
+          return Err(self.error(&format!("unescaped control character {:?} in string", c)))
+        }
+        Some(c) => {
+          self.next();
+          string.push(c);
+        }
+        None => return Err(self.error("unterminated string")),
+      }
+    }
+  }
+
+  fn parse_escape(&mut self) -> Result<char, Error> {
+    let escaped = match self.peek() {
+      Some('"') => '"',
+      Some('\\') => '\\',
+      Some('/') => '/',
+      Some('b') => '\u{8}',
+      Some('f') => '\u{c}',
+      Some('n') => '\n',
+      Some('r') => '\r',
+      Some('t') => '\t',
+      Some('u') => {
+        self.next();
+        return self.parse_unicode_escape();
+      }
+      Some(c) => return Err(self.error(&format!("invalid escape {:?}", c))),
+      None => return Err(self.error("unterminated string")),
+    };
+    self.next();
+    Ok(escaped)
+  }
+
+  fn parse_unicode_escape(&mut self) -> Result<char, Error> {
+    let high = self.parse_hex()?;
+    if !(0xD800..0xDC00).contains(&high) {
+      return char::from_u32(high).ok_or_else(|| self.error("invalid unicode escape"));
+    }
+
+    self.expect('\\')?;
+    self.expect('u')?;
+    let low = self.parse_hex()?;
+    if !(0xDC00..0xE000).contains(&low) {
+      return Err(self.error("invalid unicode surrogate pair"));
+    }
+    let code = 0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00);
+    char::from_u32(code).ok_or_else(|| self.error("invalid unicode escape"))
+  }
+
+  fn parse_hex(&mut self) -> Result<u32, Error> {
+    let mut code = 0;
+    for _ in 0..4 {
+      match self.peek().and_then(|c| c.to_digit(16)) {
+        Some(digit) => {
+          self.next();
+          code = code * 16 + digit;
+        }
+        None => return Err(self.unexpected()),
+      }
+    }
+    Ok(code)
+  }
+
+  fn parse_number(&mut self) -> Result<Object, Error> {
+    let (line, column) = (self.line, self.column);
+    let mut number = String::new();
+    let mut is_float = false;
+
+    if self.peek() == Some('-') {
+      number.push('-');
+      self.next();
+    }
+    match self.peek() {
+      Some('0') => {
+        number.push('0');
+        self.next();
+      }
+      Some('1'..='9') => self.push_digits(&mut number),
+      _ => return Err(self.unexpected()),
+    }
+    if self.peek() == Some('.') {
+      is_float = true;
+      number.push('.');
+      self.next();
+      self.expect_digits(&mut number)?;
+    }
+    if let Some('e') | Some('E') = self.peek() {
+      is_float = true;
+      number.push('e');
+      self.next();
+      if let Some(sign @ '+') | Some(sign @ '-') = self.peek() {
+        number.push(sign);
+        self.next();
+      }
+      self.expect_digits(&mut number)?;
+    }
+
+    if !is_float {
+      if let Ok(integer) = number.parse() {
+        return Ok(Object::Integer(integer));
+      }
+    }
+    match number.parse() {
+      Ok(float) => Ok(Object::Float(float)),
+      Err(_) => Err(Error::json(
+        &format!("invalid number {}", number),
+        line,
+        column,
+      )),
+    }
+  }
+
+  fn expect_digits(&mut self, number: &mut String) -> Result<(), Error> {
+    match self.peek() {
+      Some('0'..='9') => {
+        self.push_digits(number);
+        Ok(())
+      }
+      _ => Err(self.unexpected()),
+    }
+  }
+
+  fn push_digits(&mut self, number: &mut String) {
+    while let Some(digit @ '0'..='9') = self.peek() {
+      number.push(digit);
+      self.next();
+    }
+  }
+}
+
+fn write_value(
+  output: &mut String,
+  object: &Object,
+  indent: usize,
+  depth: usize,
+) -> Result<(), Error> {
+  match object {
+    Object::Null => output.push_str("null"),
+    Object::Boolean(value) => output.push_str(&value.to_string()),
+    Object::Integer(value) => output.push_str(&value.to_string()),
+    Object::Float(value) if value.is_finite() => output.push_str(&format!("{:?}", value)),
+    Object::String(value) => write_string(output, value),
+    Object::Color(color) => write_string(output, &color.to_string()),
+    Object::Return(value) => write_value(output, value, indent, depth)?,
+    Object::Array(array) => {
+      output.push('[');
+      for (i, item) in array.iter().enumerate() {
+        if i > 0 {
+          output.push(',');
+        }
+        write_newline(output, indent, depth + 1);
+        write_value(output, item, indent, depth + 1)?;
+      }
+      if !array.is_empty() {
+        write_newline(output, indent, depth);
+      }
+      output.push(']');
+    }
+    Object::Hash(hash) => {
+      let mut keys: Vec<&String> = hash.keys().collect();
+      keys.sort();
+      output.push('{');
+      for (i, key) in keys.iter().enumerate() {
+        if i > 0 {
+          output.push(',');
+        }
+        write_newline(output, indent, depth + 1);
+        write_string(output, key);
+        output.push(':');
+        if indent > 0 {
+          output.push(' ');
+        }
+        write_value(output, &hash[*key], indent, depth + 1)?;
+      }
+      if !hash.is_empty() {
+        write_newline(output, indent, depth);
+      }
+      output.push('}');
+    }
+    obj => return Err(Error::not_serializable(obj.clone())),
+  }
+  Ok(())
+}
+
+fn write_newline(output: &mut String, indent: usize, depth: usize) {
+  if indent > 0 {
+    output.push('\n');
+    output.push_str(&" ".repeat(indent * depth));
+  }
+}
+
+fn write_string(output: &mut String, string: &str) {
+  output.push('"');
+  for c in string.chars() {
+    match c {
+      '"' => output.push_str("\\\""),
+      '\\' => output.push_str("\\\\"),
+      '\n' => output.push_str("\\n"),
+      '\r' => output.push_str("\\r"),
+      '\t' => output.push_str("\\t"),
+      '\u{8}' => output.push_str("\\b"),
+      '\u{c}' => output.push_str("\\f"),
+      c if (c as u32) < 0x20 => output.push_str(&format!("\\u{:04x}", c as u32)),
+      c => output.push(c),
+    }
+  }
+  output.push('"');
+}
@@ -1,5 +1,11 @@
 mod comma_separated;
+mod fraction;
+mod json;
+mod unescape;
 mod validate_params;
 
 pub use comma_separated::comma_separated;
+pub use fraction::fraction;
+pub use json::{parse_json, stringify_json};
+pub use unescape::unescape;
 pub use validate_params::validate_params;
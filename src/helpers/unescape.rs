@@ -0,0 +1,22 @@
+pub fn unescape(string: &str) -> String {
+  let mut result = String::with_capacity(string.len());
+  let mut chars = string.chars();
+
+  while let Some(c) = chars.next() {
+    if c != '\\' {
+      result.push(c);
+      continue;
+    }
+
+    match chars.next() {
+      Some('n') => result.push('\n'),
+      Some('t') => result.push('\t'),
+      Some('r') => result.push('\r'),
+      Some('0') => result.push('\0'),
+      Some(other) => result.push(other),
+      None => result.push('\\'),
+    }
+  }
+
+  result
+}
@@ -0,0 +1,238 @@
+use crate::{error::Error, object::Object};
+use std::{collections::HashMap, convert::TryFrom};
+
+pub trait IntoObject {
+  fn into_object(self) -> Object;
+}
+
+pub trait FromObject: Sized {
+  fn from_object(object: Object) -> Result<Self, Error>;
+}
+
+pub trait IntoResult {
+  fn into_result(self) -> Result<Object, Error>;
+}
+
+pub trait IntoArgs {
+  fn into_args(self) -> Vec<Object>;
+}
+
+impl IntoObject for Object {
+  fn into_object(self) -> Object {
+    self
+  }
+}
+
+impl IntoObject for () {
+  fn into_object(self) -> Object {
+    Object::Null
+  }
+}
+
+impl IntoObject for bool {
+  fn into_object(self) -> Object {
+    Object::boolean(self)
+  }
+}
+
+impl IntoObject for i64 {
+  fn into_object(self) -> Object {
+    Object::Integer(self)
+  }
+}
+
+impl IntoObject for i32 {
+  fn into_object(self) -> Object {
+    Object::Integer(self as i64)
+  }
+}
+
+impl IntoObject for usize {
+  fn into_object(self) -> Object {
+    Object::Integer(self as i64)
+  }
+}
+
+impl IntoObject for f64 {
+  fn into_object(self) -> Object {
+    Object::Float(self)
+  }
+}
+
+impl IntoObject for String {
+  fn into_object(self) -> Object {
+    Object::String(self)
+  }
+}
+
+impl IntoObject for &str {
+  fn into_object(self) -> Object {
+    Object::String(self.to_owned())
+  }
+}
+
+impl<T: IntoObject> IntoObject for Option<T> {
+  fn into_object(self) -> Object {
+    match self {
+      Some(value) => value.into_object(),
+      None => Object::Null,
+    }
+  }
+}
+
+impl<T: IntoObject> IntoObject for Vec<T> {
+  fn into_object(self) -> Object {
+    Object::Array(self.into_iter().map(IntoObject::into_object).collect())
+  }
+}
+
+impl<T: IntoObject> IntoObject for HashMap<String, T> {
+  fn into_object(self) -> Object {
+    Object::Hash(
+      self
+        .into_iter()
+        .map(|(key, value)| (key, value.into_object()))
+        .collect(),
+    )
+  }
+}
+
+impl FromObject for Object {
+  fn from_object(object: Object) -> Result<Self, Error> {
+    Ok(object)
+  }
+}
+
+impl FromObject for () {
+  fn from_object(object: Object) -> Result<Self, Error> {
+    match object {
+      Object::Null => Ok(()),
+      obj => Err(Error::type_error("null", obj)),
+    }
+  }
+}
+
+impl FromObject for bool {
+  fn from_object(object: Object) -> Result<Self, Error> {
+    match object {
+      Object::Boolean(value) => Ok(value),
+      obj => Err(Error::type_error("boolean", obj)),
+    }
+  }
+}
+
+impl FromObject for i64 {
+  fn from_object(object: Object) -> Result<Self, Error> {
+    match object {
+      Object::Integer(value) => Ok(value),
+      obj => Err(Error::type_error("integer", obj)),
+    }
+  }
+}
+
+impl FromObject for i32 {
+  fn from_object(object: Object) -> Result<Self, Error> {
+    match object {
+      Object::Integer(value) => i32::try_from(value)
+        .map_err(|_| Error::type_error("32-bit integer", Object::Integer(value))),
+      obj => Err(Error::type_error("integer", obj)),
+    }
+  }
+}
+
+impl FromObject for usize {
+  fn from_object(object: Object) -> Result<Self, Error> {
+    match object {
+      Object::Integer(value) => usize::try_from(value)
+        .map_err(|_| Error::type_error("non-negative integer", Object::Integer(value))),
+      obj => Err(Error::type_error("integer", obj)),
+    }
+  }
+}
+
+impl FromObject for f64 {
+  fn from_object(object: Object) -> Result<Self, Error> {
+    match object {
+      Object::Float(value) => Ok(value),
+      Object::Integer(value) => Ok(value as f64),
+      obj => Err(Error::type_error("float", obj)),
+    }
+  }
+}
+
+impl FromObject for String {
+  fn from_object(object: Object) -> Result<Self, Error> {
+    match object {
+      Object::String(value) => Ok(value),
+      obj => Err(Error::type_error("string", obj)),
+    }
+  }
+}
+
+impl<T: FromObject> FromObject for Option<T> {
+  fn from_object(object: Object) -> Result<Self, Error> {
+    match object {
+      Object::Null => Ok(None),
+      obj => T::from_object(obj).map(Some),
+    }
+  }
+}
+
+impl<T: FromObject> FromObject for Vec<T> {
+  fn from_object(object: Object) -> Result<Self, Error> {
+    match object {
+      Object::Array(array) => array.into_iter().map(T::from_object).collect(),
+      obj => Err(Error::type_error("array", obj)),
+    }
+  }
+}
+
+impl<T: FromObject> FromObject for HashMap<String, T> {
+  fn from_object(object: Object) -> Result<Self, Error> {
+    match object {
+      Object::Hash(hash) => hash
+        .into_iter()
+        .map(|(key, value)| Ok((key, T::from_object(value)?)))
+        .collect(),
+      obj => Err(Error::type_error("hash", obj)),
+    }
+  }
+}
+
+impl<T: IntoObject> IntoResult for T {
+  fn into_result(self) -> Result<Object, Error> {
+    Ok(self.into_object())
+  }
+}
+
+impl<T: IntoObject> IntoResult for Result<T, Error> {
+  fn into_result(self) -> Result<Object, Error> {
+    self.map(IntoObject::into_object)
+  }
+}
+
+impl IntoArgs for Vec<Object> {
+  fn into_args(self) -> Vec<Object> {
+    self
+  }
+}
+
+macro_rules! into_args {
+  ($($arg:ident),*) => {
+    impl<$($arg: IntoObject),*> IntoArgs for ($($arg,)*) {
+      #[allow(non_snake_case)]
+      fn into_args(self) -> Vec<Object> {
+        let ($($arg,)*) = self;
+        vec![$($arg.into_object()),*]
+      }
+    }
+  };
+}
+
+into_args!();
+into_args!(A);
+into_args!(A, B);
+into_args!(A, B, C);
+into_args!(A, B, C, D);
+into_args!(A, B, C, D, E);
+into_args!(A, B, C, D, E, F);
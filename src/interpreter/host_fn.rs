@@ -0,0 +1,50 @@
+use super::{FromObject, IntoResult};
+use crate::{error::Error, object::BuiltIn};
+use std::cell::RefCell;
+
+pub trait HostFn<Args> {
+  fn into_builtin(self, name: &str) -> BuiltIn;
+}
+
+macro_rules! one {
+  ($ty:ident) => {
+    1
+  };
+}
+
+macro_rules! host_fn {
+  ($($arg:ident: $ty:ident),*) => {
+    impl<Function, Output, $($ty),*> HostFn<($($ty,)*)> for Function
+    where
+      Function: FnMut($($ty),*) -> Output + 'static,
+      Output: IntoResult,
+      $($ty: FromObject,)*
+    {
+      #[allow(unused_mut, unused_variables)]
+      fn into_builtin(self, name: &str) -> BuiltIn {
+        let name = name.to_owned();
+        let function = RefCell::new(self);
+        BuiltIn::new(move |_, args| {
+          let expected = 0 $(+ one!($ty))*;
+          if args.len() != expected {
+            return Err(Error::wrong_argument_count(&name, expected, args.len()));
+          }
+          let mut args = args.into_iter();
+          $(let $arg = $ty::from_object(args.next().unwrap())?;)*
+          let mut function = function
+            .try_borrow_mut()
+            .map_err(|_| Error::reentrant_call(&name))?;
+          (function)($($arg),*).into_result()
+        })
+      }
+    }
+  };
+}
+
+host_fn!();
+host_fn!(a: A);
+host_fn!(a: A, b: B);
+host_fn!(a: A, b: B, c: C);
+host_fn!(a: A, b: B, c: C, d: D);
+host_fn!(a: A, b: B, c: C, d: D, e: E);
+host_fn!(a: A, b: B, c: C, d: D, e: E, f: F);
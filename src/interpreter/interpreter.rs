@@ -0,0 +1,105 @@
+use super::{FromObject, HostFn, IntoArgs, IntoObject};
+use crate::{
+  builtin::{self, FsPolicy},
+  env::Env,
+  error::Error,
+  helium::parse_with_warnings,
+  object::{BuiltIn, Object},
+  optimizer::optimize,
+  visitor::{CancelHandle, Context, Visitor},
+  warning::Warning,
+};
+use std::{cell::RefCell, time::Duration};
+
+pub struct Interpreter {
+  visitor: Visitor,
+  warnings: RefCell<Vec<Warning>>,
+}
+
+impl Default for Interpreter {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Interpreter {
+  pub fn new() -> Self {
+    Interpreter {
+      visitor: Visitor::new(),
+      warnings: RefCell::new(vec![]),
+    }
+  }
+
+  pub fn run(&self, input: &str) -> Result<Object, Vec<Error>> {
+    self.warnings.borrow_mut().clear();
+    let (program, warnings) = parse_with_warnings(input)?;
+    self.warnings.replace(warnings);
+    let program = optimize(program);
+    self.visitor.limits().start();
+    self.visitor.visit(&program).map_err(|err| vec![err])
+  }
+
+  // Warnings from the last `run`; the interpreter never prints them itself.
+  pub fn warnings(&self) -> Vec<Warning> {
+    self.warnings.borrow().clone()
+  }
+
+  pub fn register_fn<Args, F: HostFn<Args>>(&self, name: &str, function: F) {
+    let builtin = function.into_builtin(name);
+    self.env().define_builtin(name, Object::BuiltIn(builtin));
+  }
+
+  pub fn register_builtin<F>(&self, name: &str, function: F)
+  where
+    F: Fn(&Context, Vec<Object>) -> Result<Object, Error> + 'static,
+  {
+    let builtin = BuiltIn::new(function);
+    self.env().define_builtin(name, Object::BuiltIn(builtin));
+  }
+
+  pub fn enable_fs(&self, policy: FsPolicy) {
+    builtin::register_fs(self.env(), policy)
+  }
+
+  pub fn get_global<T: FromObject>(&self, name: &str) -> Result<T, Error> {
+    match self.env().get(name) {
+      Some(value) => T::from_object(value),
+      None => Err(Error::undefined_variable(name)),
+    }
+  }
+
+  pub fn set_global<T: IntoObject>(&self, name: &str, value: T) -> Result<(), Error> {
+    self.env().define(name, value.into_object())
+  }
+
+  pub fn call_function<T: FromObject, A: IntoArgs>(&self, name: &str, args: A) -> Result<T, Error> {
+    let function = self.get_global(name)?;
+    self.visitor.limits().start();
+    let result = self.visitor.call_function(function, args.into_args())?;
+    T::from_object(result)
+  }
+
+  pub fn set_max_depth(&self, max_depth: usize) {
+    self.visitor.set_max_depth(max_depth)
+  }
+
+  pub fn set_max_steps(&self, max_steps: u64) {
+    self.visitor.limits().set_max_steps(max_steps)
+  }
+
+  pub fn set_max_collection_size(&self, max_size: usize) {
+    self.visitor.limits().set_max_collection_size(max_size)
+  }
+
+  pub fn set_timeout(&self, timeout: Duration) {
+    self.visitor.limits().set_timeout(timeout)
+  }
+
+  pub fn cancel_handle(&self) -> CancelHandle {
+    self.visitor.limits().cancel_handle()
+  }
+
+  pub fn env(&self) -> &Env {
+    &self.visitor.env
+  }
+}
@@ -0,0 +1,9 @@
+mod convert;
+mod host_fn;
+mod interpreter;
+#[cfg(test)]
+mod test;
+
+pub use convert::{FromObject, IntoArgs, IntoObject, IntoResult};
+pub use host_fn::HostFn;
+pub use interpreter::Interpreter;
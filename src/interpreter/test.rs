@@ -0,0 +1,382 @@
+use super::*;
+use crate::{
+  error::{Error, ErrorType},
+  object::{HostObject, Object},
+  visitor::Context,
+};
+use std::{
+  cell::{Cell, RefCell},
+  collections::HashMap,
+  rc::Rc,
+  thread,
+  time::Duration,
+};
+
+#[test]
+fn register_typed_functions() {
+  let interpreter = Interpreter::new();
+  interpreter.register_fn("add", |a: i64, b: i64| a + b);
+  interpreter.register_fn("greet", |name: String| format!("hello, {}", name));
+
+  assert_eq!(
+    interpreter.run("greet('helium') + '!'"),
+    Ok(Object::String("hello, helium!".to_owned()))
+  );
+  assert_eq!(interpreter.run("add(1, add(2, 3))"), Ok(Object::Integer(6)));
+}
+
+#[test]
+fn register_stateful_closures() {
+  let interpreter = Interpreter::new();
+  let mut count = 0;
+  interpreter.register_fn("tick", move || {
+    count += 1;
+    count
+  });
+
+  let log = Rc::new(RefCell::new(vec![]));
+  let sink = log.clone();
+  interpreter.register_fn("log", move |message: String| {
+    sink.borrow_mut().push(message)
+  });
+
+  interpreter.run("tick(); tick(); log('done')").unwrap();
+  assert_eq!(interpreter.run("tick()"), Ok(Object::Integer(3)));
+  assert_eq!(*log.borrow(), vec!["done".to_owned()]);
+}
+
+#[test]
+fn registered_functions_check_arguments() {
+  let interpreter = Interpreter::new();
+  interpreter.register_fn("double", |x: i64| x * 2);
+
+  let errors = interpreter.run("double(1, 2)").unwrap_err();
+  assert_eq!(errors[0].error_type(), &ErrorType::WrongParameters);
+
+  let errors = interpreter.run("double('x')").unwrap_err();
+  assert_eq!(errors[0].error_type(), &ErrorType::TypeError);
+}
+
+#[test]
+fn registered_functions_can_fail() {
+  let interpreter = Interpreter::new();
+  interpreter.register_fn("checked_div", |a: i64, b: i64| {
+    if b == 0 {
+      Err(Error::call_error(Object::Integer(a)))
+    } else {
+      Ok(a / b)
+    }
+  });
+
+  assert_eq!(interpreter.run("checked_div(6, 3)"), Ok(Object::Integer(2)));
+  assert!(interpreter.run("checked_div(6, 0)").is_err());
+}
+
+#[test]
+fn registered_functions_are_protected() {
+  let interpreter = Interpreter::new();
+  interpreter.register_fn("answer", || 42);
+
+  let errors = interpreter.run("let answer = 1").unwrap_err();
+  assert_eq!(errors[0].error_type(), &ErrorType::CannotAssign);
+}
+
+#[test]
+fn register_builtin_with_context() {
+  let interpreter = Interpreter::new();
+  interpreter.register_builtin("twice", |context, mut args| {
+    let function = args.remove(0);
+    let once = context.call(function.clone(), args)?;
+    context.call(function, vec![once])
+  });
+
+  assert_eq!(
+    interpreter.run("twice(|x| x * 3, 2)"),
+    Ok(Object::Integer(18))
+  );
+}
+
+#[test]
+fn globals() {
+  let interpreter = Interpreter::new();
+  let mut config = HashMap::new();
+  config.insert("retries".to_owned(), 3);
+  interpreter.set_global("config", config).unwrap();
+  interpreter.set_global("names", vec!["a", "b"]).unwrap();
+
+  interpreter
+    .run("let total = config['retries'] + len(names)")
+    .unwrap();
+
+  assert_eq!(interpreter.get_global::<i64>("total"), Ok(5));
+  assert_eq!(
+    interpreter.get_global::<Vec<String>>("names"),
+    Ok(vec!["a".to_owned(), "b".to_owned()])
+  );
+  assert_eq!(
+    interpreter
+      .get_global::<i64>("missing")
+      .unwrap_err()
+      .error_type(),
+    &ErrorType::UndefinedVariable
+  );
+}
+
+#[test]
+fn globals_cannot_replace_builtins_or_constants() {
+  let interpreter = Interpreter::new();
+  interpreter.run("const limit = 3").unwrap();
+
+  for name in ["len", "limit"] {
+    let err = interpreter.set_global(name, 1).unwrap_err();
+    assert_eq!(err.error_type(), &ErrorType::CannotAssign, "{}", name);
+  }
+  assert_eq!(
+    interpreter.run("[len([1]), limit]").unwrap().to_string(),
+    "[1, 3]"
+  );
+}
+
+#[test]
+fn call_functions_from_rust() {
+  let interpreter = Interpreter::new();
+  interpreter
+    .run("fn area(width, height = width) { width * height }")
+    .unwrap();
+
+  assert_eq!(interpreter.call_function::<i64, _>("area", (3, 4)), Ok(12));
+  assert_eq!(interpreter.call_function::<i64, _>("area", (5,)), Ok(25));
+  assert_eq!(
+    interpreter.call_function::<Option<i64>, _>("first", vec![Object::Array(vec![])]),
+    Ok(None)
+  );
+}
+
+#[derive(Default)]
+struct Player {
+  x: Cell<i64>,
+  y: Cell<i64>,
+  inventory: Vec<&'static str>,
+}
+
+impl HostObject for Player {
+  fn type_name(&self) -> &str {
+    "Player"
+  }
+
+  fn get(&self, property: &str) -> Option<Object> {
+    match property {
+      "x" => Some(Object::Integer(self.x.get())),
+      "y" => Some(Object::Integer(self.y.get())),
+      _ => None,
+    }
+  }
+
+  fn set(&self, property: &str, value: Object) -> Result<(), Error> {
+    let cell = match property {
+      "x" => &self.x,
+      "y" => &self.y,
+      _ => return Err(Error::unknown_property(self.type_name(), property)),
+    };
+    cell.set(i64::from_object(value)?);
+    Ok(())
+  }
+
+  fn has_method(&self, method: &str) -> bool {
+    method == "move"
+  }
+
+  fn call_method(
+    &self,
+    _context: &Context,
+    method: &str,
+    args: Vec<Object>,
+  ) -> Result<Object, Error> {
+    match (method, args.as_slice()) {
+      ("move", [Object::Integer(dx), Object::Integer(dy)]) => {
+        self.x.set(self.x.get() + dx);
+        self.y.set(self.y.get() + dy);
+        Ok(Object::Null)
+      }
+      _ => Err(Error::wrong_argument_count(method, 2, args.len())),
+    }
+  }
+
+  fn iter(&self) -> Option<Box<dyn Iterator<Item = Object>>> {
+    let items: Vec<Object> = self
+      .inventory
+      .iter()
+      .map(|item| item.into_object())
+      .collect();
+    Some(Box::new(items.into_iter()))
+  }
+
+  fn equals(&self, other: &dyn HostObject) -> bool {
+    match other.downcast_ref::<Player>() {
+      Some(other) => self.x == other.x && self.y == other.y,
+      None => false,
+    }
+  }
+}
+
+#[test]
+fn host_objects() {
+  let interpreter = Interpreter::new();
+  let player = Rc::new(Player {
+    inventory: vec!["sword", "shield"],
+    ..Player::default()
+  });
+  interpreter
+    .set_global("player", Object::Native(player.clone()))
+    .unwrap();
+
+  interpreter
+    .run("player.move(1, 2); player.x = player.x * 10; let step = player.move; step(0, 1)")
+    .unwrap();
+  assert_eq!((player.x.get(), player.y.get()), (10, 3));
+
+  assert_eq!(
+    interpreter.run("let items = []; for item in player { items = push(items, item) }; items"),
+    Ok(vec!["sword", "shield"].into_object())
+  );
+  assert_eq!(
+    interpreter.run("player"),
+    Ok(Object::Native(player.clone()))
+  );
+  assert_eq!(interpreter.run("player").unwrap().to_string(), "<Player>");
+}
+
+#[test]
+fn host_object_equality_and_errors() {
+  let interpreter = Interpreter::new();
+  interpreter
+    .set_global("a", Object::native(Player::default()))
+    .unwrap();
+  interpreter
+    .set_global("b", Object::native(Player::default()))
+    .unwrap();
+
+  assert_eq!(interpreter.run("a == b"), Ok(Object::TRUE));
+  assert_eq!(interpreter.run("a.move(1, 0); a == b"), Ok(Object::FALSE));
+
+  let errors = interpreter.run("a.jump()").unwrap_err();
+  assert_eq!(errors[0].error_type(), &ErrorType::PropertyError);
+  let errors = interpreter.run("a.z = 1").unwrap_err();
+  assert_eq!(errors[0].error_type(), &ErrorType::PropertyError);
+}
+
+#[test]
+fn hash_properties() {
+  let interpreter = Interpreter::new();
+
+  assert_eq!(
+    interpreter.run("let point = {'x': 1, 'y': 2}; point.x + point.y"),
+    Ok(Object::Integer(3))
+  );
+  assert_eq!(interpreter.run("point.z"), Ok(Object::Null));
+}
+
+fn assert_fails(interpreter: &Interpreter, input: &str, expected: ErrorType) {
+  let errors = interpreter.run(input).unwrap_err();
+  assert_eq!(errors[0].error_type(), &expected, "{}", input);
+}
+
+#[test]
+fn step_limits_stop_infinite_loops() {
+  let interpreter = Interpreter::new();
+  interpreter.set_max_steps(10_000);
+
+  assert_fails(&interpreter, "while true {}", ErrorType::LimitExceeded);
+  assert_eq!(
+    interpreter.run("let i = 0; while i < 100 { i = i + 1 }; i"),
+    Ok(Object::Integer(100))
+  );
+}
+
+#[test]
+fn timeouts_stop_long_running_scripts() {
+  let interpreter = Interpreter::new();
+  interpreter.set_timeout(Duration::from_millis(50));
+
+  assert_fails(&interpreter, "while true {}", ErrorType::LimitExceeded);
+  assert_eq!(interpreter.run("1 + 1"), Ok(Object::Integer(2)));
+}
+
+#[test]
+fn collection_size_limits() {
+  let interpreter = Interpreter::new();
+  interpreter.set_max_collection_size(100);
+
+  for input in [
+    "let items = []; while true { items = push(items, 1) }",
+    "let text = 'x'; while true { text = text + text }",
+    "let a = [1]; while true { a = [a, a] }",
+    "let h = {'k': 1}; while true { h = {'l': h, 'r': h} }",
+    "repeat('x', 1000000000000)",
+    "pad_left('', 1000)",
+  ] {
+    assert_fails(&interpreter, input, ErrorType::LimitExceeded);
+  }
+  assert_eq!(
+    interpreter.run("len(repeat('ab', 50))"),
+    Ok(Object::Integer(100))
+  );
+}
+
+#[test]
+fn cancel_from_another_thread() {
+  let interpreter = Interpreter::new();
+  let handle = interpreter.cancel_handle();
+  let canceller = thread::spawn(move || {
+    thread::sleep(Duration::from_millis(20));
+    handle.cancel();
+  });
+
+  assert_fails(&interpreter, "while true {}", ErrorType::Cancelled);
+  canceller.join().unwrap();
+  assert_eq!(interpreter.run("1 + 1"), Ok(Object::Integer(2)));
+}
+
+#[test]
+fn cancel_before_run() {
+  let interpreter = Interpreter::new();
+  interpreter.cancel_handle().cancel();
+
+  assert_fails(&interpreter, "while true {}", ErrorType::Cancelled);
+  assert_eq!(
+    interpreter.run("let i = 0; while i < 2000 { i = i + 1 }; i"),
+    Ok(Object::Integer(2000))
+  );
+}
+
+#[test]
+fn arithmetic_errors_do_not_exit() {
+  let interpreter = Interpreter::new();
+
+  for input in [
+    "1 / 0",
+    "9223372036854775807 + 1",
+    "-(-9223372036854775807 - 1)",
+  ] {
+    assert_fails(&interpreter, input, ErrorType::ArithmeticError);
+  }
+  for input in ["1 + 'a'", "null < 1", "-'a'"] {
+    assert!(interpreter.run(input).is_err(), "{}", input);
+  }
+}
+
+#[test]
+fn warnings_are_returned_from_the_last_run() {
+  let interpreter = Interpreter::new();
+
+  assert_eq!(
+    interpreter.run("match 1 { 1 => 2 }"),
+    Ok(Object::Integer(2))
+  );
+  let warnings = interpreter.warnings();
+  assert_eq!(warnings.len(), 1);
+  assert!(warnings[0].message().contains("no '_' arm"));
+
+  interpreter.run("1").unwrap();
+  assert!(interpreter.warnings().is_empty());
+}
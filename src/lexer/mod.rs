@@ -11,12 +11,18 @@ pub enum Token<'a> {
   Id(&'a str),
   #[regex(r"\d+", |lex| lex.slice().parse())]
   Integer(i64),
+  #[regex(r"\d+\.\d+", |lex| lex.slice().parse())]
+  Float(f64),
   #[regex(r#""(?:\\.|[^"\\])*""#, remove_quotes)]
   #[regex(r#"'(?:\\.|[^'\\])*'"#, remove_quotes)]
   String(&'a str),
+  #[regex(r#"r"(?:\\.|[^"\\])*"[a-zA-Z]*"#)]
+  Regex(&'a str),
+  #[regex(r"#[0-9a-fA-F]+")]
+  Color(&'a str),
 
   // Operators
-  #[regex(r"(\.|([+\-*/<>!=]=?))")]
+  #[regex(r"(\.|=~|([+\-*/<>!=]=?))")]
   Operator(&'a str),
 
   // Delimiters
@@ -38,12 +44,20 @@ pub enum Token<'a> {
   LeftBracket,
   #[token("]")]
   RightBracket,
+  #[token("=>")]
+  Arrow,
+  #[token("|")]
+  Pipe,
+  #[token("...")]
+  Ellipsis,
 
   // Keywords
   #[token("fn")]
   Function,
   #[token("let")]
   Let,
+  #[token("const")]
+  Const,
   #[token("true")]
   True,
   #[token("false")]
@@ -60,9 +74,17 @@ pub enum Token<'a> {
   While,
   #[token("in")]
   In,
+  #[token("match")]
+  Match,
+  #[token("null")]
+  Null,
 
   // Special
-  #[regex(r"[ \n\t\f]+", logos::skip)]
+  #[regex(r"//[^\n]*")]
+  Comment(&'a str),
+  #[regex(r"\n[ \n\t\f]*")]
+  Newline,
+  #[regex(r"[ \t\f]+", logos::skip)]
   #[error]
   Illegal,
   Eof,
@@ -72,6 +94,6 @@ fn remove_quotes<'a>(lex: &mut Lexer<'a, Token<'a>>) -> Option<&'a str> {
   Some(&lex.slice()[1..lex.slice().len() - 1])
 }
 
-pub fn lex<'a>(input: &'a str) -> Lexer<'a, Token> {
+pub fn lex<'a>(input: &'a str) -> Lexer<'a, Token<'a>> {
   Token::lexer(input)
 }
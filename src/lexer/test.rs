@@ -363,7 +363,7 @@ fn symbols() {
 }
 
 fn compare(input: &str, expected_tokens: Vec<Token>) {
-  let mut lexer = lex(input);
+  let mut lexer = lex(input).filter(|token| *token != Token::Newline);
 
   for expected in expected_tokens {
     let actual = lexer.next();
@@ -371,3 +371,139 @@ fn compare(input: &str, expected_tokens: Vec<Token>) {
   }
   assert_eq!(None, lexer.next())
 }
+
+#[test]
+fn match_expressions() {
+  let input = "match x { 1 | 2 => null, [first, ...rest] => first, _ => x }";
+
+  let expected = vec![
+    Token::Match,
+    Token::Id("x"),
+    Token::LeftBrace,
+    Token::Integer(1),
+    Token::Pipe,
+    Token::Integer(2),
+    Token::Arrow,
+    Token::Null,
+    Token::Comma,
+    Token::LeftBracket,
+    Token::Id("first"),
+    Token::Comma,
+    Token::Ellipsis,
+    Token::Id("rest"),
+    Token::RightBracket,
+    Token::Arrow,
+    Token::Id("first"),
+    Token::Comma,
+    Token::Id("_"),
+    Token::Arrow,
+    Token::Id("x"),
+    Token::RightBrace,
+  ];
+
+  compare(input, expected)
+}
+
+#[test]
+fn const_declarations() {
+  let input = "const x = 1";
+
+  let expected = vec![
+    Token::Const,
+    Token::Id("x"),
+    Token::Operator("="),
+    Token::Integer(1),
+  ];
+
+  compare(input, expected)
+}
+
+#[test]
+fn floats() {
+  let input = "1.5 + 10.25";
+
+  let expected = vec![Token::Float(1.5), Token::Operator("+"), Token::Float(10.25)];
+
+  compare(input, expected)
+}
+
+#[test]
+fn regexes() {
+  let input = r#"line =~ r"(\d+)/\w+"i"#;
+
+  let expected = vec![
+    Token::Id("line"),
+    Token::Operator("=~"),
+    Token::Regex(r#"r"(\d+)/\w+"i"#),
+  ];
+
+  compare(input, expected)
+}
+
+#[test]
+fn division_is_not_a_regex() {
+  let input = "r/2 + y/3";
+
+  let expected = vec![
+    Token::Id("r"),
+    Token::Operator("/"),
+    Token::Integer(2),
+    Token::Operator("+"),
+    Token::Id("y"),
+    Token::Operator("/"),
+    Token::Integer(3),
+  ];
+
+  compare(input, expected)
+}
+
+#[test]
+fn colors() {
+  let input = "mix(#ff8800, #F80a)";
+
+  let expected = vec![
+    Token::Id("mix"),
+    Token::LeftParen,
+    Token::Color("#ff8800"),
+    Token::Comma,
+    Token::Color("#F80a"),
+    Token::RightParen,
+  ];
+
+  compare(input, expected)
+}
+
+#[test]
+fn comments() {
+  let input = "// note\na / b // half";
+
+  let expected = vec![
+    Token::Comment("// note"),
+    Token::Id("a"),
+    Token::Operator("/"),
+    Token::Id("b"),
+    Token::Comment("// half"),
+  ];
+
+  compare(input, expected)
+}
+
+#[test]
+fn newlines() {
+  let input = "a\n\n  (b) // c\n\t-d\n";
+
+  let expected = vec![
+    Token::Id("a"),
+    Token::Newline,
+    Token::LeftParen,
+    Token::Id("b"),
+    Token::RightParen,
+    Token::Comment("// c"),
+    Token::Newline,
+    Token::Operator("-"),
+    Token::Id("d"),
+    Token::Newline,
+  ];
+
+  assert_eq!(lex(input).collect::<Vec<_>>(), expected);
+}
@@ -1,14 +1,23 @@
+#![allow(clippy::module_inception)]
 pub mod ast;
 pub mod builtin;
 // pub mod bytecode;
 // pub mod compiler;
 pub mod env;
 pub mod error;
+pub mod formatter;
 pub mod helium;
 pub mod helpers;
+pub mod interpreter;
 pub mod lexer;
+pub mod linter;
+#[cfg(feature = "lsp")]
+pub mod lsp;
 pub mod object;
+pub mod optimizer;
 pub mod parser;
+#[cfg(feature = "repl")]
 pub mod repl;
 pub mod visitor;
+pub mod warning;
 // pub mod vm;
@@ -0,0 +1,49 @@
+use super::Diagnostic;
+use crate::ast::Span;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum DefinitionKind {
+  Variable,
+  Constant,
+  Parameter,
+  Loop,
+  Function,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Definition {
+  pub name: String,
+  pub kind: DefinitionKind,
+  pub span: Span,
+  pub detail: String,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Reference {
+  pub span: Span,
+  pub definition: usize,
+}
+
+#[derive(Debug, Default)]
+pub struct Analysis {
+  pub diagnostics: Vec<Diagnostic>,
+  pub definitions: Vec<Definition>,
+  pub references: Vec<Reference>,
+}
+
+impl Analysis {
+  pub fn definition_at(&self, offset: usize) -> Option<&Definition> {
+    let contains = |span: &Span| span.start <= offset && offset <= span.end;
+    self
+      .references
+      .iter()
+      .find(|reference| contains(&reference.span))
+      .map(|reference| &self.definitions[reference.definition])
+      .or_else(|| {
+        self
+          .definitions
+          .iter()
+          .find(|definition| contains(&definition.span))
+      })
+  }
+}
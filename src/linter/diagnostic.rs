@@ -0,0 +1,70 @@
+use crate::ast::Span;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Copy)]
+pub enum Rule {
+  UnusedVariable,
+  UseBeforeDefinition,
+  ShadowedBuiltin,
+  UnreachableCode,
+  UndeclaredAssignment,
+  ConstantCondition,
+  WrongArity,
+}
+
+impl Rule {
+  pub const ALL: [Rule; 7] = [
+    Rule::UnusedVariable,
+    Rule::UseBeforeDefinition,
+    Rule::ShadowedBuiltin,
+    Rule::UnreachableCode,
+    Rule::UndeclaredAssignment,
+    Rule::ConstantCondition,
+    Rule::WrongArity,
+  ];
+
+  pub fn id(&self) -> &'static str {
+    match self {
+      Rule::UnusedVariable => "unused-variable",
+      Rule::UseBeforeDefinition => "use-before-definition",
+      Rule::ShadowedBuiltin => "shadowed-builtin",
+      Rule::UnreachableCode => "unreachable-code",
+      Rule::UndeclaredAssignment => "undeclared-assignment",
+      Rule::ConstantCondition => "constant-condition",
+      Rule::WrongArity => "wrong-arity",
+    }
+  }
+
+  pub fn from_id(id: &str) -> Option<Rule> {
+    Rule::ALL.iter().copied().find(|rule| rule.id() == id)
+  }
+}
+
+impl fmt::Display for Rule {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}", self.id())
+  }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Diagnostic {
+  pub rule: Rule,
+  pub message: String,
+  pub span: Span,
+}
+
+impl Diagnostic {
+  pub fn new(rule: Rule, message: String, span: Span) -> Self {
+    Self {
+      rule,
+      message,
+      span,
+    }
+  }
+}
+
+impl fmt::Display for Diagnostic {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "{}: {} [{}]", self.span, self.message, self.rule)
+  }
+}
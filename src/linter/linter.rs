@@ -0,0 +1,569 @@
+use super::{Analysis, Definition, DefinitionKind, Diagnostic, Reference, Rule};
+use crate::{
+  ast::{Expression, MatchArm, Parameter, Span, Statement},
+  env,
+  error::Error,
+  helpers::comma_separated,
+  lexer::lex,
+  parser::Parser,
+};
+use std::collections::{HashMap, HashSet};
+
+pub fn lint(source: &str) -> Result<Vec<Diagnostic>, Vec<Error>> {
+  Linter::new().lint(source)
+}
+
+pub struct Linter {
+  disabled: HashSet<Rule>,
+  builtins: HashSet<String>,
+}
+
+impl Linter {
+  pub fn new() -> Self {
+    Self {
+      disabled: HashSet::new(),
+      builtins: env::global().names().into_iter().collect(),
+    }
+  }
+
+  pub fn disable(mut self, rule: Rule) -> Self {
+    self.disabled.insert(rule);
+    self
+  }
+
+  pub fn lint(&self, source: &str) -> Result<Vec<Diagnostic>, Vec<Error>> {
+    let mut parser = Parser::new(lex(source));
+    let program = parser.parse();
+    if parser.errors.is_empty() {
+      Ok(self.lint_program(&program))
+    } else {
+      Err(parser.errors)
+    }
+  }
+
+  pub fn lint_program(&self, program: &[Statement]) -> Vec<Diagnostic> {
+    self.analyze(program).diagnostics
+  }
+
+  pub fn analyze(&self, program: &[Statement]) -> Analysis {
+    let mut walker = Walker {
+      linter: self,
+      scopes: vec![],
+      analysis: Analysis::default(),
+      seen: None,
+      last: Span::default(),
+    };
+    walker.push(false);
+    walker.block(program);
+    walker.pop();
+
+    let mut analysis = walker.analysis;
+    analysis
+      .diagnostics
+      .sort_by_key(|diagnostic| diagnostic.span.start);
+    analysis
+  }
+
+  pub fn is_builtin(&self, name: &str) -> bool {
+    self.builtins.contains(name)
+  }
+}
+
+impl Default for Linter {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Kind {
+  Variable,
+  Constant,
+  Parameter,
+  Loop,
+  Function,
+  Pattern,
+}
+
+#[derive(Clone, Copy)]
+struct Arity {
+  min: usize,
+  max: Option<usize>,
+}
+
+impl Arity {
+  fn of(parameters: &[Parameter]) -> Self {
+    let required = parameters
+      .iter()
+      .filter(|parameter| parameter.default.is_none() && !parameter.rest)
+      .count();
+    let max = match parameters.iter().any(|parameter| parameter.rest) {
+      true => None,
+      false => Some(parameters.len()),
+    };
+    Self { min: required, max }
+  }
+
+  fn accepts(&self, count: usize) -> bool {
+    count >= self.min && self.max.is_none_or(|max| count <= max)
+  }
+
+  fn describe(&self) -> String {
+    let (count, plural) = match self.max {
+      Some(max) if max == self.min => (max.to_string(), max != 1),
+      Some(max) => (format!("{} to {}", self.min, max), true),
+      None => (format!("at least {}", self.min), self.min != 1),
+    };
+    format!("{} argument{}", count, if plural { "s" } else { "" })
+  }
+}
+
+struct Binding {
+  kind: Kind,
+  span: Span,
+  used: bool,
+  arity: Option<Arity>,
+  definition: Option<usize>,
+}
+
+#[derive(Default)]
+struct Scope {
+  bindings: HashMap<String, Binding>,
+  later: HashSet<String>,
+  used_ahead: HashMap<String, Vec<Span>>,
+  function: bool,
+}
+
+struct Walker<'l> {
+  linter: &'l Linter,
+  scopes: Vec<Scope>,
+  analysis: Analysis,
+  seen: Option<Span>,
+  last: Span,
+}
+
+impl Walker<'_> {
+  fn report(&mut self, rule: Rule, message: String, span: Span) {
+    if !self.linter.disabled.contains(&rule) {
+      let diagnostic = Diagnostic::new(rule, message, span);
+      self.analysis.diagnostics.push(diagnostic);
+    }
+  }
+
+  fn mark(&mut self, span: Span) {
+    if span.is_known() {
+      self.seen = self.seen.or(Some(span));
+      self.last = span;
+    }
+  }
+
+  fn push(&mut self, function: bool) {
+    self.scopes.push(Scope {
+      function,
+      ..Scope::default()
+    });
+  }
+
+  fn pop(&mut self) {
+    if let Some(scope) = self.scopes.pop() {
+      for (name, binding) in scope.bindings {
+        self.check_unused(&name, &binding);
+      }
+    }
+  }
+
+  fn scope(&mut self) -> &mut Scope {
+    self.scopes.last_mut().unwrap()
+  }
+
+  fn check_unused(&mut self, name: &str, binding: &Binding) {
+    let reported = matches!(
+      binding.kind,
+      Kind::Variable | Kind::Constant | Kind::Parameter | Kind::Loop
+    );
+    if reported && !binding.used && binding.span.is_known() && !name.starts_with('_') {
+      self.report(
+        Rule::UnusedVariable,
+        format!("'{}' is never used", name),
+        binding.span,
+      );
+    }
+  }
+
+  fn declare(&mut self, name: &str, kind: Kind, span: Span, arity: Option<Arity>, detail: String) {
+    self.mark(span);
+    // Like the interpreter, only flag names that replace a global builtin;
+    // parameters, bindings and locals in nested scopes may shadow them.
+    let global = self.scopes.len() == 1;
+    if global && self.linter.builtins.contains(name) && !self.scope().bindings.contains_key(name) {
+      self.report(
+        Rule::ShadowedBuiltin,
+        format!("'{}' shadows a builtin", name),
+        span,
+      );
+    }
+
+    let definition = self.define(name, kind, span, detail);
+    let used_ahead = self.scope().used_ahead.remove(name);
+    if let (Some(definition), Some(spans)) = (definition, &used_ahead) {
+      for span in spans {
+        let reference = Reference {
+          span: *span,
+          definition,
+        };
+        self.analysis.references.push(reference);
+      }
+    }
+    let binding = Binding {
+      kind,
+      span,
+      used: used_ahead.is_some(),
+      arity,
+      definition,
+    };
+    if let Some(previous) = self.scope().bindings.insert(name.to_owned(), binding) {
+      self.check_unused(name, &previous);
+    }
+  }
+
+  fn define(&mut self, name: &str, kind: Kind, span: Span, detail: String) -> Option<usize> {
+    let kind = match kind {
+      Kind::Variable => DefinitionKind::Variable,
+      Kind::Constant => DefinitionKind::Constant,
+      Kind::Parameter => DefinitionKind::Parameter,
+      Kind::Loop => DefinitionKind::Loop,
+      Kind::Function => DefinitionKind::Function,
+      Kind::Pattern => return None,
+    };
+    if !span.is_known() {
+      return None;
+    }
+
+    self.analysis.definitions.push(Definition {
+      name: name.to_owned(),
+      kind,
+      span,
+      detail,
+    });
+    Some(self.analysis.definitions.len() - 1)
+  }
+
+  fn refer(&mut self, definition: Option<usize>, span: Span) {
+    if let (Some(definition), true) = (definition, span.is_known()) {
+      let reference = Reference { span, definition };
+      self.analysis.references.push(reference);
+    }
+  }
+
+  fn resolve(&mut self, name: &str, span: Span) -> Option<Arity> {
+    self.mark(span);
+    let mut crossed_function = false;
+    let mut later = None;
+    for scope in self.scopes.iter_mut().rev() {
+      if let Some(binding) = scope.bindings.get_mut(name) {
+        binding.used = true;
+        let (arity, definition) = (binding.arity, binding.definition);
+        self.refer(definition, span);
+        return arity;
+      }
+      if later.is_none() && scope.later.contains(name) {
+        later = Some((&mut scope.used_ahead, crossed_function));
+      }
+      crossed_function |= scope.function;
+    }
+
+    match later {
+      Some((used_ahead, true)) => used_ahead.entry(name.to_owned()).or_default().push(span),
+      Some((_, false)) => self.report(
+        Rule::UseBeforeDefinition,
+        format!("'{}' is used before it is defined", name),
+        span,
+      ),
+      None => (),
+    }
+    None
+  }
+
+  fn assign(&mut self, name: &str, span: Span) {
+    self.mark(span);
+    let mut crossed_function = false;
+    let mut later = None;
+    for scope in self.scopes.iter_mut().rev() {
+      if let Some(binding) = scope.bindings.get_mut(name) {
+        binding.arity = None;
+        let definition = binding.definition;
+        self.refer(definition, span);
+        return;
+      }
+      if later.is_none() && scope.later.contains(name) {
+        later = Some(crossed_function);
+      }
+      crossed_function |= scope.function;
+    }
+
+    match later {
+      Some(true) => (),
+      Some(false) => self.report(
+        Rule::UseBeforeDefinition,
+        format!("'{}' is assigned before it is defined", name),
+        span,
+      ),
+      None if self.linter.builtins.contains(name) => (),
+      None => self.report(
+        Rule::UndeclaredAssignment,
+        format!("assignment to undeclared variable '{}'", name),
+        span,
+      ),
+    }
+  }
+
+  fn block(&mut self, statements: &[Statement]) {
+    let later = statements
+      .iter()
+      .filter_map(declared_name)
+      .map(str::to_owned);
+    self.scope().later.extend(later);
+
+    let mut returned = false;
+    let mut reported = false;
+    for statement in statements {
+      if let Statement::Trivia(..) = statement {
+        continue;
+      }
+      if returned && !reported {
+        self.seen = None;
+        self.statement(statement);
+        let span = self.seen.unwrap_or(self.last);
+        self.report(Rule::UnreachableCode, "unreachable code".to_owned(), span);
+        reported = true;
+        continue;
+      }
+      self.statement(statement);
+      returned |= always_returns(statement);
+    }
+  }
+
+  fn statement(&mut self, statement: &Statement) {
+    match statement {
+      Statement::Block(statements) => {
+        self.push(false);
+        self.block(statements);
+        self.pop();
+      }
+      Statement::VariableDeclaration(name, value, span) => {
+        self.mark(*span);
+        self.expression(value);
+        let detail = format!("let {}{}", name, signature_of(value));
+        self.declare(name, Kind::Variable, *span, arity_of(value), detail);
+      }
+      Statement::ConstDeclaration(name, value, span) => {
+        self.mark(*span);
+        self.expression(value);
+        let detail = format!("const {}{}", name, signature_of(value));
+        self.declare(name, Kind::Constant, *span, arity_of(value), detail);
+      }
+      Statement::Return(value)
+      | Statement::Expression(value)
+      | Statement::LocalDeclaration(_, value, _)
+      | Statement::LocalConstDeclaration(_, value, _) => self.expression(value),
+      Statement::WhileLoop(condition, body, span) => {
+        if *condition != Expression::TRUE {
+          self.condition(condition, *span);
+        }
+        self.expression(condition);
+        self.statement(body);
+      }
+      Statement::ForLoop(variable, iterable, body, span) => {
+        self.mark(*span);
+        self.expression(iterable);
+        let detail = format!("for {}", variable);
+        self.declare(variable, Kind::Loop, *span, None, detail);
+        self.statement(body);
+      }
+      Statement::Trivia(..) | Statement::Error(..) | Statement::Null => (),
+    }
+  }
+
+  fn condition(&mut self, condition: &Expression, span: Span) {
+    self.mark(span);
+    if is_constant(condition) {
+      self.report(
+        Rule::ConstantCondition,
+        "this condition is constant".to_owned(),
+        span,
+      );
+    }
+  }
+
+  fn expression(&mut self, expression: &Expression) {
+    match expression {
+      Expression::Id(name, span) => {
+        self.resolve(name, *span);
+      }
+      Expression::Local(name, ..) => {
+        self.resolve(name, Span::default());
+      }
+      Expression::Infix(operator, target, value) if operator == "=" => {
+        self.expression(value);
+        match &**target {
+          Expression::Id(name, span) => self.assign(name, *span),
+          target => self.expression(target),
+        }
+      }
+      Expression::Infix(_, left, right) => {
+        self.expression(left);
+        self.expression(right);
+      }
+      Expression::Call(function, args, span) => {
+        self.mark(*span);
+        let arity = match &**function {
+          Expression::Id(name, id_span) => self.resolve(name, *id_span),
+          function => {
+            self.expression(function);
+            None
+          }
+        };
+        args.iter().for_each(|arg| self.expression(arg));
+
+        let spread = args.iter().any(|arg| matches!(arg, Expression::Spread(..)));
+        match (arity, &**function) {
+          (Some(arity), Expression::Id(name, _)) if !spread && !arity.accepts(args.len()) => self
+            .report(
+              Rule::WrongArity,
+              format!(
+                "'{}' takes {} but {} {} given",
+                name,
+                arity.describe(),
+                args.len(),
+                if args.len() == 1 { "was" } else { "were" }
+              ),
+              *span,
+            ),
+          _ => (),
+        }
+      }
+      Expression::Prefix(_, operand) | Expression::Spread(operand) => self.expression(operand),
+      Expression::Named(_, value) | Expression::Property(value, _) => self.expression(value),
+      Expression::Conditional(condition, consequence, alternative, span) => {
+        self.condition(condition, *span);
+        self.expression(condition);
+        self.statement(consequence);
+        if let Some(alternative) = alternative {
+          self.statement(alternative);
+        }
+      }
+      Expression::Function(name, parameters, body, span) => {
+        self.mark(*span);
+        if let Some(name) = name {
+          let detail = format!("fn {}({})", name, comma_separated(parameters));
+          let arity = Some(Arity::of(parameters));
+          self.declare(name, Kind::Function, *span, arity, detail);
+        }
+        self.push(true);
+        for parameter in parameters {
+          if let Some(default) = &parameter.default {
+            self.expression(default);
+          }
+          let detail = format!("parameter {}", parameter);
+          self.declare(
+            &parameter.name,
+            Kind::Parameter,
+            parameter.span,
+            None,
+            detail,
+          );
+        }
+        self.statement(body);
+        self.pop();
+      }
+      Expression::Array(items) => items.iter().for_each(|item| self.expression(item)),
+      Expression::Hash(entries) => {
+        for (key, value) in entries {
+          self.expression(key);
+          self.expression(value);
+        }
+      }
+      Expression::Index(indexed, index) => {
+        self.expression(indexed);
+        self.expression(index);
+      }
+      Expression::Match(value, arms) => {
+        self.expression(value);
+        arms.iter().for_each(|arm| self.arm(arm));
+      }
+      Expression::Integer(..)
+      | Expression::Float(..)
+      | Expression::String(..)
+      | Expression::Regex(..)
+      | Expression::Color(..)
+      | Expression::Boolean(..)
+      | Expression::Null => (),
+    }
+  }
+
+  fn arm(&mut self, arm: &MatchArm) {
+    self.push(false);
+    for name in arm.pattern.bindings() {
+      self.declare(name, Kind::Pattern, Span::default(), None, String::new());
+    }
+    if let Some(guard) = &arm.guard {
+      self.expression(guard);
+    }
+    self.statement(&arm.body);
+    self.pop();
+  }
+}
+
+fn declared_name(statement: &Statement) -> Option<&str> {
+  match statement {
+    Statement::VariableDeclaration(name, ..)
+    | Statement::ConstDeclaration(name, ..)
+    | Statement::ForLoop(name, ..)
+    | Statement::Expression(Expression::Function(Some(name), ..)) => Some(name),
+    _ => None,
+  }
+}
+
+fn arity_of(value: &Expression) -> Option<Arity> {
+  match value {
+    Expression::Function(_, parameters, ..) => Some(Arity::of(parameters)),
+    _ => None,
+  }
+}
+
+fn signature_of(value: &Expression) -> String {
+  match value {
+    Expression::Function(_, parameters, ..) => format!(" = fn({})", comma_separated(parameters)),
+    _ => String::new(),
+  }
+}
+
+fn always_returns(statement: &Statement) -> bool {
+  match statement {
+    Statement::Return(..) => true,
+    Statement::Block(statements) => statements.iter().any(always_returns),
+    Statement::Expression(Expression::Conditional(_, consequence, Some(alternative), _)) => {
+      always_returns(consequence) && always_returns(alternative)
+    }
+    _ => false,
+  }
+}
+
+fn is_constant(expression: &Expression) -> bool {
+  match expression {
+    Expression::Integer(..)
+    | Expression::Float(..)
+    | Expression::String(..)
+    | Expression::Regex(..)
+    | Expression::Color(..)
+    | Expression::Boolean(..)
+    | Expression::Null
+    | Expression::Function(..) => true,
+    Expression::Array(items) => items.iter().all(is_constant),
+    Expression::Prefix(_, operand) => is_constant(operand),
+    Expression::Infix(operator, left, right) => {
+      operator != "=" && is_constant(left) && is_constant(right)
+    }
+    _ => false,
+  }
+}
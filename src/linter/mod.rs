@@ -0,0 +1,9 @@
+mod analysis;
+mod diagnostic;
+mod linter;
+#[cfg(test)]
+mod test;
+
+pub use analysis::{Analysis, Definition, DefinitionKind, Reference};
+pub use diagnostic::{Diagnostic, Rule};
+pub use linter::{lint, Linter};
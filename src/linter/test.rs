@@ -0,0 +1,155 @@
+use super::{lint, Linter, Rule};
+
+fn assert_lints(input: &str, expected: &[(Rule, usize, usize)]) {
+  let diagnostics: Vec<(Rule, usize, usize)> = lint(input)
+    .unwrap()
+    .iter()
+    .map(|diagnostic| {
+      (
+        diagnostic.rule,
+        diagnostic.span.line,
+        diagnostic.span.column,
+      )
+    })
+    .collect();
+
+  assert_eq!(diagnostics, expected, "linting {:?}", input);
+}
+
+#[test]
+fn clean_programs() {
+  assert_lints(
+    "fn fact(n) {
+  if n < 2 { return 1 }
+  n * fact(n - 1)
+}
+fn main() { helper(fact(5)) }
+fn helper(x, y = 1, ...others) { x + y + len(others) }
+let total = 0
+for i in [1, 2] { total = total + i }
+while true { return total }
+match total { [first, ..._rest] => first, n if n > 1 => n, _ => 0 }
+print(main())",
+    &[],
+  );
+}
+
+#[test]
+fn unused_variables() {
+  assert_lints(
+    "let a = 1\nlet _b = 2\nfn f(x, y) { x }\nfor i in [] {}\nf(1, 2)",
+    &[
+      (Rule::UnusedVariable, 1, 5),
+      (Rule::UnusedVariable, 3, 9),
+      (Rule::UnusedVariable, 4, 5),
+    ],
+  );
+  assert_lints("let a = 1\nlet a = a + 1", &[(Rule::UnusedVariable, 2, 5)]);
+}
+
+#[test]
+fn use_before_definition() {
+  assert_lints(
+    "print(x)\nlet x = 1\nlet y = y\nprint(x, y)",
+    &[
+      (Rule::UseBeforeDefinition, 1, 7),
+      (Rule::UseBeforeDefinition, 3, 9),
+    ],
+  );
+  assert_lints("let x = 1\n{ print(x); let x = 2; x }", &[]);
+  assert_lints("fn f() { g() }\nfn g() { 1 }\nf()", &[]);
+  assert_lints(
+    "fn f() { g(); fn g() { 1 } }\nf()",
+    &[(Rule::UseBeforeDefinition, 1, 10)],
+  );
+}
+
+#[test]
+fn shadowed_builtins() {
+  assert_lints(
+    "let len = 1\nfn first(x) { x }\nfor map in [1] { print(map) }\nprint(len, first(1))",
+    &[
+      (Rule::ShadowedBuiltin, 1, 5),
+      (Rule::ShadowedBuiltin, 2, 4),
+      (Rule::ShadowedBuiltin, 3, 5),
+    ],
+  );
+  assert_lints(
+    "fn f(map, ...rest) { let len = 1; [map, rest, len] }\nf(1)\nmatch [1] { [first, ...tail] => first, _ => 0 }",
+    &[],
+  );
+}
+
+#[test]
+fn unreachable_code() {
+  assert_lints(
+    "fn f(x) {\n  return x\n  print(x)\n  x\n}\nf(1)",
+    &[(Rule::UnreachableCode, 3, 3)],
+  );
+  assert_lints(
+    "fn f(x) {\n  if x { return 1 } else { return 2 }\n  let y = 3\n}\nf(1)",
+    &[(Rule::UnreachableCode, 3, 7), (Rule::UnusedVariable, 3, 7)],
+  );
+}
+
+#[test]
+fn undeclared_assignments() {
+  assert_lints(
+    "x = 1\nlet y = 0\ny = 2\nfn f() { z = 3 }\nlet z = 0\nf()\nprint(y, z)",
+    &[(Rule::UndeclaredAssignment, 1, 1)],
+  );
+}
+
+#[test]
+fn constant_conditions() {
+  assert_lints(
+    "if 1 > 2 { 1 }\nwhile false {}\nlet x = 1\nif x > 0 { x }\nwhile !null { 1 }",
+    &[
+      (Rule::ConstantCondition, 1, 1),
+      (Rule::ConstantCondition, 2, 1),
+      (Rule::ConstantCondition, 5, 1),
+    ],
+  );
+}
+
+#[test]
+fn wrong_arity() {
+  assert_lints(
+    "fn f(a, b = 1) { a + b }
+let g = |...xs| xs
+f()
+f(1)
+f(1, 2, 3)
+f(1, b: 2)
+g()
+f(...[1, 2, 3])",
+    &[(Rule::WrongArity, 3, 1), (Rule::WrongArity, 5, 1)],
+  );
+
+  let diagnostics = lint("fn f(a) { a }\nf(1, 2)").unwrap();
+  assert_eq!(
+    diagnostics[0].to_string(),
+    "2:1: 'f' takes 1 argument but 2 were given [wrong-arity]"
+  );
+}
+
+#[test]
+fn disabled_rules() {
+  let linter = Linter::new()
+    .disable(Rule::UnusedVariable)
+    .disable(Rule::ConstantCondition);
+
+  let diagnostics = linter.lint("let a = 1\nif true { b = 1 }").unwrap();
+  let rules: Vec<Rule> = diagnostics
+    .iter()
+    .map(|diagnostic| diagnostic.rule)
+    .collect();
+  assert_eq!(rules, vec![Rule::UndeclaredAssignment]);
+  assert_eq!(Rule::from_id("wrong-arity"), Some(Rule::WrongArity));
+  assert_eq!(Rule::from_id("nope"), None);
+}
+
+#[test]
+fn reports_parse_errors() {
+  assert!(lint("let = 1").is_err());
+}
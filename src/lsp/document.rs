@@ -0,0 +1,127 @@
+use crate::{
+  ast::Span,
+  error::Error,
+  lexer::{lex, Token},
+  linter::{Analysis, Linter},
+  parser::Parser,
+  warning::Warning,
+};
+use lsp_types::{Diagnostic, DiagnosticSeverity, NumberOrString, Position, Range};
+
+pub struct Document {
+  pub text: String,
+  pub analysis: Analysis,
+  errors: Vec<Error>,
+  warnings: Vec<Warning>,
+  line_starts: Vec<usize>,
+}
+
+impl Document {
+  pub fn new(text: String, linter: &Linter) -> Self {
+    let line_starts = std::iter::once(0)
+      .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+      .collect();
+    let mut parser = Parser::new(lex(&text));
+    let program = parser.parse();
+
+    Self {
+      analysis: linter.analyze(&program),
+      errors: parser.errors,
+      warnings: parser.warnings,
+      line_starts,
+      text,
+    }
+  }
+
+  pub fn diagnostics(&self) -> Vec<Diagnostic> {
+    if !self.errors.is_empty() {
+      return self
+        .errors
+        .iter()
+        .map(|err| Diagnostic {
+          range: self.range(err.location().unwrap_or_default()),
+          severity: Some(DiagnosticSeverity::ERROR),
+          source: Some("helium".to_owned()),
+          message: err.message().to_owned(),
+          ..Diagnostic::default()
+        })
+        .collect();
+    }
+
+    let warnings = self.warnings.iter().filter_map(|warning| {
+      Some(Diagnostic {
+        range: self.range(warning.location()?),
+        severity: Some(DiagnosticSeverity::WARNING),
+        source: Some("helium".to_owned()),
+        message: warning.message().to_owned(),
+        ..Diagnostic::default()
+      })
+    });
+    let lints = self
+      .analysis
+      .diagnostics
+      .iter()
+      .map(|diagnostic| Diagnostic {
+        range: self.range(diagnostic.span),
+        severity: Some(DiagnosticSeverity::WARNING),
+        code: Some(NumberOrString::String(diagnostic.rule.id().to_owned())),
+        source: Some("helium".to_owned()),
+        message: diagnostic.message.clone(),
+        ..Diagnostic::default()
+      });
+    warnings.chain(lints).collect()
+  }
+
+  pub fn identifier_at(&self, offset: usize) -> Option<(&str, Span)> {
+    let mut lexer = lex(&self.text);
+    while let Some(token) = lexer.next() {
+      let span = lexer.span();
+      if span.start > offset {
+        break;
+      }
+      if let Token::Id(name) = token {
+        if offset <= span.end {
+          let span = Span {
+            start: span.start,
+            end: span.end,
+            ..Span::default()
+          };
+          return Some((name, span));
+        }
+      }
+    }
+    None
+  }
+
+  pub fn offset(&self, position: Position) -> usize {
+    let start = match self.line_starts.get(position.line as usize) {
+      Some(start) => *start,
+      None => return self.text.len(),
+    };
+
+    let mut units = 0;
+    for (i, c) in self.text[start..].char_indices() {
+      if units >= position.character as usize || c == '\n' {
+        return start + i;
+      }
+      units += c.len_utf16();
+    }
+    self.text.len()
+  }
+
+  pub fn position(&self, offset: usize) -> Position {
+    let offset = offset.min(self.text.len());
+    let line = match self.line_starts.binary_search(&offset) {
+      Ok(line) => line,
+      Err(line) => line - 1,
+    };
+    let character = self.text[self.line_starts[line]..offset]
+      .encode_utf16()
+      .count();
+    Position::new(line as u32, character as u32)
+  }
+
+  pub fn range(&self, span: Span) -> Range {
+    Range::new(self.position(span.start), self.position(span.end))
+  }
+}
@@ -0,0 +1,6 @@
+mod document;
+mod server;
+#[cfg(test)]
+mod test;
+
+pub use server::{run, Server};
@@ -0,0 +1,305 @@
+use super::document::Document;
+use crate::{
+  env, formatter,
+  linter::{DefinitionKind, Linter},
+  object::Object,
+};
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, Response};
+use lsp_types::{
+  notification::{
+    DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument,
+    Notification as LspNotification, PublishDiagnostics,
+  },
+  request::{
+    Completion, DocumentSymbolRequest, Formatting, GotoDefinition, HoverRequest,
+    Request as LspRequest,
+  },
+  CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams, CompletionResponse,
+  DocumentFormattingParams, DocumentSymbol, DocumentSymbolParams, DocumentSymbolResponse,
+  GotoDefinitionParams, GotoDefinitionResponse, Hover, HoverContents, HoverParams,
+  HoverProviderCapability, Location, MarkupContent, MarkupKind, OneOf, PublishDiagnosticsParams,
+  Range, ServerCapabilities, SymbolKind, TextDocumentPositionParams, TextDocumentSyncCapability,
+  TextDocumentSyncKind, TextEdit, Url,
+};
+use std::collections::HashMap;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error + Sync + Send>>;
+
+const KEYWORDS: [&str; 13] = [
+  "fn", "let", "const", "true", "false", "if", "else", "return", "for", "while", "in", "match",
+  "null",
+];
+
+pub fn run() -> Result<()> {
+  let (connection, io_threads) = Connection::stdio();
+  let capabilities = serde_json::to_value(Server::capabilities())?;
+  connection.initialize(capabilities)?;
+
+  let mut server = Server::new();
+  for message in &connection.receiver {
+    match message {
+      Message::Request(request) => {
+        if connection.handle_shutdown(&request)? {
+          break;
+        }
+        connection.sender.send(server.request(request).into())?;
+      }
+      Message::Notification(notification) => {
+        if let Some(notification) = server.notification(notification) {
+          connection.sender.send(notification.into())?;
+        }
+      }
+      Message::Response(..) => (),
+    }
+  }
+
+  drop(connection);
+  io_threads.join()?;
+  Ok(())
+}
+
+pub struct Server {
+  documents: HashMap<Url, Document>,
+  linter: Linter,
+  builtins: Vec<(String, Object)>,
+}
+
+impl Server {
+  pub fn new() -> Self {
+    let globals = env::global();
+    let builtins = globals
+      .names()
+      .into_iter()
+      .filter_map(|name| globals.get(&name).map(|value| (name, value)))
+      .collect();
+
+    Self {
+      documents: HashMap::new(),
+      linter: Linter::new(),
+      builtins,
+    }
+  }
+
+  pub fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+      text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+      hover_provider: Some(HoverProviderCapability::Simple(true)),
+      definition_provider: Some(OneOf::Left(true)),
+      document_symbol_provider: Some(OneOf::Left(true)),
+      completion_provider: Some(CompletionOptions::default()),
+      document_formatting_provider: Some(OneOf::Left(true)),
+      ..ServerCapabilities::default()
+    }
+  }
+
+  pub fn request(&self, request: Request) -> Response {
+    None
+      .or_else(|| self.handle::<HoverRequest>(&request, Self::hover))
+      .or_else(|| self.handle::<GotoDefinition>(&request, Self::definition))
+      .or_else(|| self.handle::<DocumentSymbolRequest>(&request, Self::symbols))
+      .or_else(|| self.handle::<Completion>(&request, Self::completion))
+      .or_else(|| self.handle::<Formatting>(&request, Self::formatting))
+      .unwrap_or_else(|| {
+        let message = format!("unsupported request '{}'", request.method);
+        Response::new_err(request.id, ErrorCode::MethodNotFound as i32, message)
+      })
+  }
+
+  fn handle<R: LspRequest>(
+    &self,
+    request: &Request,
+    handler: fn(&Self, R::Params) -> R::Result,
+  ) -> Option<Response> {
+    if request.method != R::METHOD {
+      return None;
+    }
+
+    let id = request.id.clone();
+    Some(match serde_json::from_value(request.params.clone()) {
+      Ok(params) => Response::new_ok(id, handler(self, params)),
+      Err(err) => Response::new_err(id, ErrorCode::InvalidParams as i32, err.to_string()),
+    })
+  }
+
+  pub fn notification(&mut self, notification: Notification) -> Option<Notification> {
+    let params = match notification.method.as_str() {
+      DidOpenTextDocument::METHOD => {
+        let params: <DidOpenTextDocument as LspNotification>::Params =
+          serde_json::from_value(notification.params).ok()?;
+        self.open(params.text_document.uri, params.text_document.text)
+      }
+      DidChangeTextDocument::METHOD => {
+        let params: <DidChangeTextDocument as LspNotification>::Params =
+          serde_json::from_value(notification.params).ok()?;
+        let text = params.content_changes.into_iter().last()?.text;
+        self.open(params.text_document.uri, text)
+      }
+      DidCloseTextDocument::METHOD => {
+        let params: <DidCloseTextDocument as LspNotification>::Params =
+          serde_json::from_value(notification.params).ok()?;
+        self.close(params.text_document.uri)
+      }
+      _ => return None,
+    };
+
+    Some(Notification::new(
+      PublishDiagnostics::METHOD.to_owned(),
+      params,
+    ))
+  }
+
+  pub fn open(&mut self, uri: Url, text: String) -> PublishDiagnosticsParams {
+    let document = Document::new(text, &self.linter);
+    let diagnostics = document.diagnostics();
+    self.documents.insert(uri.clone(), document);
+    PublishDiagnosticsParams::new(uri, diagnostics, None)
+  }
+
+  pub fn close(&mut self, uri: Url) -> PublishDiagnosticsParams {
+    self.documents.remove(&uri);
+    PublishDiagnosticsParams::new(uri, vec![], None)
+  }
+
+  pub fn hover(&self, params: HoverParams) -> Option<Hover> {
+    let (document, offset) = self.locate(&params.text_document_position_params)?;
+    let (name, span) = document.identifier_at(offset)?;
+    let detail = match document.analysis.definition_at(offset) {
+      Some(definition) => definition.detail.clone(),
+      None => match self.builtin(name)? {
+        Object::BuiltIn(..) => format!("builtin fn {}", name),
+        value => format!("builtin {}: {}", name, value.type_name()),
+      },
+    };
+
+    Some(Hover {
+      contents: HoverContents::Markup(MarkupContent {
+        kind: MarkupKind::Markdown,
+        value: format!("```helium\n{}\n```", detail),
+      }),
+      range: Some(document.range(span)),
+    })
+  }
+
+  pub fn definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+    let position = &params.text_document_position_params;
+    let (document, offset) = self.locate(position)?;
+    let definition = document.analysis.definition_at(offset)?;
+    Some(GotoDefinitionResponse::Scalar(Location::new(
+      position.text_document.uri.clone(),
+      document.range(definition.span),
+    )))
+  }
+
+  #[allow(deprecated)]
+  pub fn symbols(&self, params: DocumentSymbolParams) -> Option<DocumentSymbolResponse> {
+    let document = self.documents.get(&params.text_document.uri)?;
+    let symbols = document
+      .analysis
+      .definitions
+      .iter()
+      .filter_map(|definition| {
+        let kind = match definition.kind {
+          DefinitionKind::Function => SymbolKind::FUNCTION,
+          DefinitionKind::Variable => SymbolKind::VARIABLE,
+          DefinitionKind::Constant => SymbolKind::CONSTANT,
+          DefinitionKind::Parameter | DefinitionKind::Loop => return None,
+        };
+        let range = document.range(definition.span);
+        Some(DocumentSymbol {
+          name: definition.name.clone(),
+          detail: Some(definition.detail.clone()),
+          kind,
+          tags: None,
+          deprecated: None,
+          range,
+          selection_range: range,
+          children: None,
+        })
+      })
+      .collect();
+
+    Some(DocumentSymbolResponse::Nested(symbols))
+  }
+
+  pub fn completion(&self, params: CompletionParams) -> Option<CompletionResponse> {
+    let mut items: Vec<CompletionItem> = KEYWORDS
+      .iter()
+      .map(|keyword| CompletionItem {
+        label: keyword.to_string(),
+        kind: Some(CompletionItemKind::KEYWORD),
+        ..CompletionItem::default()
+      })
+      .collect();
+
+    items.extend(self.builtins.iter().map(|(name, value)| {
+      let (kind, detail) = match value {
+        Object::BuiltIn(..) => (CompletionItemKind::FUNCTION, format!("builtin fn {}", name)),
+        value => (
+          CompletionItemKind::MODULE,
+          format!("builtin {}", value.type_name()),
+        ),
+      };
+      CompletionItem {
+        label: name.clone(),
+        kind: Some(kind),
+        detail: Some(detail),
+        ..CompletionItem::default()
+      }
+    }));
+
+    if let Some(document) = self
+      .documents
+      .get(&params.text_document_position.text_document.uri)
+    {
+      for definition in &document.analysis.definitions {
+        if items.iter().any(|item| item.label == definition.name) {
+          continue;
+        }
+        let kind = match definition.kind {
+          DefinitionKind::Function => CompletionItemKind::FUNCTION,
+          DefinitionKind::Constant => CompletionItemKind::CONSTANT,
+          _ => CompletionItemKind::VARIABLE,
+        };
+        items.push(CompletionItem {
+          label: definition.name.clone(),
+          kind: Some(kind),
+          detail: Some(definition.detail.clone()),
+          ..CompletionItem::default()
+        });
+      }
+    }
+
+    Some(CompletionResponse::Array(items))
+  }
+
+  pub fn formatting(&self, params: DocumentFormattingParams) -> Option<Vec<TextEdit>> {
+    let document = self.documents.get(&params.text_document.uri)?;
+    let formatted = formatter::format(&document.text).ok()?;
+    if formatted == document.text {
+      return Some(vec![]);
+    }
+
+    let end = document.position(document.text.len());
+    let range = Range::new(Default::default(), end);
+    Some(vec![TextEdit::new(range, formatted)])
+  }
+
+  fn locate(&self, position: &TextDocumentPositionParams) -> Option<(&Document, usize)> {
+    let document = self.documents.get(&position.text_document.uri)?;
+    Some((document, document.offset(position.position)))
+  }
+
+  fn builtin(&self, name: &str) -> Option<&Object> {
+    self
+      .builtins
+      .iter()
+      .find(|(builtin, _)| builtin == name)
+      .map(|(_, value)| value)
+  }
+}
+
+impl Default for Server {
+  fn default() -> Self {
+    Self::new()
+  }
+}
@@ -0,0 +1,215 @@
+use super::Server;
+use lsp_server::{Notification, Request, RequestId};
+use lsp_types::{
+  CompletionParams, CompletionResponse, DiagnosticSeverity, DocumentFormattingParams,
+  DocumentSymbolParams, DocumentSymbolResponse, GotoDefinitionParams, GotoDefinitionResponse,
+  HoverContents, HoverParams, NumberOrString, Position, Range, TextDocumentIdentifier,
+  TextDocumentPositionParams, Url,
+};
+use serde_json::json;
+
+const SOURCE: &str = "fn add(a, b = 1) {
+  a + b
+}
+let total = add(2)
+print(total, len([]))
+";
+
+fn open(source: &str) -> (Server, Url) {
+  let mut server = Server::new();
+  let uri = Url::parse("file:///test.he").unwrap();
+  server.open(uri.clone(), source.to_owned());
+  (server, uri)
+}
+
+fn at(uri: &Url, line: u32, character: u32) -> TextDocumentPositionParams {
+  TextDocumentPositionParams::new(
+    TextDocumentIdentifier::new(uri.clone()),
+    Position::new(line, character),
+  )
+}
+
+fn hover(server: &Server, uri: &Url, line: u32, character: u32) -> Option<String> {
+  let params = HoverParams {
+    text_document_position_params: at(uri, line, character),
+    work_done_progress_params: Default::default(),
+  };
+  match server.hover(params)?.contents {
+    HoverContents::Markup(markup) => Some(markup.value),
+    contents => panic!("expected markup, got {:?}", contents),
+  }
+}
+
+#[test]
+fn publishes_diagnostics() {
+  let mut server = Server::new();
+  let uri = Url::parse("file:///test.he").unwrap();
+
+  let params = server.open(uri.clone(), "let x = 1\ny = 2".to_owned());
+  let codes: Vec<_> = params
+    .diagnostics
+    .iter()
+    .map(|diagnostic| (diagnostic.code.clone(), diagnostic.range.start))
+    .collect();
+  assert_eq!(
+    codes,
+    vec![
+      (
+        Some(NumberOrString::String("unused-variable".to_owned())),
+        Position::new(0, 4)
+      ),
+      (
+        Some(NumberOrString::String("undeclared-assignment".to_owned())),
+        Position::new(1, 0)
+      ),
+    ]
+  );
+
+  let params = server.open(uri.clone(), "let x = 1\nprint(x)\n(x)".to_owned());
+  assert_eq!(params.diagnostics.len(), 1);
+  assert_eq!(params.diagnostics[0].range.start, Position::new(2, 0));
+
+  let params = server.open(uri.clone(), "let = 1".to_owned());
+  assert_eq!(params.diagnostics.len(), 1);
+  assert_eq!(
+    params.diagnostics[0].severity,
+    Some(DiagnosticSeverity::ERROR)
+  );
+  assert_eq!(params.diagnostics[0].range.start, Position::new(0, 4));
+
+  assert!(server.close(uri).diagnostics.is_empty());
+}
+
+#[test]
+fn hovers_show_signatures() {
+  let (server, uri) = open(SOURCE);
+
+  assert_eq!(
+    hover(&server, &uri, 3, 13),
+    Some("```helium\nfn add(a, b?)\n```".to_owned())
+  );
+  assert_eq!(
+    hover(&server, &uri, 4, 8),
+    Some("```helium\nlet total\n```".to_owned())
+  );
+  assert_eq!(
+    hover(&server, &uri, 1, 2),
+    Some("```helium\nparameter a\n```".to_owned())
+  );
+  assert_eq!(
+    hover(&server, &uri, 4, 14),
+    Some("```helium\nbuiltin fn len\n```".to_owned())
+  );
+  assert_eq!(hover(&server, &uri, 3, 0), None);
+}
+
+#[test]
+fn goes_to_definitions() {
+  let (server, uri) = open(SOURCE);
+  let definition = |line, character| {
+    let params = GotoDefinitionParams {
+      text_document_position_params: at(&uri, line, character),
+      work_done_progress_params: Default::default(),
+      partial_result_params: Default::default(),
+    };
+    match server.definition(params) {
+      Some(GotoDefinitionResponse::Scalar(location)) => Some(location.range),
+      None => None,
+      response => panic!("expected a single location, got {:?}", response),
+    }
+  };
+
+  let range = |line, start, end| Range::new(Position::new(line, start), Position::new(line, end));
+  assert_eq!(definition(3, 12), Some(range(0, 3, 6)));
+  assert_eq!(definition(4, 6), Some(range(3, 4, 9)));
+  assert_eq!(definition(1, 6), Some(range(0, 10, 11)));
+  assert_eq!(definition(4, 14), None);
+}
+
+#[test]
+fn lists_document_symbols() {
+  let (server, uri) = open(SOURCE);
+  let params = DocumentSymbolParams {
+    text_document: TextDocumentIdentifier::new(uri),
+    work_done_progress_params: Default::default(),
+    partial_result_params: Default::default(),
+  };
+
+  let names: Vec<String> = match server.symbols(params) {
+    Some(DocumentSymbolResponse::Nested(symbols)) => {
+      symbols.into_iter().map(|symbol| symbol.name).collect()
+    }
+    response => panic!("expected nested symbols, got {:?}", response),
+  };
+  assert_eq!(names, vec!["add", "total"]);
+}
+
+#[test]
+fn completes_builtins_keywords_and_definitions() {
+  let (server, uri) = open(SOURCE);
+  let params = CompletionParams {
+    text_document_position: at(&uri, 5, 0),
+    work_done_progress_params: Default::default(),
+    partial_result_params: Default::default(),
+    context: None,
+  };
+
+  let labels: Vec<String> = match server.completion(params) {
+    Some(CompletionResponse::Array(items)) => items.into_iter().map(|item| item.label).collect(),
+    response => panic!("expected completion items, got {:?}", response),
+  };
+  for label in ["map", "len", "math", "let", "match", "add", "total"] {
+    assert!(labels.contains(&label.to_owned()), "missing {}", label);
+  }
+}
+
+#[test]
+fn formats_documents() {
+  let (server, uri) = open("let  x=1\nprint( x )\n");
+  let params = DocumentFormattingParams {
+    text_document: TextDocumentIdentifier::new(uri),
+    options: Default::default(),
+    work_done_progress_params: Default::default(),
+  };
+
+  let edits = server.formatting(params).unwrap();
+  assert_eq!(edits.len(), 1);
+  assert_eq!(edits[0].new_text, "let x = 1\nprint(x)\n");
+  assert_eq!(edits[0].range.end, Position::new(2, 0));
+}
+
+#[test]
+fn dispatches_messages() {
+  let mut server = Server::new();
+  let notification = Notification::new(
+    "textDocument/didOpen".to_owned(),
+    json!({
+      "textDocument": {
+        "uri": "file:///test.he",
+        "languageId": "helium",
+        "version": 1,
+        "text": "let x = 1",
+      }
+    }),
+  );
+  let published = server.notification(notification).unwrap();
+  assert_eq!(published.method, "textDocument/publishDiagnostics");
+
+  let request = Request::new(
+    RequestId::from(1),
+    "textDocument/hover".to_owned(),
+    json!({
+      "textDocument": { "uri": "file:///test.he" },
+      "position": { "line": 0, "character": 4 },
+    }),
+  );
+  let response = server.request(request);
+  assert!(response.error.is_none());
+  assert_eq!(
+    response.result.unwrap()["contents"]["value"],
+    "```helium\nlet x\n```"
+  );
+
+  let request = Request::new(RequestId::from(2), "unknown".to_owned(), json!({}));
+  assert!(server.request(request).error.is_some());
+}
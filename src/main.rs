@@ -1,5 +1,180 @@
+#[cfg(feature = "repl")]
 use helium::repl::repl;
+use helium::{
+  formatter,
+  linter::{Linter, Rule},
+};
+use std::{
+  env, fs,
+  io::{self, Read},
+  path::PathBuf,
+  process::exit,
+};
 
 fn main() {
-  repl();
+  let args: Vec<String> = env::args().skip(1).collect();
+  match args.first().map(String::as_str) {
+    #[cfg(feature = "repl")]
+    None => repl(),
+    #[cfg(not(feature = "repl"))]
+    None => {
+      eprintln!("this build of helium has no REPL, rebuild it with the 'repl' feature");
+      exit(2)
+    }
+    Some("fmt") => exit(fmt(&args[1..])),
+    Some("lint") => exit(lint(&args[1..])),
+    Some(command) => {
+      eprintln!("unknown command '{}'", command);
+      eprintln!("usage: helium [fmt [--check] [paths...] | lint [--disable rules] [paths...]]");
+      exit(2)
+    }
+  }
+}
+
+fn fmt(args: &[String]) -> i32 {
+  let check = args.iter().any(|arg| arg == "--check");
+  let paths: Vec<&String> = args.iter().filter(|arg| *arg != "--check").collect();
+
+  if paths.is_empty() {
+    let mut source = String::new();
+    if let Err(err) = io::stdin().read_to_string(&mut source) {
+      eprintln!("{}", err);
+      return 1;
+    }
+    return match formatter::format(&source) {
+      Ok(formatted) if check => (formatted != source) as i32,
+      Ok(formatted) => {
+        print!("{}", formatted);
+        0
+      }
+      Err(errors) => {
+        errors.iter().for_each(|err| eprintln!("{}", err));
+        1
+      }
+    };
+  }
+
+  let mut status = 0;
+  for path in source_files(&paths) {
+    let source = match fs::read_to_string(&path) {
+      Ok(source) => source,
+      Err(err) => {
+        eprintln!("{}: {}", path.display(), err);
+        status = 1;
+        continue;
+      }
+    };
+
+    match formatter::format(&source) {
+      Ok(formatted) if formatted == source => (),
+      Ok(_) if check => {
+        println!("{}", path.display());
+        status = 1;
+      }
+      Ok(formatted) => {
+        if let Err(err) = fs::write(&path, formatted) {
+          eprintln!("{}: {}", path.display(), err);
+          status = 1;
+        }
+      }
+      Err(errors) => {
+        for err in errors {
+          eprintln!("{}: {}", path.display(), err);
+        }
+        status = 1;
+      }
+    }
+  }
+  status
+}
+
+fn lint(args: &[String]) -> i32 {
+  let mut linter = Linter::new();
+  let mut paths = vec![];
+  let mut args = args.iter();
+  while let Some(arg) = args.next() {
+    if arg != "--disable" {
+      paths.push(arg);
+      continue;
+    }
+    for id in args.next().map(String::as_str).unwrap_or("").split(',') {
+      match Rule::from_id(id) {
+        Some(rule) => linter = linter.disable(rule),
+        None => {
+          let ids: Vec<&str> = Rule::ALL.iter().map(Rule::id).collect();
+          eprintln!("unknown rule '{}', expected one of: {}", id, ids.join(", "));
+          return 2;
+        }
+      }
+    }
+  }
+
+  let mut status = 0;
+  let sources = if paths.is_empty() {
+    let mut source = String::new();
+    if let Err(err) = io::stdin().read_to_string(&mut source) {
+      eprintln!("{}", err);
+      return 1;
+    }
+    vec![("<stdin>".to_owned(), source)]
+  } else {
+    let mut sources = vec![];
+    for path in source_files(&paths) {
+      match fs::read_to_string(&path) {
+        Ok(source) => sources.push((path.display().to_string(), source)),
+        Err(err) => {
+          eprintln!("{}: {}", path.display(), err);
+          status = 1;
+        }
+      }
+    }
+    sources
+  };
+
+  for (name, source) in sources {
+    match linter.lint(&source) {
+      Ok(diagnostics) => {
+        for diagnostic in &diagnostics {
+          println!("{}:{}", name, diagnostic);
+        }
+        status |= !diagnostics.is_empty() as i32;
+      }
+      Err(errors) => {
+        for err in errors {
+          eprintln!("{}: {}", name, err);
+        }
+        status = 1;
+      }
+    }
+  }
+  status
+}
+
+fn source_files(paths: &[&String]) -> Vec<PathBuf> {
+  let mut files = vec![];
+  for path in paths {
+    collect_source_files(PathBuf::from(path), &mut files);
+  }
+  files
+}
+
+fn collect_source_files(path: PathBuf, files: &mut Vec<PathBuf>) {
+  if !path.is_dir() {
+    files.push(path);
+    return;
+  }
+
+  let mut entries: Vec<PathBuf> = match fs::read_dir(&path) {
+    Ok(entries) => entries.flatten().map(|entry| entry.path()).collect(),
+    Err(err) => {
+      eprintln!("{}: {}", path.display(), err);
+      return;
+    }
+  };
+  entries.sort();
+  for entry in entries {
+    if entry.is_dir() || entry.extension().is_some_and(|extension| extension == "he") {
+      collect_source_files(entry, files);
+    }
+  }
 }
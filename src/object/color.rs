@@ -0,0 +1,140 @@
+use super::Object;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Color {
+  pub red: u8,
+  pub green: u8,
+  pub blue: u8,
+  pub alpha: u8,
+}
+
+impl Color {
+  pub fn new(red: u8, green: u8, blue: u8, alpha: u8) -> Self {
+    Color {
+      red,
+      green,
+      blue,
+      alpha,
+    }
+  }
+
+  pub fn from_hex(hex: &str) -> Option<Self> {
+    let hex = hex.strip_prefix('#').unwrap_or(hex);
+    let digits: Vec<u8> = hex
+      .chars()
+      .map(|c| c.to_digit(16).map(|digit| digit as u8))
+      .collect::<Option<_>>()?;
+
+    let channels: Vec<u8> = match digits.len() {
+      3 | 4 => digits.iter().map(|digit| digit * 17).collect(),
+      6 | 8 => digits
+        .chunks(2)
+        .map(|pair| pair[0] * 16 + pair[1])
+        .collect(),
+      _ => return None,
+    };
+    let alpha = channels.get(3).copied().unwrap_or(255);
+    Some(Color::new(channels[0], channels[1], channels[2], alpha))
+  }
+
+  pub fn from_hsl(hue: f64, saturation: f64, lightness: f64, alpha: u8) -> Self {
+    let hue = hue.rem_euclid(360.0) / 60.0;
+    let saturation = saturation.clamp(0.0, 1.0);
+    let lightness = lightness.clamp(0.0, 1.0);
+
+    let chroma = (1.0 - (2.0 * lightness - 1.0).abs()) * saturation;
+    let x = chroma * (1.0 - (hue % 2.0 - 1.0).abs());
+    let (red, green, blue) = match hue as u8 {
+      0 => (chroma, x, 0.0),
+      1 => (x, chroma, 0.0),
+      2 => (0.0, chroma, x),
+      3 => (0.0, x, chroma),
+      4 => (x, 0.0, chroma),
+      _ => (chroma, 0.0, x),
+    };
+    let m = lightness - chroma / 2.0;
+    Color::new(
+      channel(red + m),
+      channel(green + m),
+      channel(blue + m),
+      alpha,
+    )
+  }
+
+  pub fn to_hsl(&self) -> (f64, f64, f64) {
+    let red = self.red as f64 / 255.0;
+    let green = self.green as f64 / 255.0;
+    let blue = self.blue as f64 / 255.0;
+    let max = red.max(green).max(blue);
+    let min = red.min(green).min(blue);
+    let lightness = (max + min) / 2.0;
+    let delta = max - min;
+
+    if delta == 0.0 {
+      return (0.0, 0.0, lightness);
+    }
+
+    let saturation = delta / (1.0 - (2.0 * lightness - 1.0).abs());
+    let hue = if max == red {
+      60.0 * ((green - blue) / delta).rem_euclid(6.0)
+    } else if max == green {
+      60.0 * ((blue - red) / delta + 2.0)
+    } else {
+      60.0 * ((red - green) / delta + 4.0)
+    };
+    (hue, saturation, lightness)
+  }
+
+  pub fn mix(&self, other: &Color, weight: f64) -> Self {
+    let weight = weight.clamp(0.0, 1.0);
+    let blend =
+      |left: u8, right: u8| (left as f64 * (1.0 - weight) + right as f64 * weight).round() as u8;
+    Color::new(
+      blend(self.red, other.red),
+      blend(self.green, other.green),
+      blend(self.blue, other.blue),
+      blend(self.alpha, other.alpha),
+    )
+  }
+
+  pub fn lighten(&self, amount: f64) -> Self {
+    let (hue, saturation, lightness) = self.to_hsl();
+    Color::from_hsl(hue, saturation, lightness + amount, self.alpha)
+  }
+
+  pub fn with_alpha(self, alpha: f64) -> Self {
+    Color {
+      alpha: channel(alpha),
+      ..self
+    }
+  }
+
+  pub fn get(&self, property: &str) -> Option<Object> {
+    let (hue, saturation, lightness) = self.to_hsl();
+    Some(match property {
+      "red" => Object::Integer(self.red as i64),
+      "green" => Object::Integer(self.green as i64),
+      "blue" => Object::Integer(self.blue as i64),
+      "alpha" => Object::Float(self.alpha as f64 / 255.0),
+      "hue" => Object::Float(hue),
+      "saturation" => Object::Float(saturation),
+      "lightness" => Object::Float(lightness),
+      _ => return None,
+    })
+  }
+}
+
+impl fmt::Display for Color {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "#{:02x}{:02x}{:02x}", self.red, self.green, self.blue)?;
+    if self.alpha != 255 {
+      write!(f, "{:02x}", self.alpha)?;
+    }
+    Ok(())
+  }
+}
+
+fn channel(value: f64) -> u8 {
+  (value.clamp(0.0, 1.0) * 255.0).round() as u8
+}
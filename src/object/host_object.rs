@@ -0,0 +1,58 @@
+use super::Object;
+use crate::{error::Error, visitor::Context};
+use std::{any::Any, fmt, ptr};
+
+pub trait HostObject: Any {
+  fn type_name(&self) -> &str;
+
+  fn get(&self, _property: &str) -> Option<Object> {
+    None
+  }
+
+  fn set(&self, property: &str, _value: Object) -> Result<(), Error> {
+    Err(Error::unknown_property(self.type_name(), property))
+  }
+
+  fn has_method(&self, _method: &str) -> bool {
+    false
+  }
+
+  fn call_method(
+    &self,
+    _context: &Context,
+    method: &str,
+    _args: Vec<Object>,
+  ) -> Result<Object, Error> {
+    Err(Error::unknown_property(self.type_name(), method))
+  }
+
+  fn iter(&self) -> Option<Box<dyn Iterator<Item = Object>>> {
+    None
+  }
+
+  fn equals(&self, _other: &dyn HostObject) -> bool {
+    false
+  }
+
+  fn display(&self) -> String {
+    format!("<{}>", self.type_name())
+  }
+}
+
+impl dyn HostObject {
+  pub fn downcast_ref<T: HostObject>(&self) -> Option<&T> {
+    (self as &dyn Any).downcast_ref()
+  }
+}
+
+impl fmt::Debug for dyn HostObject {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.display())
+  }
+}
+
+impl PartialEq for dyn HostObject {
+  fn eq(&self, other: &dyn HostObject) -> bool {
+    ptr::addr_eq(self, other) || self.equals(other)
+  }
+}
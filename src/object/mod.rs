@@ -1,5 +1,17 @@
+mod color;
+mod host_object;
 mod object;
+mod regexp;
+#[cfg(feature = "serde")]
+mod serialize;
 #[cfg(test)]
 mod test;
 
+pub use color::Color;
+pub use host_object::HostObject;
+pub use object::BuiltIn;
+pub use object::NativeFn;
 pub use object::Object;
+pub use regexp::Regex;
+#[cfg(feature = "serde")]
+pub use serialize::{from_object, to_object};
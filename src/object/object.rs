@@ -1,16 +1,57 @@
-use crate::{ast::Statement, env::Env, error::Error, helpers::comma_separated};
+use super::{Color, HostObject, Regex};
+use crate::{
+  ast::{Parameter, Statement},
+  env::Env,
+  error::Error,
+  helpers::comma_separated,
+  visitor::Context,
+};
 use std::{collections::HashMap, fmt, ops, rc::Rc};
 
+pub type NativeFn = dyn Fn(&Context, Vec<Object>) -> Result<Object, Error>;
+
+#[derive(Clone)]
+pub struct BuiltIn(Rc<NativeFn>);
+
+impl BuiltIn {
+  pub fn new<F>(function: F) -> Self
+  where
+    F: Fn(&Context, Vec<Object>) -> Result<Object, Error> + 'static,
+  {
+    BuiltIn(Rc::new(function))
+  }
+
+  pub fn call(&self, context: &Context, args: Vec<Object>) -> Result<Object, Error> {
+    (self.0)(context, args)
+  }
+}
+
+impl fmt::Debug for BuiltIn {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "BuiltIn")
+  }
+}
+
+impl PartialEq for BuiltIn {
+  fn eq(&self, other: &BuiltIn) -> bool {
+    Rc::ptr_eq(&self.0, &other.0)
+  }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum Object {
   Array(Vec<Object>),
   Integer(i64),
+  Float(f64),
   String(String),
   Boolean(bool),
   Return(Box<Object>),
-  Function(Vec<String>, Rc<Statement>, Env),
-  BuiltIn(fn(Vec<Object>) -> Result<Object, Error>),
+  Function(Option<String>, Vec<Parameter>, Rc<Statement>, Env),
+  BuiltIn(BuiltIn),
   Hash(HashMap<String, Object>),
+  Native(Rc<dyn HostObject>),
+  Regex(Regex),
+  Color(Color),
   Null,
 }
 
@@ -22,12 +63,16 @@ impl fmt::Display for Object {
       match self {
         Self::Hash(hash) => format!("{:?}", hash),
         Self::Integer(value) => value.to_string(),
+        Self::Float(value) => format!("{:?}", value),
         Self::Boolean(value) => value.to_string(),
         Self::String(value) => format!("'{}'", value),
         Self::Return(obj) => obj.to_string(),
-        Self::Function(args, ..) => format!("fn({})", comma_separated(args)),
-        Self::BuiltIn(..) => format!("builtin fn()"),
+        Self::Function(_, args, ..) => format!("fn({})", comma_separated(args)),
+        Self::BuiltIn(..) => "builtin fn()".to_string(),
         Self::Array(array) => format!("[{}]", comma_separated(array)),
+        Self::Native(native) => native.display(),
+        Self::Regex(regex) => regex.to_string(),
+        Self::Color(color) => color.to_string(),
         Self::Null => "null".to_string(),
       }
     )
@@ -35,46 +80,59 @@ impl fmt::Display for Object {
 }
 
 impl ops::Add for Object {
-  type Output = Object;
+  type Output = Result<Object, Error>;
 
   fn add(self, obj: Object) -> Self::Output {
     match (self, obj) {
-      (Object::Integer(left), Object::Integer(right)) => Object::Integer(left + right),
-      (Object::String(left), Object::String(right)) => Object::String(format!("{}{}", left, right)),
-      (left, right) => Error::type_mismatch("+", left, right).raise(),
+      (Object::Integer(left), Object::Integer(right)) => integer("+", left.checked_add(right)),
+      (Object::String(left), Object::String(right)) => {
+        Ok(Object::String(format!("{}{}", left, right)))
+      }
+      (left, right) => match floats(&left, &right) {
+        Some((left, right)) => Ok(Object::Float(left + right)),
+        None => Err(Error::type_mismatch("+", left, right)),
+      },
     }
   }
 }
 
 impl ops::Sub for Object {
-  type Output = Object;
+  type Output = Result<Object, Error>;
 
   fn sub(self, obj: Object) -> Self::Output {
     match (self, obj) {
-      (Object::Integer(left), Object::Integer(right)) => Object::Integer(left - right),
-      (left, right) => Error::type_mismatch("-", left, right).raise(),
+      (Object::Integer(left), Object::Integer(right)) => integer("-", left.checked_sub(right)),
+      (left, right) => match floats(&left, &right) {
+        Some((left, right)) => Ok(Object::Float(left - right)),
+        None => Err(Error::type_mismatch("-", left, right)),
+      },
     }
   }
 }
 
 impl ops::Div for Object {
-  type Output = Object;
+  type Output = Result<Object, Error>;
 
   fn div(self, obj: Object) -> Self::Output {
     match (self, obj) {
-      (Object::Integer(left), Object::Integer(right)) => Object::Integer(left / right),
-      (left, right) => Error::type_mismatch("/", left, right).raise(),
+      (Object::Integer(_), Object::Integer(0)) => Err(Error::division_by_zero()),
+      (Object::Integer(left), Object::Integer(right)) => integer("/", left.checked_div(right)),
+      (left, right) => match floats(&left, &right) {
+        Some((left, right)) => Ok(Object::Float(left / right)),
+        None => Err(Error::type_mismatch("/", left, right)),
+      },
     }
   }
 }
 
 impl ops::Neg for Object {
-  type Output = Object;
+  type Output = Result<Object, Error>;
 
   fn neg(self) -> Self::Output {
     match self {
-      Object::Integer(number) => Object::Integer(-number),
-      _ => Error::unknown_operator("-", self).raise(),
+      Object::Integer(number) => integer("-", number.checked_neg()),
+      Object::Float(number) => Ok(Object::Float(-number)),
+      _ => Err(Error::unknown_operator("-", self)),
     }
   }
 }
@@ -88,23 +146,31 @@ impl ops::Not for Object {
 }
 
 impl ops::Mul for Object {
-  type Output = Object;
+  type Output = Result<Object, Error>;
 
   fn mul(self, obj: Object) -> Self::Output {
     match (self, obj) {
-      (Object::Integer(left), Object::Integer(right)) => Object::Integer(left * right),
-      (left, right) => Error::type_mismatch("/", left, right).raise(),
+      (Object::Integer(left), Object::Integer(right)) => integer("*", left.checked_mul(right)),
+      (left, right) => match floats(&left, &right) {
+        Some((left, right)) => Ok(Object::Float(left * right)),
+        None => Err(Error::type_mismatch("*", left, right)),
+      },
     }
   }
 }
 
-impl PartialOrd for Object {
-  fn partial_cmp(&self, obj: &Object) -> Option<std::cmp::Ordering> {
-    match (self, obj) {
-      (Object::Integer(left), Object::Integer(right)) => Some(compare(left, right)),
-      (Object::String(left), Object::String(right)) => Some(compare(left, right)),
-      (left, right) => Error::type_mismatch("< or >", left.clone(), right.clone()).raise(),
-    }
+fn integer(operator: &str, result: Option<i64>) -> Result<Object, Error> {
+  result
+    .map(Object::Integer)
+    .ok_or_else(|| Error::integer_overflow(operator))
+}
+
+fn floats(left: &Object, right: &Object) -> Option<(f64, f64)> {
+  match (left, right) {
+    (Object::Float(left), Object::Float(right)) => Some((*left, *right)),
+    (Object::Integer(left), Object::Float(right)) => Some((*left as f64, *right)),
+    (Object::Float(left), Object::Integer(right)) => Some((*left, *right as f64)),
+    _ => None,
   }
 }
 
@@ -131,10 +197,66 @@ impl Object {
     }
   }
 
+  pub fn builtin<F>(function: F) -> Self
+  where
+    F: Fn(&Context, Vec<Object>) -> Result<Object, Error> + 'static,
+  {
+    Self::BuiltIn(BuiltIn::new(function))
+  }
+
+  pub fn native<T: HostObject>(value: T) -> Self {
+    Self::Native(Rc::new(value))
+  }
+
   pub fn r#return(obj: Self) -> Self {
     Self::Return(Box::new(obj))
   }
 
+  pub fn hash_key(&self) -> Option<String> {
+    match self {
+      Self::Boolean(b) => Some(b.to_string()),
+      Self::Integer(i) => Some(i.to_string()),
+      Self::String(s) => Some(s.clone()),
+      _ => None,
+    }
+  }
+
+  pub fn to_text(&self) -> String {
+    match self {
+      Self::String(string) => string.clone(),
+      obj => obj.to_string(),
+    }
+  }
+
+  pub fn type_name(&self) -> &str {
+    match self {
+      Self::Array(..) => "array",
+      Self::Integer(..) => "integer",
+      Self::Float(..) => "float",
+      Self::String(..) => "string",
+      Self::Boolean(..) => "boolean",
+      Self::Return(value) => value.type_name(),
+      Self::Function(..) => "function",
+      Self::BuiltIn(..) => "builtin",
+      Self::Hash(..) => "hash",
+      Self::Native(native) => native.type_name(),
+      Self::Regex(..) => "regex",
+      Self::Color(..) => "color",
+      Self::Null => "null",
+    }
+  }
+
+  pub fn compare_to(&self, obj: &Object) -> Result<Option<std::cmp::Ordering>, Error> {
+    match (self, obj) {
+      (Object::Integer(left), Object::Integer(right)) => Ok(Some(compare(left, right))),
+      (Object::String(left), Object::String(right)) => Ok(Some(compare(left, right))),
+      (left, right) => match floats(left, right) {
+        Some((left, right)) => Ok(left.partial_cmp(&right)),
+        None => Err(Error::type_mismatch("< or >", left.clone(), right.clone())),
+      },
+    }
+  }
+
   pub fn is_truthy(&self) -> bool {
     match self {
       Self::Boolean(b) => *b,
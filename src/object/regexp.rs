@@ -0,0 +1,68 @@
+use crate::error::Error;
+use regex::RegexBuilder;
+use std::{fmt, ops::Deref};
+
+#[derive(Clone)]
+pub struct Regex {
+  source: String,
+  flags: String,
+  regex: regex::Regex,
+}
+
+impl Regex {
+  pub fn new(source: &str, flags: &str) -> Result<Self, Error> {
+    let mut builder = RegexBuilder::new(source);
+    for flag in flags.chars() {
+      match flag {
+        'i' => builder.case_insensitive(true),
+        'm' => builder.multi_line(true),
+        's' => builder.dot_matches_new_line(true),
+        'x' => builder.ignore_whitespace(true),
+        flag => return Err(Error::invalid_regex(&format!("unknown flag '{}'", flag))),
+      };
+    }
+
+    match builder.build() {
+      Ok(regex) => Ok(Regex {
+        source: source.to_owned(),
+        flags: flags.to_owned(),
+        regex,
+      }),
+      Err(err) => Err(Error::invalid_regex(&err.to_string())),
+    }
+  }
+
+  pub fn source(&self) -> &str {
+    &self.source
+  }
+
+  pub fn flags(&self) -> &str {
+    &self.flags
+  }
+}
+
+impl Deref for Regex {
+  type Target = regex::Regex;
+
+  fn deref(&self) -> &regex::Regex {
+    &self.regex
+  }
+}
+
+impl PartialEq for Regex {
+  fn eq(&self, other: &Regex) -> bool {
+    self.source == other.source && self.flags == other.flags
+  }
+}
+
+impl fmt::Debug for Regex {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self)
+  }
+}
+
+impl fmt::Display for Regex {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "r\"{}\"{}", self.source.replace('"', "\\\""), self.flags)
+  }
+}
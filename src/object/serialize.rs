@@ -0,0 +1,514 @@
+use super::Object;
+use crate::error::Error;
+use serde::{
+  de::{
+    self,
+    value::{MapAccessDeserializer, MapDeserializer, SeqDeserializer},
+    DeserializeOwned, IntoDeserializer, MapAccess, SeqAccess, Visitor,
+  },
+  forward_to_deserialize_any,
+  ser::{self, SerializeMap as _, SerializeSeq as _},
+  Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{collections::HashMap, convert::TryFrom, fmt};
+
+pub fn to_object<T: Serialize + ?Sized>(value: &T) -> Result<Object, Error> {
+  value.serialize(ObjectSerializer)
+}
+
+pub fn from_object<T: DeserializeOwned>(object: Object) -> Result<T, Error> {
+  T::deserialize(object)
+}
+
+impl ser::Error for Error {
+  fn custom<T: fmt::Display>(message: T) -> Self {
+    Error::conversion(&message.to_string())
+  }
+}
+
+impl de::Error for Error {
+  fn custom<T: fmt::Display>(message: T) -> Self {
+    Error::conversion(&message.to_string())
+  }
+}
+
+impl Serialize for Object {
+  fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+    match self {
+      Object::Null => serializer.serialize_unit(),
+      Object::Boolean(value) => serializer.serialize_bool(*value),
+      Object::Integer(value) => serializer.serialize_i64(*value),
+      Object::Float(value) => serializer.serialize_f64(*value),
+      Object::String(value) => serializer.serialize_str(value),
+      Object::Color(color) => serializer.collect_str(color),
+      Object::Return(value) => value.serialize(serializer),
+      Object::Array(array) => {
+        let mut seq = serializer.serialize_seq(Some(array.len()))?;
+        for item in array {
+          seq.serialize_element(item)?;
+        }
+        seq.end()
+      }
+      Object::Hash(hash) => {
+        let mut keys: Vec<&String> = hash.keys().collect();
+        keys.sort();
+        let mut map = serializer.serialize_map(Some(hash.len()))?;
+        for key in keys {
+          map.serialize_entry(key, &hash[key])?;
+        }
+        map.end()
+      }
+      obj => Err(ser::Error::custom(format!("cannot serialize {}", obj))),
+    }
+  }
+}
+
+impl<'de> Deserialize<'de> for Object {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    deserializer.deserialize_any(ObjectVisitor)
+  }
+}
+
+struct ObjectVisitor;
+
+impl<'de> Visitor<'de> for ObjectVisitor {
+  type Value = Object;
+
+  fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    write!(f, "a null, boolean, number, string, array or map")
+  }
+
+  fn visit_bool<E: de::Error>(self, value: bool) -> Result<Object, E> {
+    Ok(Object::boolean(value))
+  }
+
+  fn visit_i64<E: de::Error>(self, value: i64) -> Result<Object, E> {
+    Ok(Object::Integer(value))
+  }
+
+  fn visit_u64<E: de::Error>(self, value: u64) -> Result<Object, E> {
+    match i64::try_from(value) {
+      Ok(value) => Ok(Object::Integer(value)),
+      Err(_) => Ok(Object::Float(value as f64)),
+    }
+  }
+
+  fn visit_f64<E: de::Error>(self, value: f64) -> Result<Object, E> {
+    Ok(Object::Float(value))
+  }
+
+  fn visit_str<E: de::Error>(self, value: &str) -> Result<Object, E> {
+    Ok(Object::String(value.to_owned()))
+  }
+
+  fn visit_string<E: de::Error>(self, value: String) -> Result<Object, E> {
+    Ok(Object::String(value))
+  }
+
+  fn visit_unit<E: de::Error>(self) -> Result<Object, E> {
+    Ok(Object::Null)
+  }
+
+  fn visit_none<E: de::Error>(self) -> Result<Object, E> {
+    Ok(Object::Null)
+  }
+
+  fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Object, D::Error> {
+    Object::deserialize(deserializer)
+  }
+
+  fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Object, A::Error> {
+    let mut array = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+    while let Some(item) = seq.next_element()? {
+      array.push(item);
+    }
+    Ok(Object::Array(array))
+  }
+
+  fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Object, A::Error> {
+    let mut hash = HashMap::with_capacity(map.size_hint().unwrap_or(0));
+    while let Some((key, value)) = map.next_entry::<String, Object>()? {
+      hash.insert(key, value);
+    }
+    Ok(Object::Hash(hash))
+  }
+}
+
+impl<'de> IntoDeserializer<'de, Error> for Object {
+  type Deserializer = Self;
+
+  fn into_deserializer(self) -> Self {
+    self
+  }
+}
+
+impl<'de> Deserializer<'de> for Object {
+  type Error = Error;
+
+  fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    match self {
+      Object::Null => visitor.visit_unit(),
+      Object::Boolean(value) => visitor.visit_bool(value),
+      Object::Integer(value) => visitor.visit_i64(value),
+      Object::Float(value) => visitor.visit_f64(value),
+      Object::String(value) => visitor.visit_string(value),
+      Object::Return(value) => value.deserialize_any(visitor),
+      Object::Array(array) => visitor.visit_seq(SeqDeserializer::new(array.into_iter())),
+      Object::Hash(hash) => visitor.visit_map(MapDeserializer::new(hash.into_iter())),
+      obj => Err(Error::conversion(&format!("cannot deserialize {}", obj))),
+    }
+  }
+
+  fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Error> {
+    match self {
+      Object::Null => visitor.visit_none(),
+      obj => visitor.visit_some(obj),
+    }
+  }
+
+  fn deserialize_newtype_struct<V: Visitor<'de>>(
+    self,
+    _name: &'static str,
+    visitor: V,
+  ) -> Result<V::Value, Error> {
+    visitor.visit_newtype_struct(self)
+  }
+
+  fn deserialize_enum<V: Visitor<'de>>(
+    self,
+    _name: &'static str,
+    _variants: &'static [&'static str],
+    visitor: V,
+  ) -> Result<V::Value, Error> {
+    match self {
+      Object::String(variant) => visitor.visit_enum(variant.into_deserializer()),
+      Object::Hash(hash) if hash.len() == 1 => visitor.visit_enum(MapAccessDeserializer::new(
+        MapDeserializer::new(hash.into_iter()),
+      )),
+      obj => Err(Error::conversion(&format!("expected an enum, got {}", obj))),
+    }
+  }
+
+  forward_to_deserialize_any! {
+    bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+    bytes byte_buf unit unit_struct seq tuple tuple_struct map struct
+    identifier ignored_any
+  }
+}
+
+struct ObjectSerializer;
+
+impl Serializer for ObjectSerializer {
+  type Ok = Object;
+  type Error = Error;
+  type SerializeSeq = SerializeArray;
+  type SerializeTuple = SerializeArray;
+  type SerializeTupleStruct = SerializeArray;
+  type SerializeTupleVariant = SerializeVariant<SerializeArray>;
+  type SerializeMap = SerializeHash;
+  type SerializeStruct = SerializeHash;
+  type SerializeStructVariant = SerializeVariant<SerializeHash>;
+
+  fn serialize_bool(self, value: bool) -> Result<Object, Error> {
+    Ok(Object::boolean(value))
+  }
+
+  fn serialize_i8(self, value: i8) -> Result<Object, Error> {
+    self.serialize_i64(value as i64)
+  }
+
+  fn serialize_i16(self, value: i16) -> Result<Object, Error> {
+    self.serialize_i64(value as i64)
+  }
+
+  fn serialize_i32(self, value: i32) -> Result<Object, Error> {
+    self.serialize_i64(value as i64)
+  }
+
+  fn serialize_i64(self, value: i64) -> Result<Object, Error> {
+    Ok(Object::Integer(value))
+  }
+
+  fn serialize_u8(self, value: u8) -> Result<Object, Error> {
+    self.serialize_i64(value as i64)
+  }
+
+  fn serialize_u16(self, value: u16) -> Result<Object, Error> {
+    self.serialize_i64(value as i64)
+  }
+
+  fn serialize_u32(self, value: u32) -> Result<Object, Error> {
+    self.serialize_i64(value as i64)
+  }
+
+  fn serialize_u64(self, value: u64) -> Result<Object, Error> {
+    match i64::try_from(value) {
+      Ok(value) => Ok(Object::Integer(value)),
+      Err(_) => Err(Error::conversion(&format!(
+        "{} does not fit in an integer",
+        value
+      ))),
+    }
+  }
+
+  fn serialize_f32(self, value: f32) -> Result<Object, Error> {
+    self.serialize_f64(value as f64)
+  }
+
+  fn serialize_f64(self, value: f64) -> Result<Object, Error> {
+    Ok(Object::Float(value))
+  }
+
+  fn serialize_char(self, value: char) -> Result<Object, Error> {
+    Ok(Object::String(value.to_string()))
+  }
+
+  fn serialize_str(self, value: &str) -> Result<Object, Error> {
+    Ok(Object::String(value.to_owned()))
+  }
+
+  fn serialize_bytes(self, value: &[u8]) -> Result<Object, Error> {
+    Ok(Object::Array(
+      value
+        .iter()
+        .map(|byte| Object::Integer(*byte as i64))
+        .collect(),
+    ))
+  }
+
+  fn serialize_none(self) -> Result<Object, Error> {
+    Ok(Object::Null)
+  }
+
+  fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<Object, Error> {
+    value.serialize(self)
+  }
+
+  fn serialize_unit(self) -> Result<Object, Error> {
+    Ok(Object::Null)
+  }
+
+  fn serialize_unit_struct(self, _name: &'static str) -> Result<Object, Error> {
+    Ok(Object::Null)
+  }
+
+  fn serialize_unit_variant(
+    self,
+    _name: &'static str,
+    _index: u32,
+    variant: &'static str,
+  ) -> Result<Object, Error> {
+    Ok(Object::String(variant.to_owned()))
+  }
+
+  fn serialize_newtype_struct<T: Serialize + ?Sized>(
+    self,
+    _name: &'static str,
+    value: &T,
+  ) -> Result<Object, Error> {
+    value.serialize(self)
+  }
+
+  fn serialize_newtype_variant<T: Serialize + ?Sized>(
+    self,
+    _name: &'static str,
+    _index: u32,
+    variant: &'static str,
+    value: &T,
+  ) -> Result<Object, Error> {
+    let mut hash = HashMap::new();
+    hash.insert(variant.to_owned(), to_object(value)?);
+    Ok(Object::Hash(hash))
+  }
+
+  fn serialize_seq(self, len: Option<usize>) -> Result<SerializeArray, Error> {
+    Ok(SerializeArray(Vec::with_capacity(len.unwrap_or(0))))
+  }
+
+  fn serialize_tuple(self, len: usize) -> Result<SerializeArray, Error> {
+    self.serialize_seq(Some(len))
+  }
+
+  fn serialize_tuple_struct(
+    self,
+    _name: &'static str,
+    len: usize,
+  ) -> Result<SerializeArray, Error> {
+    self.serialize_seq(Some(len))
+  }
+
+  fn serialize_tuple_variant(
+    self,
+    _name: &'static str,
+    _index: u32,
+    variant: &'static str,
+    len: usize,
+  ) -> Result<SerializeVariant<SerializeArray>, Error> {
+    Ok(SerializeVariant(variant, self.serialize_seq(Some(len))?))
+  }
+
+  fn serialize_map(self, len: Option<usize>) -> Result<SerializeHash, Error> {
+    Ok(SerializeHash(
+      HashMap::with_capacity(len.unwrap_or(0)),
+      None,
+    ))
+  }
+
+  fn serialize_struct(self, _name: &'static str, len: usize) -> Result<SerializeHash, Error> {
+    self.serialize_map(Some(len))
+  }
+
+  fn serialize_struct_variant(
+    self,
+    _name: &'static str,
+    _index: u32,
+    variant: &'static str,
+    len: usize,
+  ) -> Result<SerializeVariant<SerializeHash>, Error> {
+    Ok(SerializeVariant(variant, self.serialize_map(Some(len))?))
+  }
+}
+
+struct SerializeArray(Vec<Object>);
+
+struct SerializeHash(HashMap<String, Object>, Option<String>);
+
+struct SerializeVariant<T>(&'static str, T);
+
+impl SerializeArray {
+  fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+    self.0.push(to_object(value)?);
+    Ok(())
+  }
+}
+
+impl SerializeHash {
+  fn insert<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), Error> {
+    self.0.insert(key.to_owned(), to_object(value)?);
+    Ok(())
+  }
+}
+
+impl<T> SerializeVariant<T> {
+  fn wrap(variant: &str, value: Object) -> Object {
+    let mut hash = HashMap::new();
+    hash.insert(variant.to_owned(), value);
+    Object::Hash(hash)
+  }
+}
+
+impl ser::SerializeSeq for SerializeArray {
+  type Ok = Object;
+  type Error = Error;
+
+  fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+    self.push(value)
+  }
+
+  fn end(self) -> Result<Object, Error> {
+    Ok(Object::Array(self.0))
+  }
+}
+
+impl ser::SerializeTuple for SerializeArray {
+  type Ok = Object;
+  type Error = Error;
+
+  fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+    self.push(value)
+  }
+
+  fn end(self) -> Result<Object, Error> {
+    Ok(Object::Array(self.0))
+  }
+}
+
+impl ser::SerializeTupleStruct for SerializeArray {
+  type Ok = Object;
+  type Error = Error;
+
+  fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+    self.push(value)
+  }
+
+  fn end(self) -> Result<Object, Error> {
+    Ok(Object::Array(self.0))
+  }
+}
+
+impl ser::SerializeTupleVariant for SerializeVariant<SerializeArray> {
+  type Ok = Object;
+  type Error = Error;
+
+  fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+    self.1.push(value)
+  }
+
+  fn end(self) -> Result<Object, Error> {
+    Ok(Self::wrap(self.0, Object::Array((self.1).0)))
+  }
+}
+
+impl ser::SerializeMap for SerializeHash {
+  type Ok = Object;
+  type Error = Error;
+
+  fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Error> {
+    let key = to_object(key)?;
+    match key.hash_key() {
+      Some(key) => {
+        self.1 = Some(key);
+        Ok(())
+      }
+      None => Err(Error::conversion(&format!(
+        "{} cannot be used as a key",
+        key
+      ))),
+    }
+  }
+
+  fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Error> {
+    match self.1.take() {
+      Some(key) => self.insert(&key, value),
+      None => Err(Error::conversion("map value serialized before its key")),
+    }
+  }
+
+  fn end(self) -> Result<Object, Error> {
+    Ok(Object::Hash(self.0))
+  }
+}
+
+impl ser::SerializeStruct for SerializeHash {
+  type Ok = Object;
+  type Error = Error;
+
+  fn serialize_field<T: Serialize + ?Sized>(
+    &mut self,
+    key: &'static str,
+    value: &T,
+  ) -> Result<(), Error> {
+    self.insert(key, value)
+  }
+
+  fn end(self) -> Result<Object, Error> {
+    Ok(Object::Hash(self.0))
+  }
+}
+
+impl ser::SerializeStructVariant for SerializeVariant<SerializeHash> {
+  type Ok = Object;
+  type Error = Error;
+
+  fn serialize_field<T: Serialize + ?Sized>(
+    &mut self,
+    key: &'static str,
+    value: &T,
+  ) -> Result<(), Error> {
+    self.1.insert(key, value)
+  }
+
+  fn end(self) -> Result<Object, Error> {
+    Ok(Self::wrap(self.0, Object::Hash((self.1).0)))
+  }
+}
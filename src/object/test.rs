@@ -1,9 +1,10 @@
-use super::Object;
+use super::{Color, Object};
 use crate::{
-  ast::{Expression, Statement},
+  ast::{Expression, Parameter, Statement},
   env,
+  error::ErrorType,
 };
-use std::{collections::HashMap, rc::Rc};
+use std::{cmp::Ordering, collections::HashMap, rc::Rc};
 
 #[test]
 fn print_hash() {
@@ -54,7 +55,8 @@ fn print_return() {
 fn print_function() {
   assert_eq!(
     Object::Function(
-      vec!["argc".to_owned(), "argv".to_owned()],
+      None,
+      vec![Parameter::new("argc"), Parameter::new("argv")],
       Rc::new(Statement::Expression(Expression::Integer(0))),
       env::global()
     )
@@ -63,10 +65,28 @@ fn print_function() {
   )
 }
 
+#[test]
+fn print_function_with_optional_parameters() {
+  assert_eq!(
+    Object::Function(
+      Some("format".to_owned()),
+      vec![
+        Parameter::new("value"),
+        Parameter::with_default("width", Expression::Integer(10)),
+        Parameter::rest("others")
+      ],
+      Rc::new(Statement::Expression(Expression::Integer(0))),
+      env::global()
+    )
+    .to_string(),
+    "fn(value, width?, ...others)".to_owned()
+  )
+}
+
 #[test]
 fn print_builtin() {
   assert_eq!(
-    Object::BuiltIn(|_v| Ok(Object::Null)).to_string(),
+    Object::builtin(|_, _| Ok(Object::Null)).to_string(),
     "builtin fn()".to_owned()
   )
 }
@@ -75,3 +95,164 @@ fn print_builtin() {
 fn print_null() {
   assert_eq!(Object::Null.to_string(), "null".to_owned())
 }
+
+#[test]
+fn float_arithmetic() {
+  assert_eq!(
+    Object::Float(1.5) + Object::Float(2.0),
+    Ok(Object::Float(3.5))
+  );
+  assert_eq!(
+    Object::Integer(3) * Object::Float(0.5),
+    Ok(Object::Float(1.5))
+  );
+  assert_eq!(
+    Object::Float(1.0) / Object::Integer(4),
+    Ok(Object::Float(0.25))
+  );
+  assert_eq!(-Object::Float(2.5), Ok(Object::Float(-2.5)));
+  assert_eq!(
+    Object::Integer(1).compare_to(&Object::Float(1.5)),
+    Ok(Some(Ordering::Less))
+  );
+  assert_eq!(Object::Float(2.0).to_string(), "2.0".to_owned());
+}
+
+#[test]
+fn failing_arithmetic_returns_errors() {
+  for result in [
+    Object::Integer(1) / Object::Integer(0),
+    Object::Integer(i64::MIN) / Object::Integer(-1),
+    Object::Integer(i64::MAX) + Object::Integer(1),
+    Object::Integer(i64::MIN) - Object::Integer(1),
+    Object::Integer(i64::MAX) * Object::Integer(2),
+    -Object::Integer(i64::MIN),
+  ] {
+    assert_eq!(
+      result.unwrap_err().error_type(),
+      &ErrorType::ArithmeticError
+    );
+  }
+
+  let err = (Object::Integer(1) + Object::String("a".to_owned())).unwrap_err();
+  assert_eq!(err.error_type(), &ErrorType::TypeMismatch);
+  let err = Object::Null.compare_to(&Object::Integer(1)).unwrap_err();
+  assert_eq!(err.error_type(), &ErrorType::TypeMismatch);
+}
+
+#[test]
+fn colors_from_hex() {
+  assert_eq!(
+    Color::from_hex("#ff8800"),
+    Some(Color::new(255, 136, 0, 255))
+  );
+  assert_eq!(Color::from_hex("#f80a"), Some(Color::new(255, 136, 0, 170)));
+  assert_eq!(Color::from_hex("F80"), Some(Color::new(255, 136, 0, 255)));
+  assert_eq!(
+    Color::from_hex("#ff880080"),
+    Some(Color::new(255, 136, 0, 128))
+  );
+  assert_eq!(
+    Color::from_hex("#ff88"),
+    Some(Color::new(255, 255, 136, 136))
+  );
+  assert_eq!(Color::from_hex("#ff880"), None);
+  assert_eq!(Color::from_hex("#gg8800"), None);
+}
+
+#[test]
+fn colors_to_and_from_hsl() {
+  let orange = Color::new(255, 136, 0, 255);
+  let (hue, saturation, lightness) = orange.to_hsl();
+  assert_eq!(hue.round(), 32.0);
+  assert_eq!(saturation, 1.0);
+  assert_eq!(lightness, 0.5);
+  assert_eq!(Color::from_hsl(hue, saturation, lightness, 255), orange);
+  assert_eq!(
+    Color::from_hsl(240.0, 1.0, 0.25, 255),
+    Color::new(0, 0, 128, 255)
+  );
+}
+
+#[test]
+fn print_color() {
+  assert_eq!(
+    Object::Color(Color::new(255, 136, 0, 255)).to_string(),
+    "#ff8800"
+  );
+  assert_eq!(
+    Object::Color(Color::new(0, 0, 0, 128)).to_string(),
+    "#00000080"
+  );
+}
+
+#[cfg(feature = "serde")]
+mod serde {
+  use super::super::{from_object, to_object, Object};
+  use crate::error::ErrorType;
+  use serde::{Deserialize, Serialize};
+  use std::collections::HashMap;
+
+  #[derive(Serialize, Deserialize, Debug, PartialEq)]
+  enum Role {
+    Admin,
+    Guest { expires: Option<i64> },
+  }
+
+  #[derive(Serialize, Deserialize, Debug, PartialEq)]
+  struct User {
+    name: String,
+    age: u8,
+    score: f64,
+    tags: Vec<String>,
+    role: Role,
+  }
+
+  #[test]
+  fn round_trip_structs() {
+    let user = User {
+      name: "leo".to_owned(),
+      age: 30,
+      score: 9.5,
+      tags: vec!["a".to_owned()],
+      role: Role::Guest { expires: None },
+    };
+
+    let object = to_object(&user).unwrap();
+    match &object {
+      Object::Hash(hash) => {
+        assert_eq!(hash["name"], Object::String("leo".to_owned()));
+        assert_eq!(hash["age"], Object::Integer(30));
+        assert_eq!(hash["score"], Object::Float(9.5));
+      }
+      obj => panic!("expected a hash, got {}", obj),
+    }
+    assert_eq!(from_object::<User>(object), Ok(user));
+    assert_eq!(
+      from_object::<Role>(Object::String("Admin".to_owned())),
+      Ok(Role::Admin)
+    );
+  }
+
+  #[test]
+  fn round_trip_objects() {
+    let mut hash = HashMap::new();
+    hash.insert(
+      "list".to_owned(),
+      Object::Array(vec![Object::Null, Object::TRUE]),
+    );
+    let object = Object::Hash(hash);
+
+    assert_eq!(from_object::<Object>(object.clone()), Ok(object));
+  }
+
+  #[test]
+  fn functions_cannot_be_serialized() {
+    let object = Object::Array(vec![Object::builtin(|_, _| Ok(Object::Null))]);
+
+    let error = from_object::<Object>(object).unwrap_err();
+    assert_eq!(error.error_type(), &ErrorType::ConversionError);
+    let error = from_object::<User>(Object::Integer(1)).unwrap_err();
+    assert_eq!(error.error_type(), &ErrorType::ConversionError);
+  }
+}
@@ -0,0 +1,5 @@
+mod optimizer;
+#[cfg(test)]
+mod test;
+
+pub use optimizer::optimize;
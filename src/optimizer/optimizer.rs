@@ -0,0 +1,353 @@
+use crate::ast::{Expression, MatchArm, Parameter, Pattern, Statement};
+use std::{collections::HashMap, rc::Rc};
+
+pub fn optimize(program: Vec<Statement>) -> Vec<Statement> {
+  let mut optimizer = Optimizer {
+    scopes: vec![Scope::global()],
+  };
+  optimizer.block(program)
+}
+
+#[derive(Clone, Copy)]
+enum Binding {
+  Slot(usize),
+  Dynamic,
+}
+
+// Every scope matches one environment created by the visitor. Names bound
+// by `let` and `const` (and parameters) live in numbered slots; the global
+// scope, loop variables, function names and pattern bindings stay in the
+// environment's name map and are still looked up by name.
+#[derive(Default)]
+struct Scope {
+  declared: HashMap<String, Binding>,
+  hoisted: HashMap<String, usize>,
+  slots: usize,
+  function: bool,
+  global: bool,
+}
+
+impl Scope {
+  fn global() -> Self {
+    Self {
+      global: true,
+      ..Self::default()
+    }
+  }
+
+  fn function() -> Self {
+    Self {
+      function: true,
+      ..Self::default()
+    }
+  }
+}
+
+struct Optimizer {
+  scopes: Vec<Scope>,
+}
+
+impl Optimizer {
+  fn block(&mut self, mut statements: Vec<Statement>) -> Vec<Statement> {
+    if let Some(end) = statements
+      .iter()
+      .position(|statement| matches!(statement, Statement::Return(..)))
+    {
+      statements.truncate(end + 1);
+    }
+
+    for statement in &statements {
+      if let Statement::VariableDeclaration(name, ..) | Statement::ConstDeclaration(name, ..) =
+        statement
+      {
+        self.hoist(name);
+      }
+    }
+
+    statements
+      .into_iter()
+      .map(|statement| self.statement(statement))
+      .collect()
+  }
+
+  fn scoped<T>(&mut self, scope: Scope, f: impl FnOnce(&mut Self) -> T) -> T {
+    self.scopes.push(scope);
+    let result = f(self);
+    self.scopes.pop();
+    result
+  }
+
+  fn statement(&mut self, statement: Statement) -> Statement {
+    match statement {
+      Statement::Block(statements) => {
+        Statement::Block(self.scoped(Scope::default(), |this| this.block(statements)))
+      }
+      Statement::VariableDeclaration(name, value, span) => {
+        let value = self.expression(value);
+        match self.declare(&name) {
+          Some(slot) => Statement::LocalDeclaration(name, value, slot),
+          None => Statement::VariableDeclaration(name, value, span),
+        }
+      }
+      Statement::ConstDeclaration(name, value, span) => {
+        let value = self.expression(value);
+        match self.declare(&name) {
+          Some(slot) => Statement::LocalConstDeclaration(name, value, slot),
+          None => Statement::ConstDeclaration(name, value, span),
+        }
+      }
+      Statement::Return(value) => Statement::Return(self.expression(value)),
+      Statement::Expression(value) => match self.expression(value) {
+        Expression::Conditional(condition, consequence, None, _)
+          if *condition == Expression::TRUE =>
+        {
+          *consequence
+        }
+        value => Statement::Expression(value),
+      },
+      Statement::WhileLoop(condition, body, span) => {
+        let condition = self.expression(condition);
+        Statement::WhileLoop(condition, Box::new(self.statement(*body)), span)
+      }
+      Statement::ForLoop(variable, iterable, body, span) => {
+        let iterable = self.expression(iterable);
+        self.declare_dynamic(&variable);
+        Statement::ForLoop(variable, iterable, Box::new(self.statement(*body)), span)
+      }
+      statement => statement,
+    }
+  }
+
+  fn expression(&mut self, expression: Expression) -> Expression {
+    match expression {
+      Expression::Id(name, span) => match self.resolve(&name) {
+        Some((depth, slot)) => Expression::Local(name, depth, slot),
+        None => Expression::Id(name, span),
+      },
+      Expression::Call(function, args, span) => {
+        let function = self.expression(*function);
+        Expression::Call(Box::new(function), self.expressions(args), span)
+      }
+      Expression::Prefix(operator, operand) => fold_prefix(&operator, self.expression(*operand)),
+      Expression::Infix(operator, left, right) => {
+        let right = self.expression(*right);
+        let left = self.expression(*left);
+        fold_infix(&operator, left, right)
+      }
+      Expression::Conditional(condition, consequence, alternative, span) => {
+        match self.expression(*condition) {
+          Expression::Boolean(true) => {
+            let consequence = self.statement(*consequence);
+            Expression::conditional(Expression::TRUE, consequence, None)
+          }
+          Expression::Boolean(false) => match alternative {
+            Some(alternative) => {
+              let alternative = self.statement(*alternative);
+              Expression::conditional(Expression::TRUE, alternative, None)
+            }
+            None => Expression::Null,
+          },
+          condition => {
+            let consequence = self.statement(*consequence);
+            let alternative = alternative.map(|alternative| Box::new(self.statement(*alternative)));
+            Expression::Conditional(
+              Box::new(condition),
+              Box::new(consequence),
+              alternative,
+              span,
+            )
+          }
+        }
+      }
+      Expression::Function(name, parameters, body, span) => {
+        if let Some(name) = &name {
+          self.declare_dynamic(name);
+        }
+        let body = Rc::try_unwrap(body).unwrap_or_else(|body| (*body).clone());
+        let (parameters, body) = self.scoped(Scope::function(), |this| {
+          let parameters = parameters
+            .into_iter()
+            .map(|parameter| this.parameter(parameter))
+            .collect();
+          (parameters, this.statement(body))
+        });
+        Expression::Function(name, parameters, Rc::new(body), span)
+      }
+      Expression::Array(items) => Expression::Array(self.expressions(items)),
+      Expression::Hash(entries) => Expression::Hash(
+        entries
+          .into_iter()
+          .map(|(key, value)| (self.expression(key), self.expression(value)))
+          .collect(),
+      ),
+      Expression::Index(indexed, index) => {
+        let indexed = self.expression(*indexed);
+        Expression::index(indexed, self.expression(*index))
+      }
+      Expression::Property(object, name) => Expression::property(self.expression(*object), &name),
+      Expression::Match(value, arms) => {
+        let value = self.expression(*value);
+        let arms = arms.into_iter().map(|arm| self.arm(arm)).collect();
+        Expression::r#match(value, arms)
+      }
+      Expression::Spread(value) => Expression::spread(self.expression(*value)),
+      Expression::Named(name, value) => Expression::named(&name, self.expression(*value)),
+      expression => expression,
+    }
+  }
+
+  fn expressions(&mut self, expressions: Vec<Expression>) -> Vec<Expression> {
+    expressions
+      .into_iter()
+      .map(|expression| self.expression(expression))
+      .collect()
+  }
+
+  fn parameter(&mut self, parameter: Parameter) -> Parameter {
+    let default = parameter.default.map(|default| self.expression(default));
+    self.declare(&parameter.name);
+    Parameter {
+      default,
+      ..parameter
+    }
+  }
+
+  fn arm(&mut self, arm: MatchArm) -> MatchArm {
+    let mut scope = Scope::default();
+    for name in arm.pattern.bindings() {
+      scope.declared.insert(name.to_owned(), Binding::Dynamic);
+    }
+
+    self.scoped(scope, |this| {
+      let pattern = this.pattern(arm.pattern);
+      let guard = arm.guard.map(|guard| this.expression(guard));
+      MatchArm::new(pattern, guard, this.statement(arm.body))
+    })
+  }
+
+  fn pattern(&mut self, pattern: Pattern) -> Pattern {
+    match pattern {
+      Pattern::Literal(literal) => Pattern::Literal(self.expression(literal)),
+      Pattern::Array(patterns, rest) => Pattern::Array(
+        patterns
+          .into_iter()
+          .map(|pattern| self.pattern(pattern))
+          .collect(),
+        rest,
+      ),
+      Pattern::Hash(entries) => Pattern::Hash(
+        entries
+          .into_iter()
+          .map(|(key, pattern)| (key, self.pattern(pattern)))
+          .collect(),
+      ),
+      Pattern::Or(patterns) => Pattern::Or(
+        patterns
+          .into_iter()
+          .map(|pattern| self.pattern(pattern))
+          .collect(),
+      ),
+      pattern => pattern,
+    }
+  }
+
+  fn hoist(&mut self, name: &str) {
+    let scope = self.scopes.last_mut().unwrap();
+    if !scope.global && !scope.hoisted.contains_key(name) {
+      scope.hoisted.insert(name.to_owned(), scope.slots);
+      scope.slots += 1;
+    }
+  }
+
+  fn declare(&mut self, name: &str) -> Option<usize> {
+    self.hoist(name);
+    let scope = self.scopes.last_mut().unwrap();
+    let slot = *scope.hoisted.get(name)?;
+    scope.declared.insert(name.to_owned(), Binding::Slot(slot));
+    Some(slot)
+  }
+
+  fn declare_dynamic(&mut self, name: &str) {
+    let scope = self.scopes.last_mut().unwrap();
+    if !scope.global {
+      scope.declared.insert(name.to_owned(), Binding::Dynamic);
+    }
+  }
+
+  // A function body runs after the enclosing scopes have finished declaring
+  // their names, so once a lookup leaves a function it also sees the slots
+  // of declarations that come later in the source.
+  fn resolve(&self, name: &str) -> Option<(usize, usize)> {
+    let mut crossed = false;
+    for (depth, scope) in self.scopes.iter().rev().enumerate() {
+      match scope.declared.get(name) {
+        Some(Binding::Slot(slot)) => return Some((depth, *slot)),
+        Some(Binding::Dynamic) => return None,
+        None => (),
+      }
+      if crossed {
+        if let Some(slot) = scope.hoisted.get(name) {
+          return Some((depth, *slot));
+        }
+      }
+      crossed |= scope.function;
+    }
+    None
+  }
+}
+
+fn fold_prefix(operator: &str, operand: Expression) -> Expression {
+  match (operator, &operand) {
+    ("-", Expression::Integer(value)) if *value != i64::MIN => Expression::Integer(-value),
+    ("-", Expression::Float(value)) => Expression::Float(-value),
+    ("!", Expression::Boolean(value)) => Expression::Boolean(!value),
+    _ => Expression::prefix(operator, operand),
+  }
+}
+
+fn fold_infix(operator: &str, left: Expression, right: Expression) -> Expression {
+  use Expression::{Boolean, Float, Integer, String};
+
+  let folded = match (operator, &left, &right) {
+    ("+", Integer(left), Integer(right)) => left.checked_add(*right).map(Integer),
+    ("-", Integer(left), Integer(right)) => left.checked_sub(*right).map(Integer),
+    ("*", Integer(left), Integer(right)) => left.checked_mul(*right).map(Integer),
+    ("/", Integer(left), Integer(right)) => left.checked_div(*right).map(Integer),
+    ("<", Integer(left), Integer(right)) => Some(Boolean(left < right)),
+    (">", Integer(left), Integer(right)) => Some(Boolean(left > right)),
+    ("+", String(left), String(right)) => Some(String(format!("{}{}", left, right))),
+    ("<", String(left), String(right)) => Some(Boolean(left < right)),
+    (">", String(left), String(right)) => Some(Boolean(left > right)),
+    ("==", left, right) if is_literal(left) && is_literal(right) => Some(Boolean(left == right)),
+    ("!=", left, right) if is_literal(left) && is_literal(right) => Some(Boolean(left != right)),
+    (operator, left, right) => match (float(left), float(right)) {
+      (Some(left), Some(right)) => match operator {
+        "+" => Some(Float(left + right)),
+        "-" => Some(Float(left - right)),
+        "*" => Some(Float(left * right)),
+        "/" => Some(Float(left / right)),
+        "<" => Some(Boolean(left < right)),
+        ">" => Some(Boolean(left > right)),
+        _ => None,
+      },
+      _ => None,
+    },
+  };
+
+  folded.unwrap_or_else(|| Expression::infix(operator, left, right))
+}
+
+fn float(expression: &Expression) -> Option<f64> {
+  match expression {
+    Expression::Float(value) => Some(*value),
+    Expression::Integer(value) => Some(*value as f64),
+    _ => None,
+  }
+}
+
+fn is_literal(expression: &Expression) -> bool {
+  matches!(
+    expression,
+    Expression::Integer(..) | Expression::String(..) | Expression::Boolean(..) | Expression::Null
+  )
+}
@@ -0,0 +1,255 @@
+use super::optimize;
+use crate::{
+  ast::{without_spans, Expression, Parameter, Statement},
+  helium::parse,
+  interpreter::Interpreter,
+  object::Object,
+};
+use std::rc::Rc;
+
+fn optimized(input: &str) -> Vec<Statement> {
+  without_spans(optimize(parse(input).unwrap()))
+}
+
+fn function_body(statement: &Statement) -> Vec<Statement> {
+  match statement {
+    Statement::Expression(Expression::Function(_, _, body, _)) => match &**body {
+      Statement::Block(block) => block.clone(),
+      body => panic!("expected a block, got {:?}", body),
+    },
+    statement => panic!("expected a function, got {:?}", statement),
+  }
+}
+
+#[test]
+fn folds_constant_expressions() {
+  assert_eq!(
+    optimized("1 + 2 * 3; \"a\" + \"b\"; -(4 / 2); 1.5 * 2; 2 > 1 == !false"),
+    vec![
+      Statement::Expression(Expression::Integer(7)),
+      Statement::Expression(Expression::String("ab".to_owned())),
+      Statement::Expression(Expression::Integer(-2)),
+      Statement::Expression(Expression::Float(3.0)),
+      Statement::Expression(Expression::TRUE),
+    ]
+  );
+}
+
+#[test]
+fn leaves_failing_operations_to_run_time() {
+  assert_eq!(
+    optimized("1 / 0; 1 + \"a\"; 9223372036854775807 + 1"),
+    vec![
+      Statement::Expression(Expression::infix(
+        "/",
+        Expression::Integer(1),
+        Expression::Integer(0)
+      )),
+      Statement::Expression(Expression::infix(
+        "+",
+        Expression::Integer(1),
+        Expression::String("a".to_owned())
+      )),
+      Statement::Expression(Expression::infix(
+        "+",
+        Expression::Integer(i64::MAX),
+        Expression::Integer(1)
+      )),
+    ]
+  );
+}
+
+#[test]
+fn simplifies_constant_conditions() {
+  assert_eq!(
+    optimized("if true { a } else { b }; if 1 > 2 { a } else { b }; if false { a }"),
+    vec![
+      Statement::Block(vec![Statement::Expression(Expression::id("a"))]),
+      Statement::Block(vec![Statement::Expression(Expression::id("b"))]),
+      Statement::Expression(Expression::Null),
+    ]
+  );
+
+  assert_eq!(
+    optimized("let x = if false { a } else { b }"),
+    vec![Statement::variable(
+      "x",
+      Expression::conditional(
+        Expression::TRUE,
+        Statement::Block(vec![Statement::Expression(Expression::id("b"))]),
+        None
+      )
+    )]
+  );
+}
+
+#[test]
+fn removes_code_after_return() {
+  let program = optimized("fn f() { return 1; print(2); 3 }");
+  assert_eq!(
+    function_body(&program[0]),
+    vec![Statement::Return(Expression::Integer(1))]
+  );
+}
+
+#[test]
+fn resolves_locals_to_slots() {
+  let program = optimized(
+    "fn f(a, b = a) {
+  let x = a + b
+  const y = x
+  { let x = 1; x + y }
+  global
+}",
+  );
+
+  assert_eq!(
+    program,
+    vec![Statement::Expression(Expression::Function(
+      Some("f".to_owned()),
+      vec![
+        Parameter::new("a"),
+        Parameter::with_default("b", Expression::Local("a".to_owned(), 0, 0)),
+      ],
+      Rc::new(Statement::Block(vec![
+        Statement::LocalDeclaration(
+          "x".to_owned(),
+          Expression::infix(
+            "+",
+            Expression::Local("a".to_owned(), 1, 0),
+            Expression::Local("b".to_owned(), 1, 1)
+          ),
+          0
+        ),
+        Statement::LocalConstDeclaration(
+          "y".to_owned(),
+          Expression::Local("x".to_owned(), 0, 0),
+          1
+        ),
+        Statement::Block(vec![
+          Statement::LocalDeclaration("x".to_owned(), Expression::Integer(1), 0),
+          Statement::Expression(Expression::infix(
+            "+",
+            Expression::Local("x".to_owned(), 0, 0),
+            Expression::Local("y".to_owned(), 1, 1)
+          )),
+        ]),
+        Statement::Expression(Expression::id("global")),
+      ])),
+      Default::default()
+    ))]
+  );
+}
+
+#[test]
+fn keeps_dynamic_bindings_by_name() {
+  let program = optimized(
+    "fn f(xs) {
+  for x in xs { x }
+  fn g() { g }
+  match xs { [y] => y }
+}",
+  );
+  let body = function_body(&program[0]);
+
+  assert_eq!(
+    body[0],
+    Statement::for_loop(
+      "x",
+      Expression::Local("xs".to_owned(), 1, 0),
+      Statement::Block(vec![Statement::Expression(Expression::id("x"))])
+    )
+  );
+  assert_eq!(function_body(&body[1]), vec![Expression::id("g").into()]);
+  match &body[2] {
+    Statement::Expression(Expression::Match(value, arms)) => {
+      assert_eq!(**value, Expression::Local("xs".to_owned(), 1, 0));
+      assert_eq!(arms[0].body, Expression::id("y").into());
+    }
+    statement => panic!("expected a match, got {:?}", statement),
+  }
+}
+
+#[test]
+fn closures_see_later_declarations() {
+  let program = optimized(
+    "fn f() {
+  let g = fn() { later }
+  let later = 1
+  g()
+}",
+  );
+  let body = function_body(&program[0]);
+
+  match &body[0] {
+    Statement::LocalDeclaration(_, Expression::Function(_, _, closure, _), 0) => assert_eq!(
+      **closure,
+      Statement::Block(vec![Statement::Expression(Expression::Local(
+        "later".to_owned(),
+        2,
+        1
+      ))])
+    ),
+    statement => panic!("expected a closure, got {:?}", statement),
+  }
+}
+
+#[test]
+fn optimized_programs_behave_the_same() {
+  let run = |input: &str| Interpreter::new().run(input).unwrap();
+
+  assert_eq!(
+    run(
+      "fn counter() {
+  let count = 0
+  fn() { count = count + 1; count }
+}
+let next = counter()
+next()
+next()"
+    ),
+    Object::Integer(2)
+  );
+  assert_eq!(
+    run(
+      "let g = 10
+fn f() {
+  let early = fn() { late + g }
+  let late = 5
+  let x = g
+  let g = 2
+  early() + x + g
+}
+f()"
+    ),
+    Object::Integer(19)
+  );
+  assert_eq!(
+    run(
+      "fn f(n) {
+  fn even(n) { if n == 0 { true } else { odd(n - 1) } }
+  fn odd(n) { if n == 0 { false } else { even(n - 1) } }
+  even(n)
+}
+f(10)"
+    ),
+    Object::TRUE
+  );
+  assert_eq!(
+    run("{ let x = 1; { let f = fn() { x }; let seen = f(); let x = 2; seen } }"),
+    Object::Integer(1)
+  );
+  assert_eq!(
+    run("{ let x = 1; { let f = fn() { x = 7 }; f(); let x = 2 }; x }"),
+    Object::Integer(7)
+  );
+  assert_eq!(
+    run("let g = fn() { let i = 5; let f = fn() { i }; for i in [1, 2] {}; [f(), i] }; g()"),
+    Object::Array(vec![Object::Integer(2), Object::Integer(2)])
+  );
+
+  let errors = Interpreter::new()
+    .run("fn f() { const x = 1; x = 2 }; f()")
+    .unwrap_err();
+  assert_eq!(errors[0].message(), "cannot assign to constant 'x'");
+}
@@ -1,59 +1,186 @@
 use crate::{
-  ast::{Expression, Precedence, Statement},
+  ast::{Expression, MatchArm, Parameter, Pattern, Precedence, Span, Statement, Trivia},
   error::Error,
+  helpers::unescape,
   lexer::{Lexer, Token},
+  object::{Color, Regex},
+  warning::Warning,
 };
-use std::rc::Rc;
+use std::{ops::Range, rc::Rc};
 
 type Result<T> = std::result::Result<T, Error>;
 
 pub struct Parser<'a> {
   lexer: Lexer<'a, Token<'a>>,
   current: Token<'a>,
+  current_span: Range<usize>,
+  previous_start: usize,
+  previous_end: usize,
+  reached_eof: bool,
+  incomplete: bool,
+  newline_before: bool,
+  groups: Vec<bool>,
+  token_end: usize,
+  comments: Vec<(Range<usize>, bool)>,
+  keep_comments: bool,
+  line_starts: Vec<usize>,
   pub errors: Vec<Error>,
+  pub warnings: Vec<Warning>,
 }
 
 impl<'a> Parser<'a> {
-  pub fn new(mut lexer: Lexer<'a, Token<'a>>) -> Self {
-    Parser {
-      current: lexer.next().unwrap_or(Token::Eof),
+  pub fn new(lexer: Lexer<'a, Token<'a>>) -> Self {
+    let line_starts = std::iter::once(0)
+      .chain(lexer.source().match_indices('\n').map(|(i, _)| i + 1))
+      .collect();
+
+    let mut parser = Parser {
+      current: Token::Eof,
+      current_span: 0..0,
+      previous_start: 0,
+      previous_end: 0,
+      reached_eof: false,
+      incomplete: false,
+      newline_before: false,
+      groups: vec![],
+      token_end: 0,
+      comments: vec![],
+      keep_comments: false,
+      line_starts,
       lexer,
       errors: vec![],
-    }
+      warnings: vec![],
+    };
+    parser.current = parser.next_token();
+    parser.current_span = Self::token_span(&parser.lexer, &parser.current);
+    parser
+  }
+
+  pub fn keep_comments(mut self) -> Self {
+    self.keep_comments = true;
+    self
   }
 
   pub fn parse(&mut self) -> Vec<Statement> {
     let mut block = vec![];
 
     loop {
+      self.parse_trivia(&mut block);
+      let start = self.current_span.start;
       match self.parse_statement() {
-        Ok(Statement::Null) => break,
-        Err(err) => self.errors.push(err),
+        Ok(Statement::Null) if self.current == Token::Eof => break,
+        Ok(Statement::Null) => {
+          self.advance();
+          let err = Error::expected_expression(Token::RightBrace);
+          block.push(self.recover(err, start, 0));
+        }
         Ok(statement) => block.push(statement),
+        Err(err) => block.push(self.recover(err, start, 0)),
       }
     }
 
     block
   }
 
+  pub fn is_incomplete(&self) -> bool {
+    self.incomplete
+  }
+
   fn parse_block(&mut self) -> Result<Statement> {
     let mut block = vec![];
 
     self.eat(Token::LeftBrace)?;
+    let depth = self.groups.len();
     loop {
-      match self.parse_statement()? {
-        Statement::Null => {
+      self.parse_trivia(&mut block);
+      let start = self.current_span.start;
+      match self.parse_statement() {
+        Ok(Statement::Null) => {
           self.eat(Token::RightBrace)?;
           return Ok(Statement::Block(block));
         }
-        statement => block.push(statement),
+        Ok(statement) => block.push(statement),
+        Err(err) => block.push(self.recover(err, start, depth)),
       }
     }
   }
 
+  fn recover(&mut self, err: Error, start: usize, depth: usize) -> Statement {
+    let err = err.at(self.span_from(self.previous_start));
+    let reported = self.errors.iter().any(|reported| {
+      reported.message() == err.message()
+        && reported.location().map(|span| span.start) == Some(self.previous_start)
+    });
+    if !reported {
+      // Only input that runs out before its first mistake could still be
+      // completed by more lines.
+      if self.errors.is_empty() {
+        self.incomplete = self.reached_eof;
+      }
+      self.errors.push(err);
+    }
+
+    if self.current_span.start == start && self.current != Token::Eof {
+      self.advance();
+    }
+    self.synchronize(depth);
+    Statement::Error(self.span_from(start))
+  }
+
+  fn synchronize(&mut self, depth: usize) {
+    loop {
+      let nested = self.groups.len() > depth;
+      match self.current {
+        Token::Eof => return,
+        Token::RightBrace if !nested => return,
+        Token::Semicolon if !nested => {
+          self.skip_semicolons();
+          return;
+        }
+        Token::Let | Token::Const if self.newline_before => {
+          self.groups.truncate(depth);
+          return;
+        }
+        Token::Return | Token::For | Token::While | Token::Function | Token::If | Token::Match
+          if !nested =>
+        {
+          return
+        }
+        _ if !nested && self.newline_before => return,
+        _ => {
+          self.advance();
+        }
+      }
+    }
+  }
+
+  fn line_break(&self) -> bool {
+    self.newline_before && self.groups.last().copied().unwrap_or(true)
+  }
+
+  fn warn_continuation(&mut self) {
+    self.warn_at_current(Warning::ambiguous_continuation)
+  }
+
+  // The same token can be looked at again after backtracking (a call on a
+  // new line is checked by both the callee and the statement around it), so
+  // only one warning is kept per location.
+  fn warn_at_current(&mut self, warning: fn(&str) -> Warning) {
+    let token = &self.lexer.source()[self.current_span.clone()];
+    let location = self.span(self.current_span.start, self.current_span.end);
+    if self
+      .warnings
+      .iter()
+      .any(|warning| warning.location() == Some(location))
+    {
+      return;
+    }
+    self.warnings.push(warning(token).at(location));
+  }
+
   fn parse_statement(&mut self) -> Result<Statement> {
     let statement = match self.current {
-      Token::Let => self.parse_variable_declaration()?,
+      Token::Let | Token::Const => self.parse_variable_declaration()?,
       Token::Return => self.parse_return()?,
       Token::For => self.parse_for_loop()?,
       Token::While => self.parse_while_loop()?,
@@ -65,27 +192,45 @@ impl<'a> Parser<'a> {
     Ok(statement)
   }
 
+  fn parse_body(&mut self) -> Result<Statement> {
+    match self.current {
+      Token::RightBrace => Err(Error::expected_expression(Token::RightBrace)),
+      Token::Eof => Err(Error::expected_expression(self.advance())),
+      _ => self.parse_statement(),
+    }
+  }
+
   fn parse_expression(&mut self, precedence: Precedence) -> Result<Expression> {
+    let start = self.current_span.start;
     let current = self.advance();
     let left = match current {
-      Token::Id(value) => self.parse_id(value),
+      Token::Id(value) => self.parse_id(value, start),
       Token::Integer(value) => Ok(Expression::Integer(value)),
-      Token::String(value) => Ok(Expression::String(value.to_owned())),
+      Token::Float(value) => Ok(Expression::Float(value)),
+      Token::Regex(literal) => parse_regex(literal),
+      Token::Color(hex) => match Color::from_hex(hex) {
+        Some(color) => Ok(Expression::Color(color)),
+        None => Err(Error::invalid_color(hex)),
+      },
+      Token::String(value) => Ok(Expression::String(unescape(value))),
       Token::True => Ok(Expression::TRUE),
       Token::False => Ok(Expression::FALSE),
+      Token::Null => Ok(Expression::Null),
       Token::Operator(op) => self.parse_prefix(op),
       Token::LeftParen => self.parse_grouped_expression(),
-      Token::If => self.parse_if_expression(),
+      Token::If => self.parse_if_expression(start),
+      Token::Match => self.parse_match_expression(start),
       Token::LeftBracket => Ok(Expression::Array(
         self.parse_expression_list(Token::RightBracket)?,
       )),
       Token::LeftBrace => self.parse_hash(),
-      Token::Function => self.parse_function(),
+      Token::Function => self.parse_function(start),
+      Token::Pipe => self.parse_lambda(start),
       Token::Semicolon | Token::RightBrace => Ok(Expression::Null),
       token => Err(Error::expected_expression(token)),
     }?;
 
-    self.parse_infix(left, precedence)
+    self.parse_infix(left, precedence, start)
   }
 
   fn parse_hash(&mut self) -> Result<Expression> {
@@ -101,11 +246,28 @@ impl<'a> Parser<'a> {
     Ok(Expression::Hash(hash))
   }
 
-  fn parse_infix(&mut self, mut left: Expression, precedence: Precedence) -> Result<Expression> {
+  fn parse_infix(
+    &mut self,
+    mut left: Expression,
+    precedence: Precedence,
+    start: usize,
+  ) -> Result<Expression> {
     loop {
       left = match self.current {
+        Token::LeftBracket | Token::LeftParen if self.line_break() => {
+          self.warn_continuation();
+          break;
+        }
+        Token::Operator("-") | Token::Operator("+") if self.line_break() => {
+          if precedence < Precedence::Sum {
+            self.warn_continuation();
+          }
+          break;
+        }
         Token::LeftBracket => self.parse_index_expression(left)?,
-        Token::LeftParen => self.parse_function_call(left)?,
+        Token::LeftParen => self.parse_function_call(left, start)?,
+        Token::Operator(".") => self.parse_property(left)?,
+        Token::Operator("!") => break,
         Token::Operator(operator) if precedence < Precedence::from(operator) => {
           self.parse_operator(left, operator)?
         }
@@ -117,7 +279,7 @@ impl<'a> Parser<'a> {
 
   fn parse_operator(&mut self, left: Expression, operator: &'a str) -> Result<Expression> {
     self.advance();
-    let precedence = Precedence::from(&operator);
+    let precedence = Precedence::from(operator);
     Ok(Expression::infix(
       operator,
       left,
@@ -132,34 +294,51 @@ impl<'a> Parser<'a> {
     Ok(Expression::index(left, right))
   }
 
+  fn parse_property(&mut self, left: Expression) -> Result<Expression> {
+    self.advance();
+    match self.advance() {
+      Token::Id(name) => Ok(Expression::property(left, name)),
+      token => Err(Error::unexpected_token(Token::Id("..."), token)),
+    }
+  }
+
   fn parse_for_loop(&mut self) -> Result<Statement> {
     self.eat(Token::For)?;
+    let start = self.current_span.start;
     let variable = match self.advance() {
-      Token::Id(id) => id,
+      Token::Id(id) => id.to_owned(),
       token => return Err(Error::unexpected_token(Token::Id("..."), token)),
     };
+    let span = self.span_from(start);
     self.eat(Token::In)?;
 
-    Ok(Statement::for_loop(
-      &variable,
+    Ok(Statement::ForLoop(
+      variable,
       self.parse_expression(Precedence::Lowest)?,
-      self.parse_statement()?,
+      Box::new(self.parse_body()?),
+      span,
     ))
   }
 
   fn parse_while_loop(&mut self) -> Result<Statement> {
+    let start = self.current_span.start;
     self.eat(Token::While)?;
-    Ok(Statement::while_loop(
-      self.parse_expression(Precedence::Lowest)?,
-      self.parse_statement()?,
+    let condition = self.parse_expression(Precedence::Lowest)?;
+    let span = self.span_from(start);
+
+    Ok(Statement::WhileLoop(
+      condition,
+      Box::new(self.parse_body()?),
+      span,
     ))
   }
 
-  fn parse_function(&mut self) -> Result<Expression> {
-    let mut args = vec![];
-
+  fn parse_function(&mut self, start: usize) -> Result<Expression> {
+    let mut span = self.span_from(start);
+    let name_start = self.current_span.start;
     let name = match self.advance() {
       Token::Id(id) => {
+        span = self.span_from(name_start);
         self.eat(Token::LeftParen)?;
         Some(id.to_owned())
       }
@@ -167,26 +346,65 @@ impl<'a> Parser<'a> {
       token => return Err(Error::unexpected_token(Token::LeftParen, token)),
     };
 
-    while !self.eat_if(&Token::RightParen) {
-      match self.advance() {
-        Token::Id(arg) => args.push(arg.to_owned()),
+    Ok(Expression::Function(
+      name,
+      self.parse_parameters(Token::RightParen)?,
+      Rc::new(self.parse_body()?),
+      span,
+    ))
+  }
+
+  fn parse_lambda(&mut self, start: usize) -> Result<Expression> {
+    let span = self.span_from(start);
+    let parameters = self.parse_parameters(Token::Pipe)?;
+    let body = if self.current == Token::LeftBrace {
+      self.parse_block()?
+    } else {
+      Statement::Expression(self.parse_expression(Precedence::Lowest)?)
+    };
+
+    Ok(Expression::Function(None, parameters, Rc::new(body), span))
+  }
+
+  fn parse_parameters(&mut self, end: Token) -> Result<Vec<Parameter>> {
+    let mut parameters: Vec<Parameter> = vec![];
+
+    while !self.eat_if(&end) {
+      let rest = self.eat_if(&Token::Ellipsis);
+      let start = self.current_span.start;
+      let name = match self.advance() {
+        Token::Id(arg) => arg,
         token => return Err(Error::unexpected_token(Token::Id("..."), token)),
       };
+      let span = self.span_from(start);
+      if parameters.iter().any(|parameter| parameter.name == name) {
+        return Err(Error::duplicate_parameter(name));
+      }
+
+      let mut parameter = if rest {
+        Parameter::rest(name)
+      } else if self.eat_if(&Token::Operator("=")) {
+        Parameter::with_default(name, self.parse_expression(Precedence::Lowest)?)
+      } else {
+        Parameter::new(name)
+      };
+      parameter.span = span;
+      parameters.push(parameter);
 
       self.eat_if(&Token::Comma);
+      if rest {
+        self.eat(end)?;
+        break;
+      }
     }
 
-    Ok(Expression::Function(
-      name,
-      args,
-      Rc::new(self.parse_statement()?),
-    ))
+    Ok(parameters)
   }
 
   fn parse_expression_list(&mut self, end: Token) -> std::result::Result<Vec<Expression>, Error> {
     let mut args = vec![];
     while !self.eat_if(&end) {
-      args.push(self.parse_expression(Precedence::Lowest)?);
+      args.push(self.parse_list_item()?);
 
       if !self.eat_if(&Token::Comma) {
         self.eat(end)?;
@@ -196,17 +414,156 @@ impl<'a> Parser<'a> {
     Ok(args)
   }
 
-  fn parse_if_expression(&mut self) -> Result<Expression> {
+  fn parse_arguments(&mut self) -> Result<Vec<Expression>> {
+    let mut args = vec![];
+    while !self.eat_if(&Token::RightParen) {
+      let arg = match self.parse_list_item()? {
+        Expression::Id(name, _) if self.eat_if(&Token::Colon) => {
+          Expression::named(&name, self.parse_expression(Precedence::Lowest)?)
+        }
+        arg => arg,
+      };
+      args.push(arg);
+
+      if !self.eat_if(&Token::Comma) {
+        self.eat(Token::RightParen)?;
+        break;
+      }
+    }
+    Ok(args)
+  }
+
+  fn parse_list_item(&mut self) -> Result<Expression> {
+    if self.eat_if(&Token::Ellipsis) {
+      Ok(Expression::spread(
+        self.parse_expression(Precedence::Lowest)?,
+      ))
+    } else {
+      self.parse_expression(Precedence::Lowest)
+    }
+  }
+
+  fn parse_if_expression(&mut self, start: usize) -> Result<Expression> {
     let condition = self.parse_expression(Precedence::Lowest)?;
-    let consequence = self.parse_statement()?;
+    let span = self.span_from(start);
+    let consequence = self.parse_body()?;
     let alternative = if self.eat_if(&Token::Else) {
-      let else_block = self.parse_statement()?;
+      let else_block = self.parse_body()?;
       Some(else_block)
     } else {
       None
     };
 
-    Ok(Expression::conditional(condition, consequence, alternative))
+    Ok(Expression::Conditional(
+      Box::new(condition),
+      Box::new(consequence),
+      alternative.map(Box::new),
+      span,
+    ))
+  }
+
+  fn parse_match_expression(&mut self, start: usize) -> Result<Expression> {
+    let keyword = self.span(start, self.previous_end);
+    let value = self.parse_expression(Precedence::Lowest)?;
+    let mut arms = vec![];
+
+    self.eat(Token::LeftBrace)?;
+    while !self.eat_if(&Token::RightBrace) {
+      let pattern = self.parse_pattern()?;
+      let guard = if self.eat_if(&Token::If) {
+        Some(self.parse_expression(Precedence::Lowest)?)
+      } else {
+        None
+      };
+      self.eat(Token::Arrow)?;
+      arms.push(MatchArm::new(pattern, guard, self.parse_body()?));
+      self.eat_if(&Token::Comma);
+    }
+
+    if !arms
+      .iter()
+      .any(|arm| arm.guard.is_none() && arm.pattern.is_irrefutable())
+    {
+      self
+        .warnings
+        .push(Warning::non_exhaustive_match().at(keyword));
+    }
+
+    Ok(Expression::r#match(value, arms))
+  }
+
+  fn parse_pattern(&mut self) -> Result<Pattern> {
+    let pattern = self.parse_single_pattern()?;
+    if self.current != Token::Pipe {
+      return Ok(pattern);
+    }
+
+    let mut patterns = vec![pattern];
+    while self.eat_if(&Token::Pipe) {
+      patterns.push(self.parse_single_pattern()?);
+    }
+    Ok(Pattern::Or(patterns))
+  }
+
+  fn parse_single_pattern(&mut self) -> Result<Pattern> {
+    Ok(match self.advance() {
+      Token::Id("_") => Pattern::Wildcard,
+      Token::Id(id) => Pattern::Binding(id.to_owned()),
+      Token::Integer(value) => Pattern::Literal(Expression::Integer(value)),
+      Token::Operator("-") => match self.advance() {
+        Token::Integer(value) => Pattern::Literal(Expression::Integer(-value)),
+        token => return Err(Error::unexpected_token(Token::Integer(0), token)),
+      },
+      Token::String(value) => Pattern::Literal(Expression::String(unescape(value))),
+      Token::True => Pattern::Literal(Expression::TRUE),
+      Token::False => Pattern::Literal(Expression::FALSE),
+      Token::Null => Pattern::Literal(Expression::Null),
+      Token::LeftBracket => self.parse_array_pattern()?,
+      Token::LeftBrace => self.parse_hash_pattern()?,
+      token => return Err(Error::expected_pattern(token)),
+    })
+  }
+
+  fn parse_array_pattern(&mut self) -> Result<Pattern> {
+    let mut patterns = vec![];
+    let mut rest = None;
+
+    while !self.eat_if(&Token::RightBracket) {
+      if self.eat_if(&Token::Ellipsis) {
+        rest = match self.advance() {
+          Token::Id(id) => Some(id.to_owned()),
+          token => return Err(Error::unexpected_token(Token::Id("..."), token)),
+        };
+        self.eat_if(&Token::Comma);
+        self.eat(Token::RightBracket)?;
+        break;
+      }
+
+      patterns.push(self.parse_pattern()?);
+      self.eat_if(&Token::Comma);
+    }
+
+    Ok(Pattern::Array(patterns, rest))
+  }
+
+  fn parse_hash_pattern(&mut self) -> Result<Pattern> {
+    let mut entries = vec![];
+
+    while !self.eat_if(&Token::RightBrace) {
+      let key = match self.advance() {
+        Token::Id(id) | Token::String(id) => id.to_owned(),
+        token => return Err(Error::unexpected_token(Token::Id("..."), token)),
+      };
+      let pattern = if self.eat_if(&Token::Colon) {
+        self.parse_pattern()?
+      } else {
+        Pattern::Binding(key.clone())
+      };
+      entries.push((key, pattern));
+      self.eat_if(&Token::Comma);
+    }
+
+    Ok(Pattern::Hash(entries))
   }
 
   fn parse_grouped_expression(&mut self) -> Result<Expression> {
@@ -215,15 +572,17 @@ impl<'a> Parser<'a> {
     left
   }
 
-  fn parse_id(&mut self, id: &str) -> Result<Expression> {
-    Ok(Expression::Id(id.to_owned()))
+  fn parse_id(&mut self, id: &str, start: usize) -> Result<Expression> {
+    Ok(Expression::Id(id.to_owned(), self.span_from(start)))
   }
 
-  fn parse_function_call(&mut self, left: Expression) -> Result<Expression> {
+  fn parse_function_call(&mut self, left: Expression, start: usize) -> Result<Expression> {
     self.eat(Token::LeftParen)?;
-    Ok(Expression::call(
-      left,
-      self.parse_expression_list(Token::RightParen)?,
+    let args = self.parse_arguments()?;
+    Ok(Expression::Call(
+      Box::new(left),
+      args,
+      self.span_from(start),
     ))
   }
 
@@ -235,22 +594,38 @@ impl<'a> Parser<'a> {
   }
 
   fn parse_variable_declaration(&mut self) -> Result<Statement> {
-    self.eat(Token::Let)?;
+    let constant = self.eat_if(&Token::Const);
+    if !constant {
+      self.eat(Token::Let)?;
+    }
+    let start = self.current_span.start;
     let name = match self.advance() {
       Token::Id(id) => id.to_owned(),
       token => return Err(Error::unexpected_token(Token::Id("..."), token)),
     };
+    let span = self.span_from(start);
 
     self.eat(Token::Operator("="))?;
 
     let value = self.parse_expression(Precedence::Lowest)?;
 
-    Ok(Statement::VariableDeclaration(name.to_owned(), value))
+    if constant {
+      Ok(Statement::ConstDeclaration(name, value, span))
+    } else {
+      Ok(Statement::VariableDeclaration(name, value, span))
+    }
   }
 
   fn parse_return(&mut self) -> Result<Statement> {
     self.eat(Token::Return)?;
-    let value = self.parse_expression(Precedence::Lowest)?;
+    let value = match self.current {
+      Token::Eof | Token::RightBrace | Token::Semicolon => Expression::Null,
+      _ if self.line_break() => {
+        self.warn_at_current(Warning::detached_return_value);
+        Expression::Null
+      }
+      _ => self.parse_expression(Precedence::Lowest)?,
+    };
 
     Ok(Statement::Return(value))
   }
@@ -277,7 +652,110 @@ impl<'a> Parser<'a> {
     }
   }
 
+  fn parse_trivia(&mut self, block: &mut Vec<Statement>) {
+    let comments = std::mem::take(&mut self.comments);
+    if !self.keep_comments {
+      return;
+    }
+
+    let source = self.lexer.source();
+    let mut end = self.previous_end;
+    for (span, trailing) in comments {
+      let comment = source[span.clone()].trim_end().to_owned();
+      // Comments are only kept between statements; one lexed before the
+      // last token of the previous statement sat inside an expression.
+      if span.start < self.previous_end {
+        let err = Error::misplaced_comment(&comment).at(self.span(span.start, span.end));
+        self.errors.push(err);
+      } else if trailing {
+        block.push(Statement::Trivia(Trivia::TrailingComment(comment)));
+      } else {
+        if !block.is_empty() && is_blank_gap(source, end, span.start) {
+          block.push(Statement::Trivia(Trivia::BlankLine));
+        }
+        block.push(Statement::Trivia(Trivia::Comment(comment)));
+      }
+      end = end.max(span.end);
+    }
+
+    let closing = matches!(self.current, Token::RightBrace | Token::Eof);
+    if !closing && !block.is_empty() && is_blank_gap(source, end, self.current_span.start) {
+      block.push(Statement::Trivia(Trivia::BlankLine));
+    }
+  }
+
+  fn next_token(&mut self) -> Token<'a> {
+    self.newline_before = false;
+    loop {
+      match self.lexer.next() {
+        Some(Token::Newline) => self.newline_before = true,
+        Some(Token::Comment(..)) => {
+          let span = self.lexer.span();
+          let source = self.lexer.source();
+          let trailing = self.token_end > 0 && !source[self.token_end..span.start].contains('\n');
+          self.token_end = span.end;
+          self.comments.push((span, trailing));
+        }
+        Some(token) => {
+          self.token_end = self.lexer.span().end;
+          return token;
+        }
+        None => return Token::Eof,
+      }
+    }
+  }
+
   fn advance<'s>(&'s mut self) -> Token<'a> {
-    std::mem::replace(&mut self.current, self.lexer.next().unwrap_or(Token::Eof))
+    let next = self.next_token();
+    self.previous_start = self.current_span.start;
+    self.previous_end = self.current_span.end;
+    self.current_span = Self::token_span(&self.lexer, &next);
+    let current = std::mem::replace(&mut self.current, next);
+    self.reached_eof |= current == Token::Eof;
+    match current {
+      Token::LeftBrace => self.groups.push(true),
+      Token::LeftParen | Token::LeftBracket => self.groups.push(false),
+      Token::RightBrace | Token::RightParen | Token::RightBracket => {
+        self.groups.pop();
+      }
+      _ => (),
+    }
+    current
+  }
+
+  fn token_span(lexer: &Lexer<'a, Token<'a>>, token: &Token) -> Range<usize> {
+    match token {
+      Token::Eof => lexer.source().len()..lexer.source().len(),
+      _ => lexer.span(),
+    }
+  }
+
+  fn span_from(&self, start: usize) -> Span {
+    self.span(start, self.previous_end)
+  }
+
+  fn span(&self, start: usize, end: usize) -> Span {
+    let line = match self.line_starts.binary_search(&start) {
+      Ok(line) => line,
+      Err(line) => line - 1,
+    };
+
+    Span {
+      start,
+      end,
+      line: line + 1,
+      column: start - self.line_starts[line] + 1,
+    }
   }
 }
+
+fn is_blank_gap(source: &str, start: usize, end: usize) -> bool {
+  start <= end && source[start..end].matches('\n').count() > 1
+}
+
+fn parse_regex(literal: &str) -> Result<Expression> {
+  let body = &literal[2..];
+  let end = body.rfind('"').unwrap();
+  let source = body[..end].replace("\\\"", "\"");
+  Regex::new(&source, &body[end + 1..]).map(Expression::Regex)
+}
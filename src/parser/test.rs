@@ -1,8 +1,9 @@
 use super::parser::*;
 use crate::{
-  ast::{Expression, Statement},
+  ast::{without_spans, Expression, MatchArm, Parameter, Pattern, Span, Statement, Trivia},
   lexer::lex,
 };
+use std::rc::Rc;
 
 #[test]
 fn hash_indexes() {
@@ -11,7 +12,7 @@ fn hash_indexes() {
   let program = parse(input);
 
   let expected = vec![Statement::Expression(Expression::index(
-    Expression::Id("hash".to_owned()),
+    Expression::id("hash"),
     Expression::String("leonardo".to_owned()),
   ))];
 
@@ -24,8 +25,8 @@ fn hash() {
 
   let program = parse(input);
 
-  let expected = vec![Statement::VariableDeclaration(
-    "x".to_owned(),
+  let expected = vec![Statement::variable(
+    "x",
     Expression::Hash(vec![
       (
         Expression::String("leonardo".to_owned()),
@@ -48,11 +49,11 @@ fn reassign() {
   let program = parse(input);
 
   let expected = vec![
-    Statement::VariableDeclaration("x".to_owned(), Expression::Integer(0)),
+    Statement::variable("x", Expression::Integer(0)),
     Statement::Expression(Expression::infix(
       "=",
-      Expression::Id("x".to_owned()),
-      Expression::infix("+", Expression::Id("x".to_owned()), Expression::Integer(1)),
+      Expression::id("x"),
+      Expression::infix("+", Expression::id("x"), Expression::Integer(1)),
     )),
   ];
 
@@ -66,13 +67,13 @@ fn while_statements() {
   let program = parse(input);
 
   let expected = vec![
-    Statement::VariableDeclaration("x".to_owned(), Expression::Integer(0)),
+    Statement::variable("x", Expression::Integer(0)),
     Statement::while_loop(
-      Expression::infix("<", Expression::Id("x".to_owned()), Expression::Integer(10)),
+      Expression::infix("<", Expression::id("x"), Expression::Integer(10)),
       Statement::Expression(Expression::infix(
         "=",
-        Expression::Id("x".to_owned()),
-        Expression::infix("+", Expression::Id("x".to_owned()), Expression::Integer(1)),
+        Expression::id("x"),
+        Expression::infix("+", Expression::id("x"), Expression::Integer(1)),
       )),
     ),
   ];
@@ -87,13 +88,13 @@ fn while_blocks() {
   let program = parse(input);
 
   let expected = vec![
-    Statement::VariableDeclaration("x".to_owned(), Expression::Integer(0)),
+    Statement::variable("x", Expression::Integer(0)),
     Statement::while_loop(
-      Expression::infix("<", Expression::Id("x".to_owned()), Expression::Integer(10)),
+      Expression::infix("<", Expression::id("x"), Expression::Integer(10)),
       Statement::Block(vec![Statement::Expression(Expression::infix(
         "=",
-        Expression::Id("x".to_owned()),
-        Expression::infix("+", Expression::Id("x".to_owned()), Expression::Integer(1)),
+        Expression::id("x"),
+        Expression::infix("+", Expression::id("x"), Expression::Integer(1)),
       ))]),
     ),
   ];
@@ -114,9 +115,9 @@ fn for_statements() {
       Expression::Integer(2),
       Expression::Integer(3),
     ]),
-    Statement::VariableDeclaration(
-      "x".to_owned(),
-      Expression::infix("+", Expression::Id("a".to_owned()), Expression::Integer(1)),
+    Statement::variable(
+      "x",
+      Expression::infix("+", Expression::id("a"), Expression::Integer(1)),
     ),
   )];
 
@@ -136,9 +137,9 @@ fn for_blocks() {
       Expression::Integer(2),
       Expression::Integer(3),
     ]),
-    Statement::Block(vec![Statement::VariableDeclaration(
-      "x".to_owned(),
-      Expression::infix("+", Expression::Id("a".to_owned()), Expression::Integer(1)),
+    Statement::Block(vec![Statement::variable(
+      "x",
+      Expression::infix("+", Expression::id("a"), Expression::Integer(1)),
     )]),
   )];
 
@@ -152,7 +153,7 @@ fn array_expressions() {
   let program = parse(input);
 
   let expected = vec![Statement::Expression(Expression::index(
-    Expression::Id("myArray".to_owned()),
+    Expression::id("myArray"),
     Expression::infix("+", Expression::Integer(1), Expression::Integer(1)),
   ))];
 
@@ -292,9 +293,23 @@ fn let_statements() {
   let program = parse(input);
 
   let expected = vec![
-    Statement::VariableDeclaration("x".to_owned(), Expression::Integer(5)),
-    Statement::VariableDeclaration("y".to_owned(), Expression::Integer(10)),
-    Statement::VariableDeclaration("foobar".to_owned(), Expression::Integer(838383)),
+    Statement::variable("x", Expression::Integer(5)),
+    Statement::variable("y", Expression::Integer(10)),
+    Statement::variable("foobar", Expression::Integer(838383)),
+  ];
+
+  compare(program, expected)
+}
+
+#[test]
+fn const_statements() {
+  let input = "const x = 5; let y = x";
+
+  let program = parse(input);
+
+  let expected = vec![
+    Statement::constant("x", Expression::Integer(5)),
+    Statement::variable("y", Expression::id("x")),
   ];
 
   compare(program, expected)
@@ -311,8 +326,8 @@ fn function_declarations_with_args() {
     vec!["a".to_owned(), "b".to_owned()],
     Statement::Expression(Expression::infix(
       "+",
-      Expression::Id("a".to_owned()),
-      Expression::Id("b".to_owned()),
+      Expression::id("a"),
+      Expression::id("b"),
     )),
   ))];
 
@@ -332,8 +347,8 @@ fn function_blocks_with_args() {
     vec!["a".to_owned(), "b".to_owned()],
     Statement::Block(vec![Statement::Expression(Expression::infix(
       "+",
-      Expression::Id("a".to_owned()),
-      Expression::Id("b".to_owned()),
+      Expression::id("a"),
+      Expression::id("b"),
     ))]),
   ))];
 
@@ -378,13 +393,140 @@ fn call_expressions() {
 
   let program = parse(input);
   let expected = vec![Statement::Expression(Expression::call(
-    Expression::Id("add".to_owned()),
+    Expression::id("add"),
     vec![Expression::Integer(3), Expression::Integer(5)],
   ))];
 
   compare(program, expected)
 }
 
+#[test]
+fn function_declarations_with_default_and_rest_parameters() {
+  let input = "fn f(x, y = 10, ...others) x";
+
+  let expected = vec![Statement::Expression(Expression::Function(
+    Some("f".to_owned()),
+    vec![
+      Parameter::new("x"),
+      Parameter::with_default("y", Expression::Integer(10)),
+      Parameter::rest("others"),
+    ],
+    Rc::new(Statement::Expression(Expression::id("x"))),
+    Span::default(),
+  ))];
+
+  assert_eq!(parse(input), expected)
+}
+
+#[test]
+fn duplicate_parameters() {
+  for input in ["fn f(a, a) a", "|a, ...a| a"] {
+    let (_, errors) = parse_with_errors(input);
+    assert_eq!(errors.len(), 1, "errors in {:?}", input);
+  }
+}
+
+#[test]
+fn missing_bodies() {
+  for input in [
+    "match 1 { 1 => }",
+    "while true",
+    "for x in [1]",
+    "if true",
+    "fn f()",
+  ] {
+    let (_, errors) = parse_with_errors(input);
+    assert_eq!(errors.len(), 1, "errors in {:?}", input);
+  }
+}
+
+#[test]
+fn lambda_expressions() {
+  let inputs = vec![
+    ("|x| x * 2", "fn(x) x * 2"),
+    ("|a, b| a + b", "fn(a, b) a + b"),
+    ("|| 1", "fn() 1"),
+    ("|x, y = 1| { x; y }", "fn(x, y = 1) { x; y }"),
+    ("|...xs| xs", "fn(...xs) xs"),
+    ("map(arr, |x| x * 2)", "map(arr, fn(x) x * 2)"),
+  ];
+
+  for (lambda, function) in inputs {
+    assert_eq!(parse(lambda), parse(function));
+  }
+}
+
+#[test]
+fn call_expression_spans() {
+  let program = parse_with_spans("let x = 1\nlet y = add(x, 2)");
+
+  match &program[1] {
+    Statement::VariableDeclaration(_, Expression::Call(_, _, span), _) => {
+      assert_eq!((span.line, span.column), (2, 9));
+      assert_eq!((span.start, span.end), (18, 27));
+    }
+    statement => panic!("expected a call, got {:?}", statement),
+  }
+}
+
+#[test]
+fn declaration_and_identifier_spans() {
+  let program = parse_with_spans("let x = 1\nfn f(a) {\n  x + a\n}");
+
+  match &program[0] {
+    Statement::VariableDeclaration(_, _, span) => assert_eq!((span.start, span.end), (4, 5)),
+    statement => panic!("expected a declaration, got {:?}", statement),
+  }
+  match &program[1] {
+    Statement::Expression(Expression::Function(_, parameters, body, span)) => {
+      assert_eq!(
+        (span.line, span.column, span.start, span.end),
+        (2, 4, 13, 14)
+      );
+      assert_eq!((parameters[0].span.start, parameters[0].span.end), (15, 16));
+      match &**body {
+        Statement::Block(statements) => match &statements[0] {
+          Statement::Expression(Expression::Infix(_, left, _)) => match &**left {
+            Expression::Id(_, span) => assert_eq!((span.line, span.column), (3, 3)),
+            expression => panic!("expected an identifier, got {:?}", expression),
+          },
+          statement => panic!("expected an infix, got {:?}", statement),
+        },
+        statement => panic!("expected a block, got {:?}", statement),
+      }
+    }
+    statement => panic!("expected a function, got {:?}", statement),
+  }
+}
+
+#[test]
+fn call_expressions_with_spread_and_named_arguments() {
+  let input = "f(1, ...arr, y: 2)";
+
+  let expected = vec![Statement::Expression(Expression::call(
+    Expression::id("f"),
+    vec![
+      Expression::Integer(1),
+      Expression::spread(Expression::id("arr")),
+      Expression::named("y", Expression::Integer(2)),
+    ],
+  ))];
+
+  assert_eq!(parse(input), expected)
+}
+
+#[test]
+fn array_spread() {
+  let input = "[...a, 1]";
+
+  let expected = vec![Statement::Expression(Expression::Array(vec![
+    Expression::spread(Expression::id("a")),
+    Expression::Integer(1),
+  ]))];
+
+  assert_eq!(parse(input), expected)
+}
+
 #[test]
 fn if_expressions() {
   let input = "if x > y return x; else return y;
@@ -392,24 +534,16 @@ fn if_expressions() {
 
   let expected = vec![
     Statement::Expression(Expression::conditional(
-      Expression::infix(
-        ">",
-        Expression::Id("x".to_owned()),
-        Expression::Id("y".to_owned()),
-      ),
-      Statement::Return(Expression::Id("x".to_owned())),
-      Some(Statement::Return(Expression::Id("y".to_owned()))),
+      Expression::infix(">", Expression::id("x"), Expression::id("y")),
+      Statement::Return(Expression::id("x")),
+      Some(Statement::Return(Expression::id("y"))),
     )),
-    Statement::VariableDeclaration(
-      "result".to_owned(),
+    Statement::variable(
+      "result",
       Expression::conditional(
-        Expression::infix(
-          ">",
-          Expression::Id("x".to_owned()),
-          Expression::Id("y".to_owned()),
-        ),
-        Statement::Expression(Expression::Id("x".to_owned())),
-        Some(Statement::Expression(Expression::Id("y".to_owned()))),
+        Expression::infix(">", Expression::id("x"), Expression::id("y")),
+        Statement::Expression(Expression::id("x")),
+        Some(Statement::Expression(Expression::id("y"))),
       ),
     ),
   ];
@@ -428,27 +562,19 @@ fn if_block() {
 
   let expected = vec![
     Statement::Expression(Expression::conditional(
-      Expression::infix(
-        ">",
-        Expression::Id("x".to_owned()),
-        Expression::Id("y".to_owned()),
-      ),
-      Statement::Block(vec![Statement::Return(Expression::Id("x".to_owned()))]),
-      Some(Statement::Block(vec![Statement::Return(Expression::Id(
-        "y".to_owned(),
+      Expression::infix(">", Expression::id("x"), Expression::id("y")),
+      Statement::Block(vec![Statement::Return(Expression::id("x"))]),
+      Some(Statement::Block(vec![Statement::Return(Expression::id(
+        "y",
       ))])),
     )),
-    Statement::VariableDeclaration(
-      "result".to_owned(),
+    Statement::variable(
+      "result",
       Expression::conditional(
-        Expression::infix(
-          ">",
-          Expression::Id("x".to_owned()),
-          Expression::Id("y".to_owned()),
-        ),
-        Statement::Block(vec![Statement::Expression(Expression::Id("x".to_owned()))]),
+        Expression::infix(">", Expression::id("x"), Expression::id("y")),
+        Statement::Block(vec![Statement::Expression(Expression::id("x"))]),
         Some(Statement::Block(vec![Statement::Expression(
-          Expression::Id("y".to_owned()),
+          Expression::id("y"),
         )])),
       ),
     ),
@@ -457,6 +583,90 @@ fn if_block() {
   compare(parse(input), expected)
 }
 
+#[test]
+fn match_expressions() {
+  let input = "match x {
+      0 => 'zero',
+      1 | -1 => 'one',
+      [first, ...rest] => first,
+      {type: 'user', name} => name,
+      n if n > 10 => { n }
+      _ => null
+    }";
+
+  let expected = vec![Statement::Expression(Expression::r#match(
+    Expression::id("x"),
+    vec![
+      MatchArm::new(
+        Pattern::Literal(Expression::Integer(0)),
+        None,
+        Statement::Expression(Expression::String("zero".to_owned())),
+      ),
+      MatchArm::new(
+        Pattern::Or(vec![
+          Pattern::Literal(Expression::Integer(1)),
+          Pattern::Literal(Expression::Integer(-1)),
+        ]),
+        None,
+        Statement::Expression(Expression::String("one".to_owned())),
+      ),
+      MatchArm::new(
+        Pattern::Array(
+          vec![Pattern::Binding("first".to_owned())],
+          Some("rest".to_owned()),
+        ),
+        None,
+        Statement::Expression(Expression::id("first")),
+      ),
+      MatchArm::new(
+        Pattern::Hash(vec![
+          (
+            "type".to_owned(),
+            Pattern::Literal(Expression::String("user".to_owned())),
+          ),
+          ("name".to_owned(), Pattern::Binding("name".to_owned())),
+        ]),
+        None,
+        Statement::Expression(Expression::id("name")),
+      ),
+      MatchArm::new(
+        Pattern::Binding("n".to_owned()),
+        Some(Expression::infix(
+          ">",
+          Expression::id("n"),
+          Expression::Integer(10),
+        )),
+        Statement::Block(vec![Statement::Expression(Expression::id("n"))]),
+      ),
+      MatchArm::new(
+        Pattern::Wildcard,
+        None,
+        Statement::Expression(Expression::Null),
+      ),
+    ],
+  ))];
+
+  let mut parser = Parser::new(lex(input));
+  let program = without_spans(parser.parse());
+
+  assert!(parser.errors.is_empty());
+  assert!(parser.warnings.is_empty());
+  assert_eq!(program, expected)
+}
+
+#[test]
+fn match_without_wildcard_warns() {
+  let mut parser = Parser::new(lex(
+    "let y = 1\nlet z = match x { 0 => 1, n if n > 0 => 2 }",
+  ));
+  parser.parse();
+
+  assert!(parser.errors.is_empty());
+  assert_eq!(parser.warnings.len(), 1);
+  let location = parser.warnings[0].location().unwrap();
+  assert_eq!((location.line, location.column, location.end), (2, 9, 23))
+}
+
 #[test]
 fn semicolons() {
   compare(parse("1 + 1;"), parse("1 + 1"))
@@ -472,8 +682,8 @@ fn boolean_expressions() {
   let expected = vec![
     Statement::Expression(Expression::TRUE),
     Statement::Expression(Expression::FALSE),
-    Statement::VariableDeclaration("foobar".to_owned(), Expression::TRUE),
-    Statement::VariableDeclaration("barfoo".to_owned(), Expression::FALSE),
+    Statement::variable("foobar", Expression::TRUE),
+    Statement::variable("barfoo", Expression::FALSE),
   ];
   compare(parse(input), expected)
 }
@@ -507,7 +717,36 @@ fn precedence() {
   }
 }
 
+#[test]
+fn property_access_and_method_calls() {
+  let input = "player.position.x = -player.speed; player.move(1, 2)[0]";
+
+  let expected = vec![
+    Statement::Expression(Expression::infix(
+      "=",
+      Expression::property(
+        Expression::property(Expression::id("player"), "position"),
+        "x",
+      ),
+      Expression::prefix("-", Expression::property(Expression::id("player"), "speed")),
+    )),
+    Statement::Expression(Expression::index(
+      Expression::call(
+        Expression::property(Expression::id("player"), "move"),
+        vec![Expression::Integer(1), Expression::Integer(2)],
+      ),
+      Expression::Integer(0),
+    )),
+  ];
+
+  assert_eq!(parse(input), expected)
+}
+
 fn parse(input: &str) -> Vec<Statement> {
+  without_spans(parse_with_spans(input))
+}
+
+fn parse_with_spans(input: &str) -> Vec<Statement> {
   let mut parser = Parser::new(lex(input));
   let program = parser.parse();
 
@@ -522,3 +761,305 @@ fn compare(program: Vec<Statement>, expected: Vec<Statement>) {
     assert_eq!(a, b)
   }
 }
+
+#[test]
+fn detects_incomplete_input() {
+  for input in [
+    "fn add(a, b) {",
+    "let x = [1, 2,",
+    "match x { 1 => 2",
+    "1 +",
+    "while x < 3",
+  ] {
+    let mut parser = Parser::new(lex(input));
+    parser.parse();
+    assert!(parser.is_incomplete(), "{}", input);
+  }
+
+  for input in [
+    "fn add(a, b) { a + b }",
+    "let x = )",
+    "1 + 2",
+    "let x = ) + [1,",
+    "match 1 { 1 => }",
+  ] {
+    let mut parser = Parser::new(lex(input));
+    parser.parse();
+    assert!(!parser.is_incomplete(), "{}", input);
+  }
+}
+
+#[test]
+fn comments_are_skipped() {
+  let input = "// setup\nlet x = [1, // one\n 2]\n\nx";
+
+  let program = parse(input);
+
+  let expected = vec![
+    Statement::variable(
+      "x",
+      Expression::Array(vec![Expression::Integer(1), Expression::Integer(2)]),
+    ),
+    Statement::Expression(Expression::id("x")),
+  ];
+
+  compare(program, expected)
+}
+
+#[test]
+fn keeps_comments_as_trivia() {
+  let input = "// setup\nlet x = 1 // one\n\n\nx";
+
+  let mut parser = Parser::new(lex(input)).keep_comments();
+  let program = without_spans(parser.parse());
+
+  let expected = vec![
+    Statement::Trivia(Trivia::Comment("// setup".to_owned())),
+    Statement::variable("x", Expression::Integer(1)),
+    Statement::Trivia(Trivia::TrailingComment("// one".to_owned())),
+    Statement::Trivia(Trivia::BlankLine),
+    Statement::Expression(Expression::id("x")),
+  ];
+
+  assert_eq!(program, expected)
+}
+
+fn parse_with_errors(input: &str) -> (Vec<Statement>, Vec<(usize, usize)>) {
+  let mut parser = Parser::new(lex(input));
+  let program = without_spans(parser.parse());
+  let errors = parser
+    .errors
+    .iter()
+    .map(|err| {
+      let span = err.location().unwrap();
+      (span.line, span.column)
+    })
+    .collect();
+  (program, errors)
+}
+
+#[test]
+fn recovers_at_statement_boundaries() {
+  let (program, errors) =
+    parse_with_errors("let = 1\nlet x = 2\nlet y = )\nprint(x); let z = ]; z");
+
+  assert_eq!(errors, vec![(1, 5), (3, 9), (4, 19)]);
+  assert!(matches!(program[0], Statement::Error(..)));
+  assert_eq!(program[1], Statement::variable("x", Expression::Integer(2)));
+  assert!(matches!(program[2], Statement::Error(..)));
+  assert_eq!(
+    program[3],
+    Statement::Expression(Expression::call(
+      Expression::id("print"),
+      vec![Expression::id("x")]
+    ))
+  );
+  assert!(matches!(program[4], Statement::Error(..)));
+  assert_eq!(program[5], Statement::Expression(Expression::id("z")));
+}
+
+#[test]
+fn recovers_inside_blocks() {
+  let (program, errors) = parse_with_errors("fn f() {\n  let = 1\n  return 2\n}\nf()");
+
+  assert_eq!(errors, vec![(2, 7)]);
+  assert_eq!(program.len(), 2);
+  match &program[0] {
+    Statement::Expression(Expression::Function(_, _, body, _)) => match &**body {
+      Statement::Block(statements) => {
+        assert!(matches!(statements[0], Statement::Error(..)));
+        assert_eq!(statements[1], Statement::Return(Expression::Integer(2)));
+      }
+      statement => panic!("expected a block, got {:?}", statement),
+    },
+    statement => panic!("expected a function, got {:?}", statement),
+  }
+}
+
+#[test]
+fn reports_each_syntax_error_once() {
+  let inputs = vec![
+    ("let h = { a: , b: 2 }\nlet z = 1", vec![(1, 14)], 2),
+    ("let h = {\n  a: ,\n  b: 2\n}\nlet z = 1", vec![(2, 6)], 2),
+    ("}\nlet x = 1", vec![(1, 1)], 2),
+    ("fn f() {\n  fn g() {\n", vec![(3, 1)], 1),
+    (") ) )\n1", vec![(1, 1)], 2),
+  ];
+
+  for (input, expected, statements) in inputs {
+    let (program, errors) = parse_with_errors(input);
+    assert_eq!(errors, expected, "errors in {:?}", input);
+    assert_eq!(program.len(), statements, "statements in {:?}", input);
+  }
+}
+
+fn parse_with_warnings(input: &str) -> (Vec<Statement>, Vec<(usize, usize)>) {
+  let mut parser = Parser::new(lex(input));
+  let program = without_spans(parser.parse());
+  assert!(parser.errors.is_empty(), "errors in {:?}", input);
+  let warnings = parser
+    .warnings
+    .iter()
+    .map(|warning| {
+      let span = warning.location().unwrap();
+      (span.line, span.column)
+    })
+    .collect();
+  (program, warnings)
+}
+
+#[test]
+fn newlines_end_statements_before_ambiguous_lines() {
+  let inputs = vec![
+    (
+      "let a = b\n(c)",
+      vec![
+        Statement::variable("a", Expression::id("b")),
+        Statement::Expression(Expression::id("c")),
+      ],
+      vec![(2, 1)],
+    ),
+    (
+      "let a = b\n  [1]",
+      vec![
+        Statement::variable("a", Expression::id("b")),
+        Statement::Expression(Expression::Array(vec![Expression::Integer(1)])),
+      ],
+      vec![(2, 3)],
+    ),
+    (
+      "let a = b\n-x",
+      vec![
+        Statement::variable("a", Expression::id("b")),
+        Statement::Expression(Expression::prefix("-", Expression::id("x"))),
+      ],
+      vec![(2, 1)],
+    ),
+    (
+      "let f = |x| x\n(f)(1)",
+      vec![
+        Statement::variable(
+          "f",
+          Expression::function(
+            None,
+            vec!["x".to_owned()],
+            Statement::Expression(Expression::id("x")),
+          ),
+        ),
+        Statement::Expression(Expression::call(
+          Expression::id("f"),
+          vec![Expression::Integer(1)],
+        )),
+      ],
+      vec![(2, 1)],
+    ),
+    (
+      "a\n!b",
+      vec![
+        Statement::Expression(Expression::id("a")),
+        Statement::Expression(Expression::prefix("!", Expression::id("b"))),
+      ],
+      vec![],
+    ),
+    (
+      "let a = b;\n(c)",
+      vec![
+        Statement::variable("a", Expression::id("b")),
+        Statement::Expression(Expression::id("c")),
+      ],
+      vec![],
+    ),
+  ];
+
+  for (input, expected, warnings) in inputs {
+    assert_eq!(
+      parse_with_warnings(input),
+      (expected, warnings),
+      "parsing {:?}",
+      input
+    );
+  }
+}
+
+#[test]
+fn newlines_continue_open_expressions() {
+  let inputs = vec![
+    (
+      "let a = 1 +\n  2",
+      vec![Statement::variable(
+        "a",
+        Expression::infix("+", Expression::Integer(1), Expression::Integer(2)),
+      )],
+    ),
+    (
+      "items\n  .map(f)",
+      vec![Statement::Expression(Expression::call(
+        Expression::property(Expression::id("items"), "map"),
+        vec![Expression::id("f")],
+      ))],
+    ),
+    (
+      "f(\n  a,\n  -b\n)",
+      vec![Statement::Expression(Expression::call(
+        Expression::id("f"),
+        vec![
+          Expression::id("a"),
+          Expression::prefix("-", Expression::id("b")),
+        ],
+      ))],
+    ),
+    (
+      "[1\n  -2]",
+      vec![Statement::Expression(Expression::Array(vec![
+        Expression::infix("-", Expression::Integer(1), Expression::Integer(2)),
+      ]))],
+    ),
+    (
+      "f(|x| {\n  let y = x\n  (y)\n})",
+      vec![Statement::Expression(Expression::call(
+        Expression::id("f"),
+        vec![Expression::function(
+          None,
+          vec!["x".to_owned()],
+          Statement::Block(vec![
+            Statement::variable("y", Expression::id("x")),
+            Statement::Expression(Expression::id("y")),
+          ]),
+        )],
+      ))],
+    ),
+  ];
+
+  for (input, expected) in inputs {
+    let (program, _) = parse_with_warnings(input);
+    assert_eq!(program, expected, "parsing {:?}", input);
+  }
+}
+
+#[test]
+fn return_values_must_start_on_the_same_line() {
+  let (program, warnings) = parse_with_warnings("fn f() {\n  return\n  1\n}\nfn g() { return }");
+
+  assert_eq!(warnings, vec![(3, 3)]);
+  let mut parser = Parser::new(lex("return\n1"));
+  parser.parse();
+  assert!(parser.warnings[0].message().contains("after 'return'"));
+  assert_eq!(
+    program,
+    vec![
+      Statement::Expression(Expression::function(
+        Some("f"),
+        vec![],
+        Statement::Block(vec![
+          Statement::Return(Expression::Null),
+          Statement::Expression(Expression::Integer(1)),
+        ]),
+      )),
+      Statement::Expression(Expression::function(
+        Some("g"),
+        vec![],
+        Statement::Block(vec![Statement::Return(Expression::Null)]),
+      )),
+    ]
+  );
+}
@@ -0,0 +1,151 @@
+use crate::{
+  ast::Statement,
+  error::Error,
+  helium::{parse, parse_with_warnings},
+  lexer::lex,
+  object::Object,
+  optimizer::optimize,
+  visitor::Visitor,
+};
+use std::{fs, time::Instant};
+
+const HELP: &str = "Commands:
+  :help          show this message
+  :env           list the bindings in the global environment
+  :type <expr>   show the type of an expression
+  :ast <expr>    show the parsed statements
+  :tokens <expr> show the lexed tokens
+  :load <file>   run a file in the current environment
+  :reset         start over with a fresh environment
+  :time <expr>   evaluate an expression and show how long it took
+  :quit          exit the repl";
+
+#[derive(Debug, PartialEq)]
+pub enum Command<'a> {
+  Help,
+  Env,
+  Type(&'a str),
+  Ast(&'a str),
+  Tokens(&'a str),
+  Load(&'a str),
+  Reset,
+  Time(&'a str),
+  Quit,
+}
+
+impl<'a> Command<'a> {
+  pub fn parse(line: &'a str) -> Result<Self, String> {
+    let line = line.trim();
+    let (name, argument) = match line.find(char::is_whitespace) {
+      Some(i) => (&line[..i], line[i..].trim()),
+      None => (line, ""),
+    };
+
+    let command = match name {
+      ":help" | ":h" => Command::Help,
+      ":env" => Command::Env,
+      ":type" => Command::Type(argument),
+      ":ast" => Command::Ast(argument),
+      ":tokens" => Command::Tokens(argument),
+      ":load" => Command::Load(argument),
+      ":reset" => Command::Reset,
+      ":time" => Command::Time(argument),
+      ":quit" | ":q" => Command::Quit,
+      _ => return Err(format!("unknown command '{}', try :help", name)),
+    };
+
+    match command {
+      Command::Type("") | Command::Ast("") | Command::Tokens("") | Command::Time("") => {
+        Err(format!("usage: {} <expr>", name))
+      }
+      Command::Load("") => Err("usage: :load <file>".to_owned()),
+      command => Ok(command),
+    }
+  }
+
+  pub fn execute(&self, visitor: &Visitor) -> String {
+    match self {
+      Command::Help => HELP.to_owned(),
+      Command::Env => env(visitor),
+      Command::Type(input) => match evaluate(visitor, input) {
+        Ok(obj) => obj.type_name().to_owned(),
+        Err(errors) => show_errors(errors),
+      },
+      Command::Ast(input) => match parse(input) {
+        Ok(program) => format!("{:#?}", program),
+        Err(errors) => show_errors(errors),
+      },
+      Command::Tokens(input) => tokens(input),
+      Command::Load(path) => match fs::read_to_string(path) {
+        Ok(source) => load(visitor, path, &source),
+        Err(err) => Error::io(path, err).to_string(),
+      },
+      Command::Time(input) => {
+        let start = Instant::now();
+        let result = evaluate(visitor, input);
+        let elapsed = start.elapsed();
+        match result {
+          Ok(obj) => format!("{}\n({:?})", obj, elapsed),
+          Err(errors) => show_errors(errors),
+        }
+      }
+      Command::Reset | Command::Quit => String::new(),
+    }
+  }
+}
+
+fn evaluate(visitor: &Visitor, input: &str) -> Result<Object, Vec<Error>> {
+  run(visitor, parse(input)?).map_err(|err| vec![err])
+}
+
+// A Ctrl-C that lands after the previous evaluation's last cancellation
+// check would otherwise cancel the next line, so every run starts afresh.
+pub(super) fn run(visitor: &Visitor, program: Vec<Statement>) -> Result<Object, Error> {
+  visitor.limits().cancel_handle().reset();
+  visitor.visit(&optimize(program))
+}
+
+fn load(visitor: &Visitor, path: &str, source: &str) -> String {
+  let (program, warnings) = match parse_with_warnings(source) {
+    Ok(parsed) => parsed,
+    Err(errors) => return show_errors(errors),
+  };
+  let mut lines: Vec<String> = warnings.iter().map(ToString::to_string).collect();
+  lines.push(match run(visitor, program) {
+    Ok(..) => format!("loaded {}", path),
+    Err(err) => err.to_string(),
+  });
+  lines.join("\n")
+}
+
+fn env(visitor: &Visitor) -> String {
+  visitor
+    .env
+    .names()
+    .into_iter()
+    .filter(|name| !visitor.env.is_protected(name))
+    .filter_map(|name| {
+      let value = visitor.env.get(&name)?;
+      Some(format!("{}: {} = {}", name, value.type_name(), value))
+    })
+    .collect::<Vec<_>>()
+    .join("\n")
+}
+
+fn tokens(input: &str) -> String {
+  let mut lexer = lex(input);
+  let mut lines = vec![];
+  while let Some(token) = lexer.next() {
+    let span = lexer.span();
+    lines.push(format!("{}..{}\t{:?}", span.start, span.end, token));
+  }
+  lines.join("\n")
+}
+
+fn show_errors(errors: Vec<Error>) -> String {
+  errors
+    .iter()
+    .map(|err| err.to_string())
+    .collect::<Vec<_>>()
+    .join("\n")
+}
@@ -0,0 +1,54 @@
+use crate::env::Env;
+use rustyline::{
+  completion::Completer, highlight::Highlighter, hint::Hinter, validate::Validator, Context, Helper,
+};
+
+pub struct ReplHelper {
+  env: Env,
+}
+
+impl ReplHelper {
+  pub fn new(env: Env) -> Self {
+    ReplHelper { env }
+  }
+}
+
+impl Completer for ReplHelper {
+  type Candidate = String;
+
+  fn complete(
+    &self,
+    line: &str,
+    pos: usize,
+    _: &Context<'_>,
+  ) -> rustyline::Result<(usize, Vec<String>)> {
+    Ok(complete_identifier(&self.env, line, pos))
+  }
+}
+
+impl Hinter for ReplHelper {
+  type Hint = String;
+}
+
+impl Highlighter for ReplHelper {}
+
+impl Validator for ReplHelper {}
+
+impl Helper for ReplHelper {}
+
+pub fn complete_identifier(env: &Env, line: &str, pos: usize) -> (usize, Vec<String>) {
+  let start = line[..pos]
+    .trim_end_matches(|c: char| c.is_alphanumeric() || c == '_')
+    .len();
+  let prefix = &line[start..pos];
+  if prefix.is_empty() {
+    return (pos, vec![]);
+  }
+
+  let names = env
+    .names()
+    .into_iter()
+    .filter(|name| name.starts_with(prefix))
+    .collect();
+  (start, names)
+}
@@ -0,0 +1,7 @@
+mod command;
+mod helper;
+mod repl;
+#[cfg(test)]
+mod test;
+
+pub use repl::repl;
@@ -0,0 +1,127 @@
+use super::{
+  command::{self, Command},
+  helper::ReplHelper,
+};
+use crate::{lexer::lex, parser::Parser, visitor::Visitor};
+use rustyline::{error::ReadlineError, history::DefaultHistory, Editor};
+use signal_hook::{consts::SIGINT, flag, low_level::unregister, SigId};
+use std::{env, path::PathBuf};
+
+const PROMPT: &str = ">> ";
+const CONTINUATION_PROMPT: &str = ".. ";
+
+pub fn repl() {
+  print_welcome();
+  let mut visitor = Visitor::new();
+  let mut editor: Editor<ReplHelper, DefaultHistory> = match Editor::new() {
+    Ok(editor) => editor,
+    Err(err) => {
+      eprintln!("Failed to start the repl: {}", err);
+      return;
+    }
+  };
+  editor.set_helper(Some(ReplHelper::new(visitor.env.clone())));
+  let mut interrupt = cancel_on_interrupt(&visitor);
+
+  let history = history_path();
+  if let Some(path) = &history {
+    let _ = editor.load_history(path);
+  }
+
+  let mut input = String::new();
+  loop {
+    let prompt = if input.is_empty() {
+      PROMPT
+    } else {
+      CONTINUATION_PROMPT
+    };
+    match editor.readline(prompt) {
+      Ok(line) if input.is_empty() && line.trim_start().starts_with(':') => {
+        let _ = editor.add_history_entry(line.trim());
+        match Command::parse(&line) {
+          Ok(Command::Quit) => break,
+          Ok(Command::Reset) => {
+            visitor = Visitor::new();
+            editor.set_helper(Some(ReplHelper::new(visitor.env.clone())));
+            if let Some(id) = interrupt {
+              unregister(id);
+            }
+            interrupt = cancel_on_interrupt(&visitor);
+          }
+          Ok(command) => println!("{}", command.execute(&visitor)),
+          Err(message) => println!("{}", message),
+        }
+        continue;
+      }
+      Ok(line) => {
+        input.push_str(&line);
+        input.push('\n');
+      }
+      Err(ReadlineError::Interrupted) => {
+        input.clear();
+        continue;
+      }
+      Err(ReadlineError::Eof) => break,
+      Err(err) => {
+        eprintln!("{}", err);
+        break;
+      }
+    }
+
+    let source = std::mem::take(&mut input);
+    let mut parser = Parser::new(lex(&source));
+    let program = parser.parse();
+    if parser.is_incomplete() {
+      input = source;
+      continue;
+    }
+    let _ = editor.add_history_entry(source.trim_end());
+
+    for warning in &parser.warnings {
+      println!("{}", warning);
+    }
+
+    if parser.errors.is_empty() {
+      match command::run(&visitor, program) {
+        Ok(obj) => println!("{}", obj),
+        Err(err) => println!("{}", err),
+      }
+    } else {
+      for err in parser.errors {
+        println!("{}", err)
+      }
+    }
+  }
+
+  if let Some(path) = &history {
+    let _ = editor.save_history(path);
+  }
+}
+
+// While a line is being read the editor handles Ctrl-C itself; while code
+// runs it cancels the evaluation instead of killing the repl.
+fn cancel_on_interrupt(visitor: &Visitor) -> Option<SigId> {
+  flag::register(SIGINT, visitor.limits().cancel_handle().flag()).ok()
+}
+
+fn history_path() -> Option<PathBuf> {
+  env::var_os("HOME").map(|home| PathBuf::from(home).join(".helium_history"))
+}
+
+fn print_welcome() {
+  println!(
+    "+-------------------+
+| 2                 |
+|    _    _         |
+|   | |  | |        |
+|   | |__| | ___    |
+|   |  __  |/ _ \\   |
+|   | |  | |  __/   |
+|   |_|  |_|\\___|   |
+|                   |
+|       4.003       |
++-------------------+"
+  );
+  println!("Welcome to the Helium repl!");
+  println!("Feel free to type in commands, or :help for a list of repl commands");
+}
@@ -0,0 +1,84 @@
+use super::{
+  command::{self, Command},
+  helper::complete_identifier,
+};
+use crate::{env, helium::parse, object::Object, visitor::Visitor};
+
+#[test]
+fn completes_identifiers_from_the_env() {
+  let env = env::global();
+  env.define("filtered", Object::Null).unwrap();
+
+  assert_eq!(
+    complete_identifier(&env, "map(fil", 7),
+    (4, vec!["filter".to_owned(), "filtered".to_owned()])
+  );
+  assert_eq!(complete_identifier(&env, "1 + ", 4), (4, vec![]));
+}
+
+#[test]
+fn parses_commands() {
+  assert_eq!(Command::parse(":help"), Ok(Command::Help));
+  assert_eq!(Command::parse(" :type  1 + 2 "), Ok(Command::Type("1 + 2")));
+  assert_eq!(Command::parse(":load lib.he"), Ok(Command::Load("lib.he")));
+  assert_eq!(Command::parse(":q"), Ok(Command::Quit));
+  assert!(Command::parse(":ast").is_err());
+  assert!(Command::parse(":nope").is_err());
+}
+
+#[test]
+fn inspection_commands() {
+  let visitor = Visitor::new();
+  visitor
+    .visit(&parse("let answer = 42; fn id(x) { x }").unwrap())
+    .unwrap();
+
+  assert_eq!(
+    Command::Env.execute(&visitor),
+    "answer: integer = 42\nid: function = fn(x)"
+  );
+  assert_eq!(Command::Type("answer / 2").execute(&visitor), "integer");
+  assert_eq!(Command::Type("[answer]").execute(&visitor), "array");
+  assert_eq!(
+    Command::Tokens("id(1)").execute(&visitor),
+    "0..2\tId(\"id\")\n2..3\tLeftParen\n3..4\tInteger(1)\n4..5\tRightParen"
+  );
+  assert!(Command::Ast("1 + 2")
+    .execute(&visitor)
+    .starts_with("[\n    Expression(\n        Infix("));
+  assert!(Command::Time("id(answer)")
+    .execute(&visitor)
+    .starts_with("42\n("));
+}
+
+#[test]
+fn load_runs_files_in_the_current_env() {
+  let path = std::env::temp_dir().join(format!("helium-repl-{}.he", std::process::id()));
+  std::fs::write(&path, "let loaded = 'yes'").unwrap();
+  let visitor = Visitor::new();
+
+  let output = Command::Load(path.to_str().unwrap()).execute(&visitor);
+  assert!(output.starts_with("loaded"));
+  assert_eq!(
+    visitor.env.get("loaded"),
+    Some(Object::String("yes".to_owned()))
+  );
+  std::fs::remove_file(path).unwrap();
+
+  let output = Command::Load("missing.he").execute(&visitor);
+  assert!(output.starts_with("IoError"), "{}", output);
+}
+
+#[test]
+fn stale_interrupts_do_not_cancel_the_next_line() {
+  let visitor = Visitor::new();
+  visitor.limits().cancel_handle().cancel();
+
+  let input = "let i = 0; while i < 5000 { i = i + 1 }; i";
+  assert_eq!(
+    command::run(&visitor, parse(input).unwrap()),
+    Ok(Object::Integer(5000))
+  );
+  visitor.limits().cancel_handle().cancel();
+  assert_eq!(Command::Type(input).execute(&visitor), "integer");
+}
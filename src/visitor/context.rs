@@ -0,0 +1,47 @@
+use super::Visitor;
+use crate::{ast::Span, env::Env, error::Error, object::Object};
+
+// Builtins check the size of what they are about to allocate against this
+// even when no collection limit is configured, so absurd sizes fail with an
+// error instead of aborting the process.
+const MAX_ALLOCATION: usize = 1 << 30;
+
+pub struct Context<'a> {
+  visitor: &'a Visitor,
+  name: &'a str,
+  span: Span,
+}
+
+impl<'a> Context<'a> {
+  pub(super) fn new(visitor: &'a Visitor, name: &'a str, span: Span) -> Self {
+    Context {
+      visitor,
+      name,
+      span,
+    }
+  }
+
+  pub fn call(&self, function: Object, args: Vec<Object>) -> Result<Object, Error> {
+    self.visitor.call_at(function, args, self.span)
+  }
+
+  pub fn env(&self) -> &Env {
+    &self.visitor.env
+  }
+
+  pub fn name(&self) -> &str {
+    self.name
+  }
+
+  pub fn check_size(&self, size: usize) -> Result<(), Error> {
+    self.visitor.limits().check_size(size)?;
+    if size > MAX_ALLOCATION {
+      return Err(Error::collection_too_large(size, MAX_ALLOCATION));
+    }
+    Ok(())
+  }
+
+  pub fn span(&self) -> Span {
+    self.span
+  }
+}
@@ -0,0 +1,157 @@
+use crate::{error::Error, object::Object};
+use std::{
+  cell::Cell,
+  sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+  },
+  time::{Duration, Instant},
+};
+
+pub const DEFAULT_MAX_DEPTH: usize = 10_000;
+const CHECK_INTERVAL: u64 = 1024;
+
+pub struct Limits {
+  depth: Cell<usize>,
+  max_depth: Cell<usize>,
+  steps: Cell<u64>,
+  max_steps: Cell<Option<u64>>,
+  max_collection_size: Cell<Option<usize>>,
+  timeout: Cell<Option<Duration>>,
+  deadline: Cell<Option<Instant>>,
+  cancelled: Arc<AtomicBool>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CancelHandle(Arc<AtomicBool>);
+
+impl CancelHandle {
+  pub fn cancel(&self) {
+    self.0.store(true, Ordering::Relaxed)
+  }
+
+  #[cfg(feature = "repl")]
+  pub(crate) fn flag(&self) -> Arc<AtomicBool> {
+    self.0.clone()
+  }
+
+  #[cfg(feature = "repl")]
+  pub(crate) fn reset(&self) {
+    self.0.store(false, Ordering::Relaxed)
+  }
+}
+
+impl Default for Limits {
+  fn default() -> Self {
+    Limits {
+      depth: Cell::new(0),
+      max_depth: Cell::new(DEFAULT_MAX_DEPTH),
+      steps: Cell::new(0),
+      max_steps: Cell::new(None),
+      max_collection_size: Cell::new(None),
+      timeout: Cell::new(None),
+      deadline: Cell::new(None),
+      cancelled: Arc::new(AtomicBool::new(false)),
+    }
+  }
+}
+
+impl Limits {
+  pub fn set_max_depth(&self, max_depth: usize) {
+    self.max_depth.set(max_depth)
+  }
+
+  pub fn set_max_steps(&self, max_steps: u64) {
+    self.max_steps.set(Some(max_steps))
+  }
+
+  pub fn set_max_collection_size(&self, max_size: usize) {
+    self.max_collection_size.set(Some(max_size))
+  }
+
+  pub fn set_timeout(&self, timeout: Duration) {
+    self.timeout.set(Some(timeout))
+  }
+
+  pub fn cancel_handle(&self) -> CancelHandle {
+    CancelHandle(self.cancelled.clone())
+  }
+
+  pub fn start(&self) {
+    self.steps.set(0);
+    self
+      .deadline
+      .set(self.timeout.get().map(|timeout| Instant::now() + timeout));
+  }
+
+  pub(super) fn enter(&self) -> Result<usize, Error> {
+    let depth = self.depth.get();
+    if depth >= self.max_depth.get() {
+      return Err(Error::stack_overflow(self.max_depth.get()));
+    }
+    self.depth.set(depth + 1);
+    Ok(depth)
+  }
+
+  pub(super) fn leave(&self, depth: usize) {
+    self.depth.set(depth)
+  }
+
+  pub(super) fn step(&self) -> Result<(), Error> {
+    let steps = self.steps.get() + 1;
+    self.steps.set(steps);
+    if let Some(max_steps) = self.max_steps.get() {
+      if steps > max_steps {
+        return Err(Error::step_limit(max_steps));
+      }
+    }
+
+    if steps.is_multiple_of(CHECK_INTERVAL) {
+      if self.cancelled.swap(false, Ordering::Relaxed) {
+        return Err(Error::cancelled());
+      }
+      if let (Some(deadline), Some(timeout)) = (self.deadline.get(), self.timeout.get()) {
+        if Instant::now() >= deadline {
+          return Err(Error::timed_out(timeout));
+        }
+      }
+    }
+    Ok(())
+  }
+
+  pub fn check_size(&self, size: usize) -> Result<(), Error> {
+    match self.max_collection_size.get() {
+      Some(max_size) if size > max_size => Err(Error::collection_too_large(size, max_size)),
+      _ => Ok(()),
+    }
+  }
+
+  // Nested collections can share one another, so `[a, a]` doubles in size
+  // without any single array growing; count every element reachable from
+  // the result instead of just its own length.
+  pub(super) fn check_object(&self, object: Object) -> Result<Object, Error> {
+    match (&object, self.max_collection_size.get()) {
+      (Object::String(string), _) => self.check_size(string.len())?,
+      (_, Some(max_size)) => self.check_size(total_size(&object, max_size))?,
+      _ => (),
+    }
+    Ok(object)
+  }
+}
+
+// Counts the elements of every collection nested in `object`, stopping once
+// the count passes `max_size`.
+fn total_size(object: &Object, max_size: usize) -> usize {
+  let nested = |size: usize, item: &Object| {
+    if size > max_size {
+      size
+    } else {
+      size.saturating_add(total_size(item, max_size - size))
+    }
+  };
+  match object {
+    Object::Array(array) => array.iter().fold(array.len(), nested),
+    Object::Hash(hash) => hash.values().fold(hash.len(), nested),
+    _ => 0,
+  }
+}
@@ -1,5 +1,9 @@
+mod context;
+mod limits;
 #[cfg(test)]
 mod test;
 mod visitor;
 
+pub use context::Context;
+pub use limits::{CancelHandle, Limits, DEFAULT_MAX_DEPTH};
 pub use visitor::Visitor;
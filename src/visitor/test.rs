@@ -1,6 +1,8 @@
 use super::*;
 use crate::{
-  ast::{Expression, Statement},
+  ast::{Expression, MatchArm, Parameter, Pattern, Statement},
+  error::ErrorType,
+  helium::parse,
   object::Object,
 };
 use std::{collections::HashMap, rc::Rc};
@@ -8,15 +10,15 @@ use std::{collections::HashMap, rc::Rc};
 #[test]
 fn visit_hash_index() {
   let input = vec![
-    Statement::VariableDeclaration(
-      "x".to_owned(),
+    Statement::variable(
+      "x",
       Expression::Hash(vec![(
         Expression::String("leonardo".to_owned()),
         Expression::String("gurgel".to_owned()),
       )]),
     ),
     Statement::Expression(Expression::index(
-      Expression::Id("x".to_owned()),
+      Expression::id("x"),
       Expression::String("leonardo".to_owned()),
     )),
   ];
@@ -29,8 +31,8 @@ fn visit_hash_index() {
 #[test]
 fn visit_hash() {
   let input = vec![
-    Statement::VariableDeclaration(
-      "x".to_owned(),
+    Statement::variable(
+      "x",
       Expression::Hash(vec![
         (
           Expression::String("leonardo".to_owned()),
@@ -39,7 +41,7 @@ fn visit_hash() {
         (Expression::Integer(1), Expression::Integer(2)),
       ]),
     ),
-    Statement::Expression(Expression::Id("x".to_owned())),
+    Statement::Expression(Expression::id("x")),
   ];
 
   let mut expected = HashMap::new();
@@ -54,16 +56,16 @@ fn visit_hash() {
 #[test]
 fn visit_while_loop() {
   let input = vec![
-    Statement::VariableDeclaration("x".to_owned(), Expression::Integer(0)),
+    Statement::variable("x", Expression::Integer(0)),
     Statement::while_loop(
-      Expression::infix("<", Expression::Id("x".to_owned()), Expression::Integer(10)),
+      Expression::infix("<", Expression::id("x"), Expression::Integer(10)),
       Statement::Expression(Expression::infix(
         "=",
-        Expression::Id("x".to_owned()),
-        Expression::infix("+", Expression::Id("x".to_owned()), Expression::Integer(1)),
+        Expression::id("x"),
+        Expression::infix("+", Expression::id("x"), Expression::Integer(1)),
       )),
     ),
-    Statement::Expression(Expression::Id("x".to_owned())),
+    Statement::Expression(Expression::id("x")),
   ];
 
   let result = visit(input);
@@ -74,7 +76,7 @@ fn visit_while_loop() {
 #[test]
 fn visit_for_loop() {
   let input = vec![
-    Statement::VariableDeclaration("x".to_owned(), Expression::Integer(0)),
+    Statement::variable("x", Expression::Integer(0)),
     Statement::for_loop(
       "i",
       Expression::Array(vec![
@@ -84,11 +86,11 @@ fn visit_for_loop() {
       ]),
       Statement::Expression(Expression::infix(
         "=",
-        Expression::Id("x".to_owned()),
-        Expression::Id("i".to_owned()),
+        Expression::id("x"),
+        Expression::id("i"),
       )),
     ),
-    Statement::Expression(Expression::Id("x".to_owned())),
+    Statement::Expression(Expression::id("x")),
   ];
 
   let result = visit(input);
@@ -99,13 +101,13 @@ fn visit_for_loop() {
 #[test]
 fn visit_reassign() {
   let input = vec![
-    Statement::VariableDeclaration("x".to_owned(), Expression::Integer(0)),
+    Statement::variable("x", Expression::Integer(0)),
     Statement::Expression(Expression::infix(
       "=",
-      Expression::Id("x".to_owned()),
+      Expression::id("x"),
       Expression::Integer(1),
     )),
-    Statement::Expression(Expression::Id("x".to_owned())),
+    Statement::Expression(Expression::id("x")),
   ];
 
   let result = visit(input);
@@ -113,6 +115,121 @@ fn visit_reassign() {
   assert_eq!(result, Object::Integer(1))
 }
 
+#[test]
+fn visit_const_reassign() {
+  let input = vec![
+    Statement::constant("x", Expression::Integer(0)),
+    Statement::Expression(Expression::infix(
+      "=",
+      Expression::id("x"),
+      Expression::Integer(1),
+    )),
+  ];
+
+  let error = Visitor::new().visit(&input).unwrap_err();
+
+  assert_eq!(error.error_type(), &ErrorType::CannotAssign)
+}
+
+#[test]
+fn visit_const_redeclaration() {
+  let input = vec![
+    Statement::constant("x", Expression::Integer(0)),
+    Statement::variable("x", Expression::Integer(1)),
+  ];
+
+  let error = Visitor::new().visit(&input).unwrap_err();
+
+  assert_eq!(error.error_type(), &ErrorType::CannotAssign)
+}
+
+#[test]
+fn visit_const_shadowed_in_block() {
+  let input = vec![
+    Statement::constant("x", Expression::Integer(0)),
+    Statement::Block(vec![
+      Statement::variable("x", Expression::Integer(1)),
+      Statement::Expression(Expression::id("x")),
+    ]),
+  ];
+
+  let result = visit(input);
+
+  assert_eq!(result, Object::Integer(1))
+}
+
+#[test]
+fn visit_undefined_reassign() {
+  let input = vec![Statement::Expression(Expression::infix(
+    "=",
+    Expression::id("x"),
+    Expression::Integer(1),
+  ))];
+
+  let error = Visitor::new().visit(&input).unwrap_err();
+
+  assert_eq!(error.error_type(), &ErrorType::UndefinedVariable)
+}
+
+#[test]
+fn visit_builtin_shadowing() {
+  let input = vec![Statement::variable("len", Expression::Integer(1))];
+
+  let error = Visitor::new().visit(&input).unwrap_err();
+
+  assert_eq!(error.error_type(), &ErrorType::CannotAssign)
+}
+
+#[test]
+fn visit_global_builtin_redefinition() {
+  for input in ["fn len() { 1 }", "for len in [1] { len }", "len = 1"] {
+    let error = Visitor::new().visit(&parse(input).unwrap()).unwrap_err();
+    assert_eq!(error.error_type(), &ErrorType::CannotAssign, "{}", input);
+  }
+}
+
+#[test]
+fn visit_local_builtin_shadowing() {
+  for input in [
+    "fn f(len) { len }; f(1)",
+    "fn f(first, ...rest) { rest[0] }; f(0, 1)",
+    "(|...len| len[0])(1)",
+    "match 1 { len => len }",
+    "match [0, 1] { [x, ...len] => len[0] }",
+    "fn f() { let len = 1; len }; f()",
+    "{ let len = 1; len }",
+  ] {
+    let result = Visitor::new().visit(&parse(input).unwrap());
+    assert_eq!(result, Ok(Object::Integer(1)), "{}", input);
+  }
+}
+
+#[test]
+fn visit_builtin_reassign() {
+  let input = vec![Statement::Expression(Expression::infix(
+    "=",
+    Expression::id("map"),
+    Expression::Integer(1),
+  ))];
+
+  let error = Visitor::new().visit(&input).unwrap_err();
+
+  assert_eq!(error.error_type(), &ErrorType::CannotAssign)
+}
+
+#[test]
+fn visit_allowed_builtin_shadowing() {
+  let input = vec![
+    Statement::variable("len", Expression::Integer(1)),
+    Statement::Expression(Expression::id("len")),
+  ];
+
+  let visitor = Visitor::new();
+  visitor.env.allow_shadowing("len");
+
+  assert_eq!(visitor.visit(&input).unwrap(), Object::Integer(1))
+}
+
 #[test]
 fn visit_out_of_bounds_index() {
   let input = vec![Statement::Expression(Expression::index(
@@ -189,13 +306,10 @@ fn visit_function_doesnt_have_frozen_parent() {
     Statement::Expression(Expression::function(
       Some("print_i"),
       vec![],
-      Statement::Expression(Expression::Id("i".to_owned())),
-    )),
-    Statement::VariableDeclaration("i".to_owned(), Expression::Integer(5)),
-    Statement::Expression(Expression::call(
-      Expression::Id("print_i".to_owned()),
-      vec![],
+      Statement::Expression(Expression::id("i")),
     )),
+    Statement::variable("i", Expression::Integer(5)),
+    Statement::Expression(Expression::call(Expression::id("print_i"), vec![])),
   ];
 
   let result = visit(input);
@@ -214,20 +328,17 @@ fn visit_closure() {
         vec!["y".to_owned()],
         Statement::Expression(Expression::infix(
           "+",
-          Expression::Id("x".to_owned()),
-          Expression::Id("y".to_owned()),
+          Expression::id("x"),
+          Expression::id("y"),
         )),
       )),
     )),
-    Statement::VariableDeclaration(
-      "add_two".to_owned(),
-      Expression::call(
-        Expression::Id("adder".to_owned()),
-        vec![Expression::Integer(2)],
-      ),
+    Statement::variable(
+      "add_two",
+      Expression::call(Expression::id("adder"), vec![Expression::Integer(2)]),
     ),
     Statement::Expression(Expression::call(
-      Expression::Id("add_two".to_owned()),
+      Expression::id("add_two"),
       vec![Expression::Integer(3)],
     )),
   ];
@@ -240,16 +351,13 @@ fn visit_closure() {
 #[test]
 fn visit_function_with_outer_scope() {
   let input = vec![
-    Statement::VariableDeclaration("i".to_owned(), Expression::Integer(5)),
+    Statement::variable("i", Expression::Integer(5)),
     Statement::Expression(Expression::function(
       Some("print_i"),
       vec![],
-      Statement::Expression(Expression::Id("i".to_owned())),
-    )),
-    Statement::Expression(Expression::call(
-      Expression::Id("print_i".to_owned()),
-      vec![],
+      Statement::Expression(Expression::id("i")),
     )),
+    Statement::Expression(Expression::call(Expression::id("print_i"), vec![])),
   ];
 
   let result = visit(input);
@@ -263,10 +371,10 @@ fn visit_function_call() {
     Statement::Expression(Expression::function(
       Some("identity"),
       vec!["x".to_owned()],
-      Statement::Expression(Expression::Id("x".to_owned())),
+      Statement::Expression(Expression::id("x")),
     )),
     Statement::Expression(Expression::call(
-      Expression::Id("identity".to_owned()),
+      Expression::id("identity"),
       vec![Expression::Integer(1)],
     )),
   ];
@@ -285,9 +393,10 @@ fn visit_function_declaration() {
 
   let result = visit(input);
   match result {
-    Object::Function(args, block, ..) => {
+    Object::Function(name, args, block, ..) => {
+      assert_eq!(name, Some("name".to_owned()));
       assert_eq!(block, Rc::new(Statement::Expression(Expression::TRUE)));
-      assert_eq!(args, vec![("argv")]);
+      assert_eq!(args, vec![Parameter::new("argv")]);
     }
     _ => panic!("not a function"),
   }
@@ -296,8 +405,8 @@ fn visit_function_declaration() {
 #[test]
 fn visit_integer_variable_declaration() {
   let input = vec![
-    Statement::VariableDeclaration("x".to_owned(), Expression::Integer(5)),
-    Statement::Expression(Expression::Id("x".to_owned())),
+    Statement::variable("x", Expression::Integer(5)),
+    Statement::Expression(Expression::id("x")),
   ];
 
   let result = visit(input);
@@ -308,8 +417,8 @@ fn visit_integer_variable_declaration() {
 #[test]
 fn visit_boolean_variable_declaration() {
   let input = vec![
-    Statement::VariableDeclaration("x".to_owned(), Expression::TRUE),
-    Statement::Expression(Expression::Id("x".to_owned())),
+    Statement::variable("x", Expression::TRUE),
+    Statement::Expression(Expression::id("x")),
   ];
 
   let result = visit(input);
@@ -380,6 +489,131 @@ fn visit_no_else() {
   assert_eq!(result, Object::Null)
 }
 
+#[test]
+fn visit_match_literal() {
+  let input = vec![Statement::Expression(Expression::r#match(
+    Expression::Integer(2),
+    vec![
+      MatchArm::new(
+        Pattern::Literal(Expression::Integer(1)),
+        None,
+        Statement::Expression(Expression::String("one".to_owned())),
+      ),
+      MatchArm::new(
+        Pattern::Or(vec![
+          Pattern::Literal(Expression::Integer(2)),
+          Pattern::Literal(Expression::Integer(3)),
+        ]),
+        None,
+        Statement::Expression(Expression::String("two or three".to_owned())),
+      ),
+    ],
+  ))];
+
+  let result = visit(input);
+
+  assert_eq!(result, Object::String("two or three".to_owned()))
+}
+
+#[test]
+fn visit_match_array_rest() {
+  let input = vec![Statement::Expression(Expression::r#match(
+    Expression::Array(vec![
+      Expression::Integer(1),
+      Expression::Integer(2),
+      Expression::Integer(3),
+    ]),
+    vec![MatchArm::new(
+      Pattern::Array(
+        vec![Pattern::Binding("first".to_owned())],
+        Some("rest".to_owned()),
+      ),
+      None,
+      Statement::Expression(Expression::id("rest")),
+    )],
+  ))];
+
+  let result = visit(input);
+
+  assert_eq!(
+    result,
+    Object::Array(vec![Object::Integer(2), Object::Integer(3)])
+  )
+}
+
+#[test]
+fn visit_match_guard() {
+  let input = vec![Statement::Expression(Expression::r#match(
+    Expression::Integer(5),
+    vec![
+      MatchArm::new(
+        Pattern::Binding("n".to_owned()),
+        Some(Expression::infix(
+          ">",
+          Expression::id("n"),
+          Expression::Integer(10),
+        )),
+        Statement::Expression(Expression::String("big".to_owned())),
+      ),
+      MatchArm::new(
+        Pattern::Wildcard,
+        None,
+        Statement::Expression(Expression::String("small".to_owned())),
+      ),
+    ],
+  ))];
+
+  let result = visit(input);
+
+  assert_eq!(result, Object::String("small".to_owned()))
+}
+
+#[test]
+fn visit_match_without_matching_arm() {
+  let input = vec![Statement::Expression(Expression::r#match(
+    Expression::Integer(5),
+    vec![MatchArm::new(
+      Pattern::Literal(Expression::Integer(1)),
+      None,
+      Statement::Expression(Expression::Integer(1)),
+    )],
+  ))];
+
+  let result = visit(input);
+
+  assert_eq!(result, Object::Null)
+}
+
+#[test]
+fn visit_builtin_with_context() {
+  let input = vec![
+    Statement::variable("x", Expression::Integer(2)),
+    Statement::Expression(Expression::call(
+      Expression::id("apply_to_x"),
+      vec![Expression::function(
+        None,
+        vec!["n".to_owned()],
+        Statement::Expression(Expression::infix(
+          "*",
+          Expression::id("n"),
+          Expression::Integer(10),
+        )),
+      )],
+    )),
+  ];
+
+  let visitor = Visitor::new();
+  visitor.env.set(
+    "apply_to_x",
+    Object::builtin(|context, mut args| {
+      let x = context.env().get("x").unwrap_or(Object::Null);
+      context.call(args.remove(0), vec![x])
+    }),
+  );
+
+  assert_eq!(visitor.visit(&input).unwrap(), Object::Integer(20))
+}
+
 #[test]
 fn visit_infix_plus() {
   let input = vec![Statement::Expression(Expression::infix(
@@ -1,24 +1,66 @@
+use super::{Context, Limits};
 use crate::{
-  ast::{Expression, Statement},
+  ast::{Expression, MatchArm, Parameter, Pattern, Span, Statement},
   env,
   error::Error,
-  object::Object,
+  object::{HostObject, Object},
 };
-use std::{collections::HashMap, rc::Rc};
+use std::{cmp::Ordering, collections::HashMap, rc::Rc};
 
 type Result<T> = std::result::Result<T, Error>;
 
+const STACK_RED_ZONE: usize = 128 * 1024;
+const STACK_GROWTH: usize = 4 * 1024 * 1024;
+
 pub struct Visitor {
   pub env: env::Env,
+  limits: Rc<Limits>,
+}
+
+struct Call {
+  function: Object,
+  name: String,
+  args: Vec<Object>,
+  named: Vec<(String, Object)>,
+  span: Span,
+}
+
+enum Tail {
+  Value(Object),
+  Call(Call),
+}
+
+impl Default for Visitor {
+  fn default() -> Self {
+    Self::new()
+  }
 }
 
 impl Visitor {
   pub fn new() -> Self {
-    Visitor { env: env::global() }
+    Visitor::from(env::global())
   }
 
   pub fn from(env: env::Env) -> Self {
-    Visitor { env }
+    Visitor {
+      env,
+      limits: Rc::new(Limits::default()),
+    }
+  }
+
+  pub fn set_max_depth(&self, max_depth: usize) {
+    self.limits.set_max_depth(max_depth)
+  }
+
+  pub fn limits(&self) -> &Limits {
+    &self.limits
+  }
+
+  fn scoped(&self, env: env::Env) -> Self {
+    Visitor {
+      env,
+      limits: self.limits.clone(),
+    }
   }
 
   pub fn visit(&self, block: &[Statement]) -> Result<Object> {
@@ -34,49 +76,138 @@ impl Visitor {
   }
 
   fn visit_statement(&self, statement: &Statement) -> Result<Object> {
+    self.limits.step()?;
     match statement {
       Statement::Block(block) => self.visit_block(block, env::local(self.env.clone())),
-      Statement::Null => unreachable!(),
-      Statement::WhileLoop(condition, block) => self.visit_while(condition, block),
-      Statement::ForLoop(variable, iterable, block) => self.visit_for(variable, iterable, block),
+      Statement::Null | Statement::Error(..) => unreachable!(),
+      Statement::Trivia(..) => Ok(Object::Null),
+      Statement::WhileLoop(condition, block, _) => self.visit_while(condition, block),
+      Statement::ForLoop(variable, iterable, block, _) => self.visit_for(variable, iterable, block),
       Statement::Expression(expression) => self.visit_expression(expression),
-      Statement::VariableDeclaration(name, expression) => {
-        self.visit_variable_declaration(&name, expression)
+      Statement::VariableDeclaration(name, expression, _) => {
+        self.visit_variable_declaration(name, expression)
+      }
+      Statement::ConstDeclaration(name, expression, _) => {
+        self.visit_const_declaration(name, expression)
+      }
+      Statement::LocalDeclaration(name, expression, slot) => {
+        let value = self.visit_expression(expression)?;
+        self.env.define_slot(*slot, name, value)?;
+        Ok(Object::Null)
+      }
+      Statement::LocalConstDeclaration(name, expression, slot) => {
+        let value = self.visit_expression(expression)?;
+        self.env.define_const_slot(*slot, name, value)?;
+        Ok(Object::Null)
       }
       Statement::Return(expression) => self.visit_return(expression),
     }
   }
 
   fn visit_block(&self, block: &[Statement], env: env::Env) -> Result<Object> {
-    let sub_visitor = Visitor::from(env);
-    sub_visitor.visit(block)
+    let sub_visitor = self.scoped(env);
+    let mut result = Object::Null;
+    for statement in block {
+      result = sub_visitor.visit_statement(statement)?;
+      if let Object::Return(..) = result {
+        break;
+      }
+    }
+
+    Ok(result)
+  }
+
+  fn visit_tail_statement(&self, statement: &Statement) -> Result<Tail> {
+    match statement {
+      Statement::Expression(expression) | Statement::Return(expression) => {
+        self.visit_tail_expression(expression)
+      }
+      Statement::Block(block) => self
+        .scoped(env::local(self.env.clone()))
+        .visit_tail_block(block),
+      statement => Ok(Tail::Value(self.visit_statement(statement)?)),
+    }
+  }
+
+  fn visit_tail_block(&self, block: &[Statement]) -> Result<Tail> {
+    let (last, init) = match block.split_last() {
+      Some(split) => split,
+      None => return Ok(Tail::Value(Object::Null)),
+    };
+
+    for statement in init {
+      let result = self.visit_statement(statement)?;
+      if let Object::Return(..) = result {
+        return Ok(Tail::Value(result));
+      }
+    }
+    self.visit_tail_statement(last)
+  }
+
+  fn visit_tail_expression(&self, expression: &Expression) -> Result<Tail> {
+    match expression {
+      Expression::Call(function, args, span) => {
+        Ok(Tail::Call(self.visit_arguments(function, args, *span)?))
+      }
+      Expression::Conditional(condition, consequence, alternative, _) => {
+        if self.visit_expression(condition)?.is_truthy() {
+          self.visit_tail_statement(consequence)
+        } else {
+          match alternative {
+            Some(statement) => self.visit_tail_statement(statement),
+            None => Ok(Tail::Value(Object::Null)),
+          }
+        }
+      }
+      Expression::Match(value, arms) => match self.select_arm(value, arms)? {
+        Some((arm_visitor, body)) => arm_visitor.visit_tail_statement(body),
+        None => Ok(Tail::Value(Object::Null)),
+      },
+      expression => Ok(Tail::Value(self.visit_expression(expression)?)),
+    }
   }
 
   fn visit_for(&self, variable: &str, iterable: &Expression, block: &Statement) -> Result<Object> {
-    let array = self.visit_expression(iterable)?;
+    let items: Box<dyn Iterator<Item = Object>> = match self.visit_expression(iterable)? {
+      Object::Array(arr) => Box::new(arr.into_iter()),
+      Object::Native(native) => match native.iter() {
+        Some(iter) => iter,
+        None => return Err(Error::type_error("an array", Object::Native(native))),
+      },
+      obj => return Err(Error::type_error("an array", obj)),
+    };
+
     let mut evaluated = Object::Null;
-    if let Object::Array(arr) = array {
-      for i in arr {
-        self.env.set(variable, i);
-        evaluated = self.visit_statement(block)?;
+    for i in items {
+      self.env.define(variable, i)?;
+      evaluated = self.visit_statement(block)?;
+      if let Object::Return(..) = evaluated {
+        break;
       }
-      Ok(evaluated)
-    } else {
-      Err(Error::type_error("an array", array))
     }
+    Ok(evaluated)
   }
 
   fn visit_while(&self, condition: &Expression, block: &Statement) -> Result<Object> {
     let mut response = Object::Null;
     while self.visit_expression(condition)?.is_truthy() {
       response = self.visit_statement(block)?;
+      if let Object::Return(..) = response {
+        break;
+      }
     }
     Ok(response)
   }
 
   fn visit_variable_declaration(&self, name: &str, expression: &Expression) -> Result<Object> {
     let value = self.visit_expression(expression)?;
-    self.env.set(&name, value);
+    self.env.define(name, value)?;
+    Ok(Object::Null)
+  }
+
+  fn visit_const_declaration(&self, name: &str, expression: &Expression) -> Result<Object> {
+    let value = self.visit_expression(expression)?;
+    self.env.define_const(name, value)?;
     Ok(Object::Null)
   }
 
@@ -85,22 +216,39 @@ impl Visitor {
   }
 
   fn visit_expression(&self, expression: &Expression) -> Result<Object> {
+    self.limits.step()?;
     match expression {
       Expression::Null => Ok(Object::Null),
       Expression::Hash(hash) => self.visit_hash(hash),
       Expression::Index(indexed, indexer) => self.visit_index(indexed, indexer),
-      Expression::Array(expressions) => Ok(Object::Array(self.visit_expressions(expressions)?)),
+      Expression::Property(object, name) => self.visit_property(object, name),
+      Expression::Array(expressions) => self
+        .limits
+        .check_object(Object::Array(self.visit_expressions(expressions)?)),
       Expression::Boolean(value) => Ok(Object::boolean(*value)),
       Expression::Integer(value) => Ok(Object::Integer(*value)),
-      Expression::Call(function, args) => self.visit_call(&function, args),
-      Expression::Function(name, args, block) => self.visit_function_declaration(name, args, block),
-      Expression::Id(name) => self.visit_variable(&name),
-      Expression::Conditional(condition, consequence, alternative) => {
-        self.visit_conditional(&condition, &consequence, alternative)
+      Expression::Float(value) => Ok(Object::Float(*value)),
+      Expression::Call(function, args, span) => self.visit_call(function, args, *span),
+      Expression::Function(name, args, block, _) => {
+        self.visit_function_declaration(name, args, block)
+      }
+      Expression::Id(name, _) => self.visit_variable(name),
+      Expression::Local(name, depth, slot) => match self.env.get_slot(*depth, *slot) {
+        Some(value) => Ok(value),
+        None => self.visit_variable(name),
+      },
+      Expression::Conditional(condition, consequence, alternative, _) => {
+        self.visit_conditional(condition, consequence, alternative)
+      }
+      Expression::Match(value, arms) => self.visit_match(value, arms),
+      Expression::Spread(..) | Expression::Named(..) => {
+        Err(Error::unexpected_argument(expression.clone()))
       }
-      Expression::Infix(infix, left, right) => self.visit_infix(&infix, &left, &right),
-      Expression::Prefix(prefix, expression) => self.visit_prefix(&prefix, &expression),
+      Expression::Infix(infix, left, right) => self.visit_infix(infix, left, right),
+      Expression::Prefix(prefix, expression) => self.visit_prefix(prefix, expression),
       Expression::String(value) => Ok(Object::String(value.clone())),
+      Expression::Color(color) => Ok(Object::Color(*color)),
+      Expression::Regex(regex) => Ok(Object::Regex(regex.clone())),
     }
   }
 
@@ -118,31 +266,148 @@ impl Visitor {
     )
   }
 
+  fn visit_property(&self, object: &Expression, name: &str) -> Result<Object> {
+    // Hashes held in variables (such as `math`) are read in place instead of
+    // being cloned for every property access.
+    let property = |value: &Object| match value {
+      Object::Hash(hash) => Some(hash.get(name).cloned().unwrap_or(Object::Null)),
+      _ => None,
+    };
+    let borrowed = match object {
+      Expression::Id(id, _) => self.env.inspect(id, property),
+      Expression::Local(id, depth, slot) => self
+        .env
+        .inspect_slot(*depth, *slot, property)
+        .or_else(|| self.env.inspect(id, property)),
+      _ => None,
+    };
+    if let Some(Some(value)) = borrowed {
+      return Ok(value);
+    }
+
+    match self.visit_expression(object)? {
+      Object::Hash(hash) => Ok(hash.get(name).cloned().unwrap_or(Object::Null)),
+      Object::Color(color) => color
+        .get(name)
+        .ok_or_else(|| Error::unknown_property("color", name)),
+      Object::Native(native) => match native.get(name) {
+        Some(value) => Ok(value),
+        None if native.has_method(name) => Ok(bind_method(native, name)),
+        None => Err(Error::unknown_property(native.type_name(), name)),
+      },
+      obj => Err(Error::unknown_property(&obj.to_string(), name)),
+    }
+  }
+
   fn visit_hash(&self, key_values: &[(Expression, Expression)]) -> Result<Object> {
     let mut hash = HashMap::new();
     for (key_expression, value_expression) in key_values {
-      let key = match self.visit_expression(&key_expression)? {
-        Object::Boolean(b) => b.to_string(),
-        Object::Integer(i) => i.to_string(),
-        Object::String(s) => s,
-        obj => return Err(Error::index_error(Object::Hash(hash), obj)),
+      let key = self.visit_expression(key_expression)?;
+      let key = match key.hash_key() {
+        Some(key) => key,
+        None => return Err(Error::index_error(Object::Hash(hash), key)),
       };
-      hash.insert(key, self.visit_expression(&value_expression)?);
+      hash.insert(key, self.visit_expression(value_expression)?);
+    }
+
+    self.limits.check_object(Object::Hash(hash))
+  }
+
+  fn visit_call(
+    &self,
+    function: &Expression,
+    arg_values: &[Expression],
+    span: Span,
+  ) -> Result<Object> {
+    let call = self.visit_arguments(function, arg_values, span)?;
+    self.call(call)
+  }
+
+  fn visit_arguments(
+    &self,
+    function: &Expression,
+    arg_values: &[Expression],
+    span: Span,
+  ) -> Result<Call> {
+    let callee = self.visit_expression(function)?;
+    let mut args = vec![];
+    let mut named = vec![];
+    for arg in arg_values {
+      match arg {
+        Expression::Named(key, value) => named.push((key.clone(), self.visit_expression(value)?)),
+        arg => self.visit_list_item(arg, &mut args)?,
+      }
     }
 
-    Ok(Object::Hash(hash))
+    let name = match (&callee, function) {
+      (Object::Function(Some(name), ..), _) => name.clone(),
+      (_, Expression::Id(id, _)) | (_, Expression::Local(id, ..)) => id.clone(),
+      (_, Expression::Property(_, name)) => name.clone(),
+      _ => "anonymous function".to_owned(),
+    };
+
+    Ok(Call {
+      function: callee,
+      name,
+      args,
+      named,
+      span,
+    })
+  }
+
+  pub fn call_function(&self, function: Object, args: Vec<Object>) -> Result<Object> {
+    self.call_at(function, args, Span::default())
   }
 
-  fn visit_call(&self, function: &Expression, arg_values: &[Expression]) -> Result<Object> {
-    let function = self.visit_expression(function)?;
-    let args = self.visit_expressions(arg_values)?;
+  pub(super) fn call_at(&self, function: Object, args: Vec<Object>, span: Span) -> Result<Object> {
+    let name = match &function {
+      Object::Function(Some(name), ..) => name.clone(),
+      _ => "anonymous function".to_owned(),
+    };
 
-    match function {
-      Object::Function(arg_names, block, env) => {
-        self.visit_function_call(arg_names, args, block.as_ref(), env)
+    self.call(Call {
+      function,
+      name,
+      args,
+      named: vec![],
+      span,
+    })
+  }
+
+  fn call(&self, call: Call) -> Result<Object> {
+    let depth = self.limits.enter()?;
+    let result = stacker::maybe_grow(STACK_RED_ZONE, STACK_GROWTH, || self.trampoline(call));
+    self.limits.leave(depth);
+    result
+  }
+
+  fn trampoline(&self, mut call: Call) -> Result<Object> {
+    loop {
+      let span = call.span;
+      match call.function {
+        Object::Function(_, parameters, block, env) => {
+          let child_env = env::local(env);
+          self
+            .bind_arguments(&call.name, &parameters, call.args, call.named, &child_env)
+            .map_err(|err| err.at(span))?;
+          match self.scoped(child_env).visit_tail_statement(&block)? {
+            Tail::Call(next) => call = next,
+            Tail::Value(Object::Return(value)) => return Ok(*value),
+            Tail::Value(value) => return Ok(value),
+          }
+        }
+        Object::BuiltIn(builtin) if call.named.is_empty() => {
+          let context = Context::new(self, &call.name, call.span);
+          return builtin
+            .call(&context, call.args)
+            .and_then(|result| self.limits.check_object(result))
+            .map_err(|err| err.at(span));
+        }
+        Object::BuiltIn(..) => {
+          return Err(Error::unexpected_named_argument(&call.function).at(call.span))
+        }
+        function => return Err(Error::call_error(function).at(call.span)),
       }
-      Object::BuiltIn(function) => function(args),
-      _ => Err(Error::call_error(function)),
     }
   }
 
@@ -152,39 +417,84 @@ impl Visitor {
   ) -> std::result::Result<Vec<Object>, Error> {
     let mut results = vec![];
     for expression in expressions {
-      results.push(self.visit_expression(&expression)?);
+      self.visit_list_item(expression, &mut results)?;
     }
     Ok(results)
   }
 
-  fn visit_function_call(
+  fn visit_list_item(&self, expression: &Expression, results: &mut Vec<Object>) -> Result<()> {
+    match expression {
+      Expression::Spread(spread) => match self.visit_expression(spread)? {
+        Object::Array(items) => results.extend(items),
+        obj => return Err(Error::type_error("an array", obj)),
+      },
+      expression => results.push(self.visit_expression(expression)?),
+    }
+    Ok(())
+  }
+
+  fn bind_arguments(
     &self,
-    arg_names: Vec<String>,
-    arg_values: Vec<Object>,
-    block: &Statement,
-    env: env::Env,
-  ) -> Result<Object> {
-    if arg_names.len() != arg_values.len() {
-      return Err(Error::wrong_parameters(arg_names.len(), arg_values.len()));
+    name: &str,
+    parameters: &[Parameter],
+    args: Vec<Object>,
+    named: Vec<(String, Object)>,
+    env: &env::Env,
+  ) -> Result<()> {
+    let got = args.len();
+    let mut values: Vec<Option<Object>> = vec![None; parameters.len()];
+    let mut args = args.into_iter();
+
+    for (value, parameter) in values.iter_mut().zip(parameters) {
+      *value = if parameter.rest {
+        Some(Object::Array(args.by_ref().collect()))
+      } else {
+        args.next()
+      };
+    }
+
+    if args.next().is_some() {
+      return Err(Error::too_many_arguments(name, parameters.len(), got));
+    }
+
+    for (key, value) in named {
+      match parameters
+        .iter()
+        .position(|parameter| parameter.name == key && !parameter.rest)
+      {
+        Some(i) if values[i].is_none() => values[i] = Some(value),
+        Some(_) => return Err(Error::duplicate_argument(name, &key)),
+        None => return Err(Error::unknown_argument(name, &key)),
+      }
     }
-    let child_env = env::local(env);
-    for i in 0..arg_names.len() {
-      child_env.set(&arg_names[i], arg_values[i].clone())
+
+    let visitor = self.scoped(env.clone());
+    for (slot, (value, parameter)) in values.into_iter().zip(parameters).enumerate() {
+      let value = match (value, &parameter.default) {
+        (Some(value), _) => value,
+        (None, Some(default)) => visitor.visit_expression(default)?,
+        (None, None) => return Err(Error::missing_argument(name, &parameter.name)),
+      };
+      env.define_slot(slot, &parameter.name, value)?;
     }
-    let sub_visitor = Visitor::from(child_env);
-    sub_visitor.visit_statement(block)
+
+    Ok(())
   }
 
   fn visit_function_declaration(
     &self,
     name: &Option<String>,
-    args: &[String],
+    parameters: &[Parameter],
     block: &Rc<Statement>,
   ) -> Result<Object> {
-    let function = Object::Function(args.to_owned(), block.clone(), self.env.clone());
-    match name {
-      Some(value) => self.env.set(&value, function.clone()),
-      None => (),
+    let function = Object::Function(
+      name.clone(),
+      parameters.to_owned(),
+      block.clone(),
+      self.env.clone(),
+    );
+    if let Some(value) = name {
+      self.env.define(value, function.clone())?
     };
 
     Ok(function)
@@ -207,12 +517,96 @@ impl Visitor {
       self.visit_statement(consequence)
     } else {
       match alternative {
-        Some(statement) => self.visit_statement(&statement),
+        Some(statement) => self.visit_statement(statement),
         None => Ok(Object::Null),
       }
     }
   }
 
+  fn visit_match(&self, value: &Expression, arms: &[MatchArm]) -> Result<Object> {
+    match self.select_arm(value, arms)? {
+      Some((arm_visitor, body)) => arm_visitor.visit_statement(body),
+      None => Ok(Object::Null),
+    }
+  }
+
+  fn select_arm<'a>(
+    &self,
+    value: &Expression,
+    arms: &'a [MatchArm],
+  ) -> Result<Option<(Visitor, &'a Statement)>> {
+    let value = self.visit_expression(value)?;
+
+    for arm in arms {
+      let arm_visitor = self.scoped(env::local(self.env.clone()));
+      if !arm_visitor.match_pattern(&arm.pattern, &value)? {
+        continue;
+      }
+
+      if let Some(guard) = &arm.guard {
+        if !arm_visitor.visit_expression(guard)?.is_truthy() {
+          continue;
+        }
+      }
+
+      return Ok(Some((arm_visitor, &arm.body)));
+    }
+
+    Ok(None)
+  }
+
+  fn match_pattern(&self, pattern: &Pattern, value: &Object) -> Result<bool> {
+    Ok(match (pattern, value) {
+      (Pattern::Wildcard, _) => true,
+      (Pattern::Binding(name), value) => {
+        self.env.define(name, value.clone())?;
+        true
+      }
+      (Pattern::Literal(literal), value) => &self.visit_expression(literal)? == value,
+      (Pattern::Or(patterns), value) => {
+        for pattern in patterns {
+          if self.match_pattern(pattern, value)? {
+            return Ok(true);
+          }
+        }
+        false
+      }
+      (Pattern::Array(patterns, rest), Object::Array(items)) => {
+        let length_matches = match rest {
+          Some(_) => items.len() >= patterns.len(),
+          None => items.len() == patterns.len(),
+        };
+        if !length_matches {
+          return Ok(false);
+        }
+
+        for (pattern, item) in patterns.iter().zip(items) {
+          if !self.match_pattern(pattern, item)? {
+            return Ok(false);
+          }
+        }
+
+        match rest {
+          Some(name) if name != "_" => self
+            .env
+            .define(name, Object::Array(items[patterns.len()..].to_vec()))?,
+          _ => (),
+        }
+        true
+      }
+      (Pattern::Hash(entries), Object::Hash(hash)) => {
+        for (key, pattern) in entries {
+          match hash.get(key) {
+            Some(item) if self.match_pattern(pattern, item)? => (),
+            _ => return Ok(false),
+          }
+        }
+        true
+      }
+      _ => false,
+    })
+  }
+
   fn visit_infix(
     &self,
     infix: &str,
@@ -223,12 +617,19 @@ impl Visitor {
 
     if infix == "=" {
       match left_expression {
-        Expression::Id(id) => {
-          self.env.update(&id, right);
+        Expression::Id(id, _) => {
+          self.env.update(id, right)?;
+        }
+        Expression::Local(id, depth, slot) => {
+          self.env.update_slot(*depth, *slot, id, right)?;
         }
         Expression::Index(indexed, index) => {
           self.visit_index_assign(indexed, index, right)?;
         }
+        Expression::Property(object, name) => match self.visit_expression(object)? {
+          Object::Native(native) => native.set(name, right)?,
+          obj => return Err(Error::cannot_assign(obj)),
+        },
         _ => {
           return Err(Error::cannot_assign(
             self.visit_expression(left_expression)?,
@@ -239,28 +640,33 @@ impl Visitor {
     }
 
     let left = self.visit_expression(left_expression)?;
-    Ok(match infix {
-      "+" => left + right,
-      "*" => left * right,
+    let result = match infix {
+      "+" => (left + right)?,
+      "*" => (left * right)?,
       "==" => Object::boolean(left == right),
       "!=" => Object::boolean(left != right),
-      ">" => Object::boolean(left > right),
-      "<" => Object::boolean(left < right),
-      "-" => left - right,
-      "/" => left / right,
+      "=~" => match (left, right) {
+        (Object::String(string), Object::Regex(regex)) => Object::boolean(regex.is_match(&string)),
+        (left, right) => return Err(Error::type_mismatch("=~", left, right)),
+      },
+      ">" => Object::boolean(left.compare_to(&right)? == Some(Ordering::Greater)),
+      "<" => Object::boolean(left.compare_to(&right)? == Some(Ordering::Less)),
+      "-" => (left - right)?,
+      "/" => (left / right)?,
       _ => return Err(Error::unknown_operator(infix, left)),
-    })
+    };
+    self.limits.check_object(result)
   }
 
   fn visit_index_assign(
     &self,
-    indexed: &Box<Expression>,
-    index: &Box<Expression>,
+    indexed: &Expression,
+    index: &Expression,
     value: Object,
   ) -> Result<Object> {
     match (
-      self.visit_expression(&indexed)?,
-      self.visit_expression(&index)?,
+      self.visit_expression(indexed)?,
+      self.visit_expression(index)?,
     ) {
       (Object::Array(mut arr), Object::Integer(int)) => {
         arr[int as usize] = value;
@@ -278,8 +684,13 @@ impl Visitor {
     let obj = self.visit_expression(expression)?;
     Ok(match prefix {
       "!" => !obj,
-      "-" => -obj,
+      "-" => (-obj)?,
       _ => return Err(Error::unknown_operator(prefix, obj)),
     })
   }
 }
+
+fn bind_method(native: Rc<dyn HostObject>, method: &str) -> Object {
+  let method = method.to_owned();
+  Object::builtin(move |context, args| native.call_method(context, &method, args))
+}
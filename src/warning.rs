@@ -0,0 +1,88 @@
+use crate::ast::Span;
+use std::fmt;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Warning {
+  message: String,
+  _type: WarningType,
+  location: Option<Span>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum WarningType {
+  NonExhaustiveMatch,
+  AmbiguousContinuation,
+}
+
+impl Warning {
+  pub fn non_exhaustive_match() -> Self {
+    Self {
+      message: "match expression has no '_' arm, unmatched values evaluate to null".to_owned(),
+      _type: WarningType::NonExhaustiveMatch,
+      location: None,
+    }
+  }
+
+  pub fn ambiguous_continuation(token: &str) -> Self {
+    Self {
+      message: format!(
+        "'{}' at the start of a line begins a new statement, end the previous line with ';' or move '{}' up to continue it",
+        token, token
+      ),
+      _type: WarningType::AmbiguousContinuation,
+      location: None,
+    }
+  }
+
+  pub fn detached_return_value(token: &str) -> Self {
+    Self {
+      message: format!(
+        "'{}' on the line after 'return' is not returned, move it up to the 'return' line to return it",
+        token
+      ),
+      _type: WarningType::AmbiguousContinuation,
+      location: None,
+    }
+  }
+
+  pub fn at(mut self, span: Span) -> Self {
+    if self.location.is_none() && span.is_known() {
+      self.location = Some(span);
+    }
+    self
+  }
+
+  pub fn message(&self) -> &str {
+    &self.message
+  }
+
+  pub fn location(&self) -> Option<Span> {
+    self.location
+  }
+
+  pub fn warning_type(&self) -> &WarningType {
+    &self._type
+  }
+
+  fn type_string(&self) -> &str {
+    match self._type {
+      WarningType::NonExhaustiveMatch => "NonExhaustiveMatch",
+      WarningType::AmbiguousContinuation => "AmbiguousContinuation",
+    }
+  }
+}
+
+impl fmt::Display for Warning {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    match self.location {
+      Some(span) => write!(
+        f,
+        "Warning {} at {}:\n\t{}",
+        self.type_string(),
+        span,
+        self.message
+      ),
+      None => write!(f, "Warning {}:\n\t{}", self.type_string(), self.message),
+    }
+  }
+}
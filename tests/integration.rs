@@ -1,4 +1,6 @@
-use helium::{helium::run, object::Object};
+use helium::{
+  error::ErrorType, helium::run, lexer::lex, object::Object, parser::Parser, visitor::Visitor,
+};
 
 #[test]
 fn book_name() {
@@ -64,3 +66,196 @@ fn fibonacci() {
     ])
   )
 }
+
+#[test]
+fn match_expression() {
+  let input = String::from(
+    "
+    fn fibonacci(x) {
+      match x {
+        0 | 1 => x,
+        n => fibonacci(n - 1) + fibonacci(n - 2)
+      }
+    }
+
+    fn describe(value) {
+      match value {
+        [] => 'empty',
+        [first, ...rest] => 'list starting with ' + first,
+        {type: 'user', name} => 'user ' + name,
+        n if n > 10 => 'big',
+        _ => 'other'
+      }
+    }
+
+    let results = [
+      fibonacci(10),
+      describe([]),
+      describe(['a', 'b']),
+      describe({'type': 'user', 'name': 'leo'}),
+      describe(42),
+      describe(1)
+    ]
+    results
+  ",
+  );
+
+  let result = run(&input).unwrap();
+
+  assert_eq!(
+    result,
+    Object::Array(vec![
+      Object::Integer(55),
+      Object::String("empty".to_owned()),
+      Object::String("list starting with a".to_owned()),
+      Object::String("user leo".to_owned()),
+      Object::String("big".to_owned()),
+      Object::String("other".to_owned())
+    ])
+  )
+}
+
+#[test]
+fn function_parameters() {
+  let input = String::from(
+    "
+    fn point(x, y = 10, ...others) {
+      [x, y, others]
+    }
+
+    let args = [1, 2, 3]
+    let results = [point(1), point(y: 2, x: 1), point(...args), point(0, ...args)]
+    results
+  ",
+  );
+
+  let result = run(&input).unwrap();
+
+  let point = |x, y, others: Vec<i64>| {
+    Object::Array(vec![
+      Object::Integer(x),
+      Object::Integer(y),
+      Object::Array(others.into_iter().map(Object::Integer).collect()),
+    ])
+  };
+
+  assert_eq!(
+    result,
+    Object::Array(vec![
+      point(1, 10, vec![]),
+      point(1, 2, vec![]),
+      point(1, 2, vec![3]),
+      point(0, 1, vec![2, 3]),
+    ])
+  )
+}
+
+#[test]
+fn wrong_parameters() {
+  for input in [
+    "fn add(x, y) x + y; add(1)",
+    "fn add(x, y) x + y; add(1, 2, 3)",
+    "fn add(x, y) x + y; add(1, z: 2)",
+    "fn add(x, y) x + y; add(1, x: 2)",
+  ] {
+    let errors = run(input).unwrap_err();
+    assert_eq!(errors[0].error_type(), &ErrorType::WrongParameters);
+    assert!(errors[0].to_string().contains("add"));
+  }
+}
+
+#[test]
+fn lambdas() {
+  let input = String::from(
+    "
+    let offset = 10
+    let add = |a, b| a + b
+    let results = map([1, 2, 3], |x| {
+      let doubled = x * 2
+      add(doubled, offset)
+    })
+    results
+  ",
+  );
+
+  let result = run(&input).unwrap();
+
+  assert_eq!(
+    result,
+    Object::Array(vec![
+      Object::Integer(12),
+      Object::Integer(14),
+      Object::Integer(16)
+    ])
+  )
+}
+
+#[test]
+fn tail_calls_do_not_grow_the_stack() {
+  let input = "
+    fn count(n, acc) {
+      if n == 0 return acc
+      count(n - 1, acc + 1)
+    }
+
+    fn range(n, acc = []) {
+      if n == 0 return acc
+      range(n - 1, push(acc, n))
+    }
+
+    let doubled = map(range(3000), |x| x * 2)
+    let results = [count(100000, 0), len(doubled), first(doubled)]
+    results
+  ";
+
+  let visitor = Visitor::new();
+  visitor.set_max_depth(50);
+  let program = Parser::new(lex(input)).parse();
+
+  assert_eq!(
+    visitor.visit(&program).unwrap(),
+    Object::Array(vec![
+      Object::Integer(100000),
+      Object::Integer(3000),
+      Object::Integer(6000)
+    ])
+  )
+}
+
+#[test]
+fn deep_recursion_raises_stack_overflow() {
+  let input = "
+    fn sum(n) {
+      if n == 0 { 0 } else { n + sum(n - 1) }
+    }
+    sum(1000)
+  ";
+
+  let visitor = Visitor::new();
+  visitor.set_max_depth(100);
+  let program = Parser::new(lex(input)).parse();
+  let error = visitor.visit(&program).unwrap_err();
+
+  assert_eq!(error.error_type(), &ErrorType::StackOverflow);
+  assert_eq!(
+    visitor.visit(&Parser::new(lex("sum(10)")).parse()).unwrap(),
+    Object::Integer(55)
+  )
+}
+
+#[test]
+fn return_from_nested_block() {
+  let input = "
+    fn find_first_even(numbers) {
+      for n in numbers {
+        if n / 2 * 2 == n {
+          return n
+        }
+      }
+      null
+    }
+    find_first_even([1, 3, 4, 5, 6])
+  ";
+
+  assert_eq!(run(input).unwrap(), Object::Integer(4))
+}